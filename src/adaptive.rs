@@ -0,0 +1,150 @@
+//! A strategy that picks the cheapest method that's good enough for each
+//! turn, so no single turn takes unpredictably long.
+
+use crate::advice::Advisor;
+use crate::ranking::{Exact, LetterFrequency, Ranker};
+use crate::strategy::{GuessRules, Strategy, StrategyError, StrategyInfo};
+use crate::word::*;
+use std::collections::HashSet;
+
+/// Above this many viable words, guesses come from the letter frequency
+/// heuristic.
+pub const FREQUENCY_ABOVE: usize = 1000;
+/// Below this many viable words, guesses come from exhaustive search.
+pub const EXACT_BELOW: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Nothing to work out: only one word was left, or the rules fix the
+    /// opener.
+    Forced,
+    Frequency,
+    Entropy,
+    Exact,
+}
+
+pub struct AdaptiveStrategy<const WORD_LENGTH: usize> {
+    word_list: WordList<WORD_LENGTH>,
+    viable_words: WordList<WORD_LENGTH>,
+    guessed: HashSet<Word<WORD_LENGTH>>,
+    last_guess: Option<Word<WORD_LENGTH>>,
+    last_method: Option<Method>,
+    rules: GuessRules<WORD_LENGTH>,
+}
+
+impl<const WORD_LENGTH: usize> AdaptiveStrategy<WORD_LENGTH> {
+    /// Searches exactly once few enough words are left.
+    pub const INFO: StrategyInfo = StrategyInfo {
+        supports_hard_mode: true,
+        deterministic: true,
+        needs_score_matrix: false,
+        approximate: false,
+    };
+
+    pub fn new(word_list: WordList<WORD_LENGTH>) -> Self {
+        Self {
+            viable_words: word_list.clone(),
+            word_list,
+            guessed: HashSet::new(),
+            last_guess: None,
+            last_method: None,
+            rules: GuessRules::default(),
+        }
+    }
+
+    /// Like [`AdaptiveStrategy::new`], but guesses from `guess_list` while only
+    /// considering words in `answer_list` as the secret.
+    pub fn with_guess_list(
+        guess_list: WordList<WORD_LENGTH>,
+        answer_list: WordList<WORD_LENGTH>,
+    ) -> Self {
+        Self {
+            viable_words: answer_list,
+            ..Self::new(guess_list)
+        }
+    }
+
+    pub fn set_guess_rules(&mut self, rules: GuessRules<WORD_LENGTH>) {
+        self.rules = rules;
+    }
+
+    /// The method used for the most recent guess.
+    pub fn last_method(&self) -> Option<Method> {
+        self.last_method
+    }
+
+    /// The method for a turn with `viable` words left.
+    pub fn method_for(viable: usize) -> Method {
+        match viable {
+            0 | 1 => Method::Forced,
+            n if n > FREQUENCY_ABOVE => Method::Frequency,
+            n if n >= EXACT_BELOW => Method::Entropy,
+            _ => Method::Exact,
+        }
+    }
+}
+
+impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for AdaptiveStrategy<WORD_LENGTH> {
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        if self.viable_words.0.is_empty() {
+            return Err(StrategyError::NeedsReset);
+        }
+        let mut candidates = self.word_list.clone();
+        candidates
+            .0
+            .retain(|word| !self.guessed.contains(word) && self.rules.allows(word));
+        let (method, ranking) = match self.rules.always_open {
+            Some(opener) if self.guessed.is_empty() => (Method::Forced, vec![opener]),
+            _ => {
+                let method = Self::method_for(self.viable_words.0.len());
+                let ranking = match method {
+                    Method::Forced => {
+                        let mut viable = self.viable_words.0.clone();
+                        viable.retain(|word| self.rules.allows(word));
+                        viable
+                    }
+                    Method::Frequency => LetterFrequency.rank(&candidates, &self.viable_words),
+                    Method::Entropy => Advisor::Entropy.rank(&candidates, &self.viable_words),
+                    Method::Exact => Exact::default().rank(&candidates, &self.viable_words),
+                };
+                if ranking.is_empty() {
+                    // The last word is off limits, so the best we can do is
+                    // probe.
+                    (
+                        Method::Entropy,
+                        Advisor::Entropy.rank(&candidates, &self.viable_words),
+                    )
+                } else {
+                    (method, ranking)
+                }
+            }
+        };
+        let guess = *ranking.first().ok_or(StrategyError::OutOfCandidates)?;
+        self.guessed.insert(guess);
+        self.last_guess = Some(guess);
+        self.last_method = Some(method);
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        let last_guess = self.last_guess.expect("should've made a guess by now");
+        self.viable_words.retain_viable_words(&last_guess, score);
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        if let Some(suggested) = self.last_guess.replace(*played) {
+            self.guessed.remove(&suggested);
+        }
+        self.guessed.insert(*played);
+        Ok(())
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.viable_words.retain_fitting(clues);
+        for clue in clues {
+            if let Clue::Scored(guess, _) = clue {
+                self.guessed.insert(*guess);
+            }
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Recommendations from two different ways of picking a guess, side by side,
+//! so a player can see when the approaches disagree and what each is after.
+
+use crate::trace::CandidateStats;
+use crate::word::*;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Advisor {
+    /// Picks the guess with the most expected information.
+    Entropy,
+    /// Picks the guess that leaves the fewest words in the worst case.
+    Minimax,
+}
+
+impl Advisor {
+    /// Orders candidates from worst to best by this advisor's measure. Ties
+    /// go to guesses that could be the answer, then to the other measure.
+    pub(crate) fn compare<const WORD_LENGTH: usize>(
+        self,
+        a: &CandidateStats<WORD_LENGTH>,
+        b: &CandidateStats<WORD_LENGTH>,
+    ) -> Ordering {
+        let by_entropy = a.entropy.total_cmp(&b.entropy);
+        let by_worst_case = b.worst_case.cmp(&a.worst_case);
+        let (first, second) = match self {
+            Self::Entropy => (by_entropy, by_worst_case),
+            Self::Minimax => (by_worst_case, by_entropy),
+        };
+        first.then(a.viable.cmp(&b.viable)).then(second)
+    }
+
+    /// The best candidate by this advisor's measure; the earliest one wins
+    /// exact ties.
+    pub fn pick<const WORD_LENGTH: usize>(
+        self,
+        candidates: &[CandidateStats<WORD_LENGTH>],
+    ) -> Option<&CandidateStats<WORD_LENGTH>> {
+        candidates.iter().rev().max_by(|a, b| self.compare(a, b))
+    }
+}
+
+impl fmt::Display for Advisor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Entropy => "entropy",
+            Self::Minimax => "minimax",
+        })
+    }
+}
+
+impl FromStr for Advisor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "entropy" => Ok(Self::Entropy),
+            "minimax" => Ok(Self::Minimax),
+            _ => Err(format!(
+                "unknown advisor {:?} (expected entropy or minimax)",
+                s
+            )),
+        }
+    }
+}
+
+/// Two advisors' picks for the same position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison<const WORD_LENGTH: usize> {
+    pub picks: [(Advisor, CandidateStats<WORD_LENGTH>); 2],
+}
+
+impl<const WORD_LENGTH: usize> Comparison<WORD_LENGTH> {
+    /// Has both advisors choose from `guess_list`, given the words that are
+    /// still viable. Returns `None` if there's nothing to choose from.
+    pub fn new(
+        advisors: [Advisor; 2],
+        guess_list: &WordList<WORD_LENGTH>,
+        viable_words: &WordList<WORD_LENGTH>,
+    ) -> Option<Self> {
+        // Both advisors look at the same numbers, so work them out once.
+        let candidates = guess_list
+            .0
+            .iter()
+            .map(|word| CandidateStats::new(*word, viable_words))
+            .collect::<Vec<_>>();
+        let [first, second] = advisors;
+        Some(Self {
+            picks: [
+                (first, *first.pick(&candidates)?),
+                (second, *second.pick(&candidates)?),
+            ],
+        })
+    }
+
+    pub fn agree(&self) -> bool {
+        self.picks[0].1.word == self.picks[1].1.word
+    }
+}
+
+impl<const WORD_LENGTH: usize> fmt::Display for Comparison<WORD_LENGTH> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (advisor, stats) in &self.picks {
+            writeln!(
+                f,
+                "  {}: {} ({:.2} bits, {} left at worst{})",
+                advisor,
+                stats.word,
+                stats.entropy,
+                stats.worst_case,
+                if stats.viable {
+                    ", could be the answer"
+                } else {
+                    ""
+                }
+            )?;
+        }
+        if self.agree() {
+            return writeln!(f, "  Both advisors agree.");
+        }
+
+        let [(_, a), (_, b)] = &self.picks;
+        let (more_info, less_info) = if a.entropy >= b.entropy {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        write!(
+            f,
+            "  {} expects {:.2} more bits",
+            more_info.word,
+            more_info.entropy - less_info.entropy
+        )?;
+        let (safer, riskier) = if a.worst_case <= b.worst_case {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        if safer.worst_case < riskier.worst_case {
+            write!(
+                f,
+                "; {} leaves {} fewer words at worst",
+                safer.word,
+                riskier.worst_case - safer.worst_case
+            )?;
+        }
+        if a.viable != b.viable {
+            let viable = if a.viable { a } else { b };
+            write!(f, "; only {} could win this turn", viable.word)?;
+        }
+        writeln!(f, ".")
+    }
+}
+
+/// A guess that splits a family of viable words, ones alike in all but one
+/// position, by testing several of the letters that could fill that position
+/// at once. Guessing the family one word at a time can take a turn for each.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disambiguator<const WORD_LENGTH: usize> {
+    pub guess: Word<WORD_LENGTH>,
+    pub family: Vec<Word<WORD_LENGTH>>,
+    /// How many of the letters telling the family apart the guess has.
+    pub letters_covered: usize,
+}
+
+impl<const WORD_LENGTH: usize> Disambiguator<WORD_LENGTH> {
+    /// The guess from `guess_list` with the most of the family's telling
+    /// letters, if `viable_words` are a family of three or more and some
+    /// guess has at least two of them. Ties go to the guess that splits the
+    /// family into more groups, then to the earliest.
+    pub fn find(
+        guess_list: &WordList<WORD_LENGTH>,
+        viable_words: &WordList<WORD_LENGTH>,
+    ) -> Option<Self> {
+        let first = viable_words.0.first()?;
+        if viable_words.0.len() < 3 {
+            return None;
+        }
+        let mut differing = (0..WORD_LENGTH).filter(|position| {
+            viable_words
+                .0
+                .iter()
+                .any(|word| word.0[*position] != first.0[*position])
+        });
+        let position = differing.next()?;
+        if differing.next().is_some() {
+            return None;
+        }
+        // A letter the family shares elsewhere shows up whichever word it
+        // is, so it tells nothing apart.
+        let shared = first
+            .0
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != position)
+            .map(|(_, letter)| *letter)
+            .collect::<HashSet<_>>();
+        let letters = viable_words
+            .0
+            .iter()
+            .map(|word| word.0[position])
+            .filter(|letter| !shared.contains(letter))
+            .collect::<HashSet<_>>();
+        let (guess, letters_covered, _) = guess_list
+            .0
+            .iter()
+            .map(|guess| {
+                let covered = HashSet::from(guess).intersection(&letters).count();
+                (*guess, covered, viable_words.partition_sizes(guess).len())
+            })
+            // `max_by_key` keeps the last of equals; go backwards so the
+            // earliest word wins exact ties.
+            .rev()
+            .max_by_key(|(_, covered, groups)| (*covered, *groups))?;
+        (letters_covered >= 2).then(|| Self {
+            guess,
+            family: viable_words.0.clone(),
+            letters_covered,
+        })
+    }
+}
+
+impl<const WORD_LENGTH: usize> fmt::Display for Disambiguator<WORD_LENGTH> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let family = self
+            .family
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        write!(f, "play '{}' to split {}", self.guess, family.join("/"))
+    }
+}
@@ -0,0 +1,221 @@
+//! Analysis of played games, singly or across a whole archive of records.
+
+use crate::nyt;
+use crate::record::{GameRecord, RecordError};
+use crate::word::*;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameAnalysis {
+    pub guesses: usize,
+    pub won: bool,
+    /// Average information gained per guess, in bits: how sharply each guess
+    /// cut down the words that were still possible.
+    pub bits_per_guess: f64,
+}
+
+pub fn analyze_game<const WORD_LENGTH: usize>(
+    record: &GameRecord<WORD_LENGTH>,
+    word_list: &WordList<WORD_LENGTH>,
+) -> GameAnalysis {
+    let mut viable_words = word_list.clone();
+    let mut bits = 0.0;
+    for row in record.board.rows() {
+        let before = viable_words.0.len();
+        viable_words.retain_viable_words(&row.guess, &row.score);
+        let after = viable_words.0.len();
+        // An inconsistent record (or a secret missing from the list) leaves
+        // nothing viable; there's no information to measure past that point.
+        if after == 0 {
+            break;
+        }
+        bits += (before as f64 / after as f64).log2();
+    }
+
+    let guesses = record.board.len();
+    GameAnalysis {
+        guesses,
+        won: record.won(),
+        bits_per_guess: if guesses == 0 {
+            0.0
+        } else {
+            bits / guesses as f64
+        },
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PeriodStats {
+    pub games: usize,
+    pub wins: usize,
+    /// Guesses used across won games.
+    pub guesses_in_wins: usize,
+    pub total_bits_per_guess: f64,
+}
+
+impl PeriodStats {
+    fn add(&mut self, analysis: &GameAnalysis) {
+        self.games += 1;
+        if analysis.won {
+            self.wins += 1;
+            self.guesses_in_wins += analysis.guesses;
+        }
+        self.total_bits_per_guess += analysis.bits_per_guess;
+    }
+
+    /// `None` until a game has been played.
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.games > 0).then(|| self.wins as f64 / self.games as f64)
+    }
+
+    /// `None` until a game has been won.
+    pub fn average_guesses(&self) -> Option<f64> {
+        (self.wins > 0).then(|| self.guesses_in_wins as f64 / self.wins as f64)
+    }
+
+    /// `None` until a game has been played.
+    pub fn average_bits_per_guess(&self) -> Option<f64> {
+        (self.games > 0).then(|| self.total_bits_per_guess / self.games as f64)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ArchiveSummary {
+    pub overall: PeriodStats,
+    /// Stats for each `YYYY-MM`, in order, showing how skill trends over time.
+    /// Undated games only count towards `overall`.
+    pub months: BTreeMap<String, PeriodStats>,
+    /// Opening guesses with how often they were played, most frequent first.
+    pub openers: Vec<(String, usize)>,
+    /// Puzzles whose recorded answer disagrees with the known NYT answer,
+    /// which usually means a mistyped record.
+    pub answer_mismatches: Vec<u32>,
+}
+
+impl ArchiveSummary {
+    pub fn from_records<'a, I, const WORD_LENGTH: usize>(
+        records: I,
+        word_list: &WordList<WORD_LENGTH>,
+    ) -> Self
+    where
+        I: IntoIterator<Item = &'a GameRecord<WORD_LENGTH>>,
+    {
+        let mut summary = Self::default();
+        let mut openers = HashMap::<String, usize>::new();
+        for record in records {
+            let analysis = analyze_game(record, word_list);
+            summary.overall.add(&analysis);
+            if let Some(month) = record.month() {
+                summary
+                    .months
+                    .entry(month.to_owned())
+                    .or_default()
+                    .add(&analysis);
+            }
+            if let Some(first) = record.board.rows().first() {
+                *openers.entry(first.guess.into()).or_insert(0) += 1;
+            }
+            if let (Some(puzzle), Some(last)) = (record.puzzle, record.board.last()) {
+                let known = nyt::answer(puzzle);
+                if last.is_solved() && known.is_some_and(|known| known != last.guess) {
+                    summary.answer_mismatches.push(puzzle);
+                }
+            }
+        }
+
+        summary.openers = openers.into_iter().collect();
+        summary
+            .openers
+            .sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        summary
+    }
+}
+
+impl fmt::Display for ArchiveSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Stats with nothing to average over show as n/a.
+        let or_na = |value: Option<String>| value.unwrap_or_else(|| "n/a".to_owned());
+        let write_stats = |f: &mut fmt::Formatter, label: &str, stats: &PeriodStats| {
+            writeln!(
+                f,
+                "{:<8} {:>5} games  {:>6} won  {:>4} guesses  {:>4} bits/guess",
+                label,
+                stats.games,
+                or_na(stats.win_rate().map(|rate| format!("{:.1}%", 100.0 * rate))),
+                or_na(
+                    stats
+                        .average_guesses()
+                        .map(|guesses| format!("{:.2}", guesses))
+                ),
+                or_na(
+                    stats
+                        .average_bits_per_guess()
+                        .map(|bits| format!("{:.2}", bits))
+                )
+            )
+        };
+
+        write_stats(f, "overall", &self.overall)?;
+        for (month, stats) in &self.months {
+            write_stats(f, month, stats)?;
+        }
+        writeln!(f, "favorite openers:")?;
+        for (opener, count) in self.openers.iter().take(5) {
+            writeln!(f, "  {} ({})", opener, count)?;
+        }
+        for puzzle in &self.answer_mismatches {
+            writeln!(
+                f,
+                "warning: puzzle {} doesn't end on its answer, {}",
+                puzzle,
+                nyt::ANSWERS[*puzzle as usize]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum ArchiveError {
+    Io(PathBuf, std::io::Error),
+    Record(PathBuf, RecordError),
+}
+
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(path, error) => write!(f, "{}: {}", path.display(), error),
+            Self::Record(path, error) => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {}
+
+/// Loads every game record in `dir`, in file name order.
+pub fn load_archive<const WORD_LENGTH: usize>(
+    dir: &Path,
+) -> Result<Vec<GameRecord<WORD_LENGTH>>, ArchiveError> {
+    let io_error = |path: &Path| {
+        let path = path.to_owned();
+        move |error| ArchiveError::Io(path, error)
+    };
+
+    let mut paths = std::fs::read_dir(dir)
+        .map_err(io_error(dir))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error(dir))?;
+    paths.retain(|path| path.is_file());
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let text = std::fs::read_to_string(&path).map_err(io_error(&path))?;
+            GameRecord::parse(&text).map_err(|error| ArchiveError::Record(path, error))
+        })
+        .collect()
+}
@@ -0,0 +1,207 @@
+//! The header every saved artifact starts with, so data computed from one
+//! word list is never quietly used with another:
+//!
+//! ```text
+//! WSLV 1 transcript 5 9a3c0f27d1e4b865
+//! ```
+//!
+//! That's the magic bytes, the format version, what kind of artifact follows,
+//! the word length, and a hash of the word list it was computed from. The
+//! header is one line of ASCII, so it works in front of both text and binary
+//! payloads.
+
+use crate::word::*;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+pub const MAGIC: &str = "WSLV";
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+    /// Played games, as game records separated by blank lines.
+    Transcript,
+    OpeningBook,
+    ScoreMatrix,
+}
+
+impl fmt::Display for ArtifactKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Transcript => "transcript",
+            Self::OpeningBook => "opening-book",
+            Self::ScoreMatrix => "score-matrix",
+        })
+    }
+}
+
+impl FromStr for ArtifactKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match s {
+            "transcript" => Ok(Self::Transcript),
+            "opening-book" => Ok(Self::OpeningBook),
+            "score-matrix" => Ok(Self::ScoreMatrix),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ArtifactError {
+    Io(io::Error),
+    /// The data doesn't start with the magic bytes.
+    NotAnArtifact,
+    UnsupportedVersion(u32),
+    WrongKind {
+        expected: ArtifactKind,
+        found: ArtifactKind,
+    },
+    WrongWordLength {
+        expected: usize,
+        found: usize,
+    },
+    /// The artifact was made from a different word list than the one it's
+    /// being loaded with.
+    WordListMismatch {
+        expected: u64,
+        found: u64,
+    },
+    Malformed(String),
+}
+
+impl fmt::Display for ArtifactError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::NotAnArtifact => write!(f, "not a wordle-solve file (missing {} header)", MAGIC),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "format version {} isn't supported (this build reads version {})",
+                version, VERSION
+            ),
+            Self::WrongKind { expected, found } => {
+                write!(f, "expected a {}, found a {}", expected, found)
+            }
+            Self::WrongWordLength { expected, found } => write!(
+                f,
+                "made for {}-letter words, but loading {}-letter words",
+                found, expected
+            ),
+            Self::WordListMismatch { expected, found } => write!(
+                f,
+                "made from a different word list ({:016x}, current list is {:016x}); regenerate it",
+                found, expected
+            ),
+            Self::Malformed(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ArtifactError {}
+
+impl From<io::Error> for ArtifactError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub version: u32,
+    pub kind: ArtifactKind,
+    pub word_length: usize,
+    pub word_list_hash: u64,
+}
+
+impl Header {
+    pub fn new<const WORD_LENGTH: usize>(
+        kind: ArtifactKind,
+        word_list: &WordList<WORD_LENGTH>,
+    ) -> Self {
+        Self {
+            version: VERSION,
+            kind,
+            word_length: WORD_LENGTH,
+            word_list_hash: word_list.content_hash(),
+        }
+    }
+
+    pub fn write(&self, mut out: impl Write) -> io::Result<()> {
+        writeln!(
+            out,
+            "{} {} {} {} {:016x}",
+            MAGIC, self.version, self.kind, self.word_length, self.word_list_hash
+        )
+    }
+
+    /// Reads the header line, leaving `input` at the start of the payload.
+    pub fn read(mut input: impl BufRead) -> Result<Self, ArtifactError> {
+        let mut line = Vec::new();
+        input.read_until(b'\n', &mut line)?;
+        let line = std::str::from_utf8(&line).map_err(|_| ArtifactError::NotAnArtifact)?;
+        let mut fields = line.split_whitespace();
+        if fields.next() != Some(MAGIC) {
+            return Err(ArtifactError::NotAnArtifact);
+        }
+        let mut field = |name: &str| {
+            fields
+                .next()
+                .ok_or_else(|| ArtifactError::Malformed(format!("header is missing the {}", name)))
+        };
+        let malformed = |name: &str, value: &str| {
+            ArtifactError::Malformed(format!("bad {} {:?} in header", name, value))
+        };
+
+        let version = field("version")?;
+        let version = version.parse().map_err(|_| malformed("version", version))?;
+        if version != VERSION {
+            return Err(ArtifactError::UnsupportedVersion(version));
+        }
+        let kind = field("kind")?;
+        let kind = kind.parse().map_err(|_| malformed("kind", kind))?;
+        let word_length = field("word length")?;
+        let word_length = word_length
+            .parse()
+            .map_err(|_| malformed("word length", word_length))?;
+        let hash = field("word list hash")?;
+        let word_list_hash =
+            u64::from_str_radix(hash, 16).map_err(|_| malformed("word list hash", hash))?;
+
+        Ok(Self {
+            version,
+            kind,
+            word_length,
+            word_list_hash,
+        })
+    }
+
+    /// Checks that the artifact is a `kind` made from `word_list`.
+    pub fn check<const WORD_LENGTH: usize>(
+        &self,
+        kind: ArtifactKind,
+        word_list: &WordList<WORD_LENGTH>,
+    ) -> Result<(), ArtifactError> {
+        let expected_hash = word_list.content_hash();
+        if self.kind != kind {
+            Err(ArtifactError::WrongKind {
+                expected: kind,
+                found: self.kind,
+            })
+        } else if self.word_length != WORD_LENGTH {
+            Err(ArtifactError::WrongWordLength {
+                expected: WORD_LENGTH,
+                found: self.word_length,
+            })
+        } else if self.word_list_hash != expected_hash {
+            Err(ArtifactError::WordListMismatch {
+                expected: expected_hash,
+                found: self.word_list_hash,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Engines and strategies that wait on something slow, like an HTTP API or
+//! a player in a chat, without tying up a thread while they do. Every
+//! [`Engine`] and [`Strategy`] is also an [`AsyncEngine`] and an
+//! [`AsyncStrategy`] whose futures are ready at once, so the two kinds mix
+//! in one [`run_game`].
+//!
+//! Nothing here needs a particular runtime. The futures aren't required to
+//! be `Send`, so a game is played on one task.
+
+use crate::engine::{Engine, GameTranscript, GuessResult, TranscriptTurn};
+use crate::error::WordleError;
+use crate::strategy::{Strategy, StrategyError};
+use crate::word::*;
+use std::future::Future;
+
+pub trait AsyncEngine<const WORD_LENGTH: usize> {
+    /// Scores `guess`, like [`Engine::try_score_guess`].
+    fn score(
+        &self,
+        guess: &Word<WORD_LENGTH>,
+    ) -> impl Future<Output = Result<GuessResult<WORD_LENGTH>, WordleError>>;
+
+    /// Letters of the secret given away before the first guess, like
+    /// [`Engine::free_letters`].
+    fn free_letters(&self) -> Vec<FreeLetter> {
+        Vec::new()
+    }
+}
+
+impl<E, const WORD_LENGTH: usize> AsyncEngine<WORD_LENGTH> for E
+where
+    E: Engine<WORD_LENGTH>,
+{
+    fn score(
+        &self,
+        guess: &Word<WORD_LENGTH>,
+    ) -> impl Future<Output = Result<GuessResult<WORD_LENGTH>, WordleError>> {
+        std::future::ready(self.try_score_guess(guess))
+    }
+
+    fn free_letters(&self) -> Vec<FreeLetter> {
+        Engine::free_letters(self)
+    }
+}
+
+pub trait AsyncStrategy<const WORD_LENGTH: usize> {
+    /// Comes up with the next guess, like [`Strategy::make_guess`].
+    fn guess(&mut self) -> impl Future<Output = Result<Word<WORD_LENGTH>, StrategyError>>;
+
+    /// Hears what the engine made of `guess`, whatever it was; the sync
+    /// strategy's `receive_score`, `guess_rejected` and so on rolled into
+    /// one.
+    fn observe(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        result: &GuessResult<WORD_LENGTH>,
+    ) -> impl Future<Output = Result<(), StrategyError>>;
+
+    /// Like [`Strategy::prime`].
+    fn prime(&mut self, _clues: &[Clue<WORD_LENGTH>]) {}
+}
+
+impl<S, const WORD_LENGTH: usize> AsyncStrategy<WORD_LENGTH> for S
+where
+    S: Strategy<WORD_LENGTH>,
+{
+    fn guess(&mut self) -> impl Future<Output = Result<Word<WORD_LENGTH>, StrategyError>> {
+        std::future::ready(self.make_guess())
+    }
+
+    fn observe(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        result: &GuessResult<WORD_LENGTH>,
+    ) -> impl Future<Output = Result<(), StrategyError>> {
+        std::future::ready(crate::report(self, guess, result))
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        Strategy::prime(self, clues)
+    }
+}
+
+/// Plays a game like [`try_run_game`](crate::try_run_game), awaiting the
+/// engine and the strategy in turn.
+pub async fn run_game<E, S, const WORD_LENGTH: usize>(
+    engine: E,
+    mut strategy: S,
+) -> Result<GameTranscript<WORD_LENGTH>, WordleError>
+where
+    E: AsyncEngine<WORD_LENGTH>,
+    S: AsyncStrategy<WORD_LENGTH>,
+{
+    let clues = engine
+        .free_letters()
+        .into_iter()
+        .map(Clue::Letter)
+        .collect::<Vec<_>>();
+    if !clues.is_empty() {
+        strategy.prime(&clues);
+    }
+    let mut turns = Vec::new();
+    let error = loop {
+        let guess = match strategy.guess().await {
+            Ok(guess) => guess,
+            Err(error) => break error,
+        };
+        let result = engine.score(&guess).await?;
+        let observed = strategy.observe(&guess, &result).await;
+        let done = match result {
+            GuessResult::Done { won, .. } => Some(won),
+            _ => None,
+        };
+        turns.push(TranscriptTurn { guess, result });
+        if let Some(won) = done {
+            return Ok(GameTranscript {
+                turns,
+                won,
+                gave_up: None,
+            });
+        }
+        if let Err(error) = observed {
+            break error;
+        }
+    };
+    match error {
+        StrategyError::Input(_) => Err(error.into()),
+        error => Ok(GameTranscript {
+            turns,
+            won: false,
+            gave_up: Some(error),
+        }),
+    }
+}
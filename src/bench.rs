@@ -0,0 +1,91 @@
+//! Measures a strategy against every word in an answer list, so changes to a
+//! strategy can be judged on more than a handful of games.
+
+use crate::eval::play_games_metered;
+use crate::metrics::Metrics;
+use crate::strategy::Strategy;
+use crate::word::*;
+use std::fmt;
+
+/// Guesses allowed per game, as in Wordle.
+pub const MAX_GUESSES: usize = 6;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BenchmarkReport {
+    pub wins: usize,
+    pub losses: usize,
+    /// `histogram[n - 1]` is the number of games won in `n` guesses.
+    pub histogram: Vec<usize>,
+}
+
+impl BenchmarkReport {
+    pub fn games(&self) -> usize {
+        self.wins + self.losses
+    }
+
+    /// `None` if no games were won.
+    pub fn average_guesses(&self) -> Option<f64> {
+        let guesses: usize = (1..).zip(&self.histogram).map(|(n, games)| n * games).sum();
+        (self.wins > 0).then(|| guesses as f64 / self.wins as f64)
+    }
+}
+
+impl fmt::Display for BenchmarkReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (guesses, games) in (1..).zip(&self.histogram) {
+            writeln!(f, "{}: {}", guesses, games)?;
+        }
+        writeln!(f, "X: {}", self.losses)?;
+        write!(f, "won {} of {}", self.wins, self.games())?;
+        if let Some(average) = self.average_guesses() {
+            write!(f, ", {:.3} guesses per win", average)?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays a fresh strategy from `strategy_factory` against every word in
+/// `word_list` as the secret, with [`MAX_GUESSES`] guesses from the same list.
+/// Games run in parallel on all cores; the report doesn't depend on how
+/// they're scheduled.
+pub fn run_benchmark<S, F, const WORD_LENGTH: usize>(
+    strategy_factory: F,
+    word_list: &WordList<WORD_LENGTH>,
+) -> BenchmarkReport
+where
+    S: Strategy<WORD_LENGTH>,
+    F: Fn() -> S + Sync,
+{
+    run_benchmark_metered(strategy_factory, word_list).0
+}
+
+/// Like [`run_benchmark`], but also totals up the work the strategy did
+/// over every game, if it keeps count. Think times are summed across cores,
+/// so they can add up to more than the benchmark took.
+pub fn run_benchmark_metered<S, F, const WORD_LENGTH: usize>(
+    strategy_factory: F,
+    word_list: &WordList<WORD_LENGTH>,
+) -> (BenchmarkReport, Metrics)
+where
+    S: Strategy<WORD_LENGTH>,
+    F: Fn() -> S + Sync,
+{
+    let mut report = BenchmarkReport {
+        histogram: vec![0; MAX_GUESSES],
+        ..Default::default()
+    };
+    let mut total = Metrics::default();
+    for (guesses, metrics) in
+        play_games_metered(|_| strategy_factory(), word_list, word_list, MAX_GUESSES)
+    {
+        if let Some(guesses) = guesses {
+            report.wins += 1;
+            report.histogram[guesses - 1] += 1;
+        } else {
+            report.losses += 1;
+        }
+        total += metrics;
+    }
+    (report, total)
+}
@@ -0,0 +1,122 @@
+use crate::word::*;
+use std::collections::BTreeMap;
+
+/// One played guess and the score it received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Row<const WORD_LENGTH: usize> {
+    pub guess: Word<WORD_LENGTH>,
+    #[cfg_attr(feature = "serde", serde(with = "serde_score"))]
+    pub score: Score<WORD_LENGTH>,
+    /// How many words were still viable after this row, if known.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub viable_after: Option<usize>,
+}
+
+impl<const WORD_LENGTH: usize> Row<WORD_LENGTH> {
+    pub fn is_solved(&self) -> bool {
+        self.score
+            .iter()
+            .all(|annotation| *annotation == LetterScore::RightPlace)
+    }
+}
+
+/// The state of a game as the player sees it: the rows played so far and
+/// everything that can be derived from them.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Board<const WORD_LENGTH: usize> {
+    rows: Vec<Row<WORD_LENGTH>>,
+}
+
+impl<const WORD_LENGTH: usize> Board<WORD_LENGTH> {
+    pub fn new() -> Self {
+        Self { rows: Vec::new() }
+    }
+
+    pub fn push(
+        &mut self,
+        guess: Word<WORD_LENGTH>,
+        score: Score<WORD_LENGTH>,
+        viable_after: Option<usize>,
+    ) {
+        self.rows.push(Row {
+            guess,
+            score,
+            viable_after,
+        });
+    }
+
+    pub fn rows(&self) -> &[Row<WORD_LENGTH>] {
+        &self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    pub fn last(&self) -> Option<&Row<WORD_LENGTH>> {
+        self.rows.last()
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.rows.last().is_some_and(Row::is_solved)
+    }
+
+    pub fn scores(&self) -> impl Iterator<Item = &Score<WORD_LENGTH>> {
+        self.rows.iter().map(|row| &row.score)
+    }
+
+    /// Whether `word` could still be the secret given every row.
+    pub fn is_consistent(&self, word: &Word<WORD_LENGTH>) -> bool {
+        self.first_conflict(word).is_none()
+    }
+
+    /// The first row that rules `word` out, if any.
+    pub fn first_conflict(&self, word: &Word<WORD_LENGTH>) -> Option<&Row<WORD_LENGTH>> {
+        self.rows
+            .iter()
+            .find(|row| !word.is_consistent_with(&row.guess, &row.score))
+    }
+
+    /// The words of `word_list` that could still be the secret.
+    pub fn viable_words(&self, word_list: &WordList<WORD_LENGTH>) -> WordList<WORD_LENGTH> {
+        word_list
+            .0
+            .iter()
+            .filter(|word| self.is_consistent(word))
+            .copied()
+            .collect()
+    }
+
+    /// The best status seen so far for every guessed letter, as an on-screen
+    /// keyboard would show it.
+    pub fn keyboard(&self) -> BTreeMap<char, LetterScore> {
+        fn rank(annotation: LetterScore) -> u8 {
+            match annotation {
+                LetterScore::Wrong => 0,
+                LetterScore::RightLetter => 1,
+                LetterScore::RightPlace => 2,
+            }
+        }
+
+        let mut keyboard = BTreeMap::new();
+        for row in &self.rows {
+            for (letter, annotation) in row.guess.0.iter().zip(row.score.iter()) {
+                let status = keyboard.entry(*letter).or_insert(*annotation);
+                if rank(*annotation) > rank(*status) {
+                    *status = *annotation;
+                }
+            }
+        }
+        keyboard
+    }
+}
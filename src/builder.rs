@@ -0,0 +1,276 @@
+//! Builders for the strategies, for setting them up in one expression as
+//! their options grow:
+//!
+//! ```text
+//! EntropyStrategy::builder()
+//!     .word_list(words)
+//!     .opener(crane)
+//!     .tie_break(TieBreak::First)
+//!     .build()?
+//! ```
+//!
+//! Every builder takes a word list, an answer list, [`GuessRules`] and an
+//! opener; the rest is only offered by the strategies it means something to.
+//! Anything not set keeps the default the strategy's `new` gives it, so
+//! adding an option never breaks a caller. The `new` and `with_guess_list`
+//! constructors stay for callers that only have a word list.
+
+use crate::adaptive::AdaptiveStrategy;
+use crate::frequency::Frequencies;
+use crate::optimal::{OptimalStrategy, DEFAULT_MAX_EXACT, DEFAULT_SHORTLIST};
+use crate::score_matrix::ScoreMatrix;
+use crate::strategy::{EntropyStrategy, GuessRules, HumanLikeStrategy, SimpleStrategy, TieBreak};
+use crate::word::*;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Why a builder couldn't build its strategy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError<const WORD_LENGTH: usize> {
+    /// [`Builder::word_list`] was never called.
+    NoWordList,
+    /// The opener isn't in the word list, so no engine would take it.
+    OpenerNotInWordList(Word<WORD_LENGTH>),
+}
+
+impl<const WORD_LENGTH: usize> fmt::Display for BuildError<WORD_LENGTH> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoWordList => write!(f, "no word list to guess from"),
+            Self::OpenerNotInWordList(opener) => {
+                write!(f, "the opener {} isn't in the word list", opener)
+            }
+        }
+    }
+}
+
+impl<const WORD_LENGTH: usize> std::error::Error for BuildError<WORD_LENGTH> {}
+
+/// Sets up a strategy of type `S`; see the [module docs](self).
+pub struct Builder<S, const WORD_LENGTH: usize> {
+    word_list: Option<WordList<WORD_LENGTH>>,
+    answer_list: Option<WordList<WORD_LENGTH>>,
+    rules: GuessRules<WORD_LENGTH>,
+    opener: Option<Word<WORD_LENGTH>>,
+    think_budget: Option<Duration>,
+    score_matrix: Option<Arc<ScoreMatrix<WORD_LENGTH>>>,
+    frequencies: Option<Arc<Frequencies<WORD_LENGTH>>>,
+    tie_break: TieBreak,
+    max_exact: usize,
+    shortlist: usize,
+    mistake_rate: f64,
+    seed: u64,
+    strategy: PhantomData<fn() -> S>,
+}
+
+impl<S, const WORD_LENGTH: usize> Builder<S, WORD_LENGTH> {
+    fn new() -> Self {
+        Self {
+            word_list: None,
+            answer_list: None,
+            rules: GuessRules::default(),
+            opener: None,
+            think_budget: None,
+            score_matrix: None,
+            frequencies: None,
+            tie_break: TieBreak::default(),
+            max_exact: DEFAULT_MAX_EXACT,
+            shortlist: DEFAULT_SHORTLIST,
+            mistake_rate: 0.2,
+            seed: 0,
+            strategy: PhantomData,
+        }
+    }
+
+    /// The words to guess from, and the possible secrets unless
+    /// [`answer_list`](Self::answer_list) says otherwise.
+    pub fn word_list(mut self, word_list: WordList<WORD_LENGTH>) -> Self {
+        self.word_list = Some(word_list);
+        self
+    }
+
+    /// Only considers these words as the secret, like the NYT's answers
+    /// next to its allowed guesses.
+    pub fn answer_list(mut self, answer_list: WordList<WORD_LENGTH>) -> Self {
+        self.answer_list = Some(answer_list);
+        self
+    }
+
+    pub fn rules(mut self, rules: GuessRules<WORD_LENGTH>) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Opens every game with `opener`, which has to be in the word list.
+    pub fn opener(mut self, opener: Word<WORD_LENGTH>) -> Self {
+        self.opener = Some(opener);
+        self
+    }
+
+    /// The guess list and answer list, with the opener checked against the
+    /// guess list.
+    fn lists(
+        &self,
+    ) -> Result<(WordList<WORD_LENGTH>, WordList<WORD_LENGTH>), BuildError<WORD_LENGTH>> {
+        let word_list = self.word_list.clone().ok_or(BuildError::NoWordList)?;
+        if let Some(opener) = self.opener.filter(|opener| !word_list.0.contains(opener)) {
+            return Err(BuildError::OpenerNotInWordList(opener));
+        }
+        let answer_list = self
+            .answer_list
+            .clone()
+            .unwrap_or_else(|| word_list.clone());
+        Ok((word_list, answer_list))
+    }
+
+    /// The rules, with the opener as the fixed first guess for strategies
+    /// that have no opener of their own.
+    fn rules_with_opener(&self) -> GuessRules<WORD_LENGTH> {
+        GuessRules {
+            always_open: self.opener.or(self.rules.always_open),
+            ..self.rules.clone()
+        }
+    }
+}
+
+impl<const WORD_LENGTH: usize> SimpleStrategy<WORD_LENGTH> {
+    pub fn builder() -> Builder<Self, WORD_LENGTH> {
+        Builder::new()
+    }
+}
+
+impl<const WORD_LENGTH: usize> Builder<SimpleStrategy<WORD_LENGTH>, WORD_LENGTH> {
+    /// See [`SimpleStrategy::set_think_budget`].
+    pub fn think_budget(mut self, budget: Duration) -> Self {
+        self.think_budget = Some(budget);
+        self
+    }
+
+    /// See [`SimpleStrategy::set_score_matrix`].
+    pub fn score_matrix(mut self, matrix: Arc<ScoreMatrix<WORD_LENGTH>>) -> Self {
+        self.score_matrix = Some(matrix);
+        self
+    }
+
+    pub fn build(self) -> Result<SimpleStrategy<WORD_LENGTH>, BuildError<WORD_LENGTH>> {
+        let (guess_list, answer_list) = self.lists()?;
+        let mut strategy = SimpleStrategy::with_guess_list(guess_list, answer_list);
+        if let Some(opener) = self.opener {
+            strategy = strategy
+                .with_opener(opener)
+                .map_err(|_| BuildError::OpenerNotInWordList(opener))?;
+        }
+        strategy.set_guess_rules(self.rules);
+        if let Some(budget) = self.think_budget {
+            strategy.set_think_budget(budget);
+        }
+        if let Some(matrix) = self.score_matrix {
+            strategy.set_score_matrix(matrix);
+        }
+        Ok(strategy)
+    }
+}
+
+impl<const WORD_LENGTH: usize> EntropyStrategy<WORD_LENGTH> {
+    pub fn builder() -> Builder<Self, WORD_LENGTH> {
+        Builder::new()
+    }
+}
+
+impl<const WORD_LENGTH: usize> Builder<EntropyStrategy<WORD_LENGTH>, WORD_LENGTH> {
+    /// See [`EntropyStrategy::set_frequencies`].
+    pub fn frequencies(mut self, frequencies: Arc<Frequencies<WORD_LENGTH>>) -> Self {
+        self.frequencies = Some(frequencies);
+        self
+    }
+
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    pub fn build(self) -> Result<EntropyStrategy<WORD_LENGTH>, BuildError<WORD_LENGTH>> {
+        let (guess_list, answer_list) = self.lists()?;
+        let mut strategy = EntropyStrategy::with_guess_list(guess_list, answer_list);
+        strategy.set_guess_rules(self.rules_with_opener());
+        if let Some(frequencies) = self.frequencies {
+            strategy.set_frequencies(frequencies);
+        }
+        strategy.set_tie_break(self.tie_break);
+        Ok(strategy)
+    }
+}
+
+impl<const WORD_LENGTH: usize> AdaptiveStrategy<WORD_LENGTH> {
+    pub fn builder() -> Builder<Self, WORD_LENGTH> {
+        Builder::new()
+    }
+}
+
+impl<const WORD_LENGTH: usize> Builder<AdaptiveStrategy<WORD_LENGTH>, WORD_LENGTH> {
+    pub fn build(self) -> Result<AdaptiveStrategy<WORD_LENGTH>, BuildError<WORD_LENGTH>> {
+        let (guess_list, answer_list) = self.lists()?;
+        let mut strategy = AdaptiveStrategy::with_guess_list(guess_list, answer_list);
+        strategy.set_guess_rules(self.rules_with_opener());
+        Ok(strategy)
+    }
+}
+
+impl<const WORD_LENGTH: usize> OptimalStrategy<WORD_LENGTH> {
+    pub fn builder() -> Builder<Self, WORD_LENGTH> {
+        Builder::new()
+    }
+}
+
+impl<const WORD_LENGTH: usize> Builder<OptimalStrategy<WORD_LENGTH>, WORD_LENGTH> {
+    /// See [`OptimalStrategy::with_max_exact`].
+    pub fn max_exact(mut self, max_exact: usize) -> Self {
+        self.max_exact = max_exact;
+        self
+    }
+
+    /// See [`OptimalStrategy::with_shortlist`].
+    pub fn shortlist(mut self, shortlist: usize) -> Self {
+        self.shortlist = shortlist;
+        self
+    }
+
+    pub fn build(self) -> Result<OptimalStrategy<WORD_LENGTH>, BuildError<WORD_LENGTH>> {
+        let (guess_list, answer_list) = self.lists()?;
+        let mut strategy = OptimalStrategy::with_guess_list(guess_list, answer_list)
+            .with_max_exact(self.max_exact)
+            .with_shortlist(self.shortlist);
+        strategy.set_guess_rules(self.rules_with_opener());
+        Ok(strategy)
+    }
+}
+
+impl<const WORD_LENGTH: usize> HumanLikeStrategy<WORD_LENGTH> {
+    pub fn builder() -> Builder<Self, WORD_LENGTH> {
+        Builder::new()
+    }
+}
+
+impl<const WORD_LENGTH: usize> Builder<HumanLikeStrategy<WORD_LENGTH>, WORD_LENGTH> {
+    /// The chance, each turn, of a careless guess; 0.2 unless set. Clamped
+    /// like [`HumanLikeStrategy::new`] clamps it.
+    pub fn mistake_rate(mut self, mistake_rate: f64) -> Self {
+        self.mistake_rate = mistake_rate;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn build(self) -> Result<HumanLikeStrategy<WORD_LENGTH>, BuildError<WORD_LENGTH>> {
+        let (guess_list, answer_list) = self.lists()?;
+        let mut strategy = HumanLikeStrategy::new(guess_list, self.mistake_rate, self.seed);
+        strategy.set_answer_list(answer_list);
+        strategy.set_guess_rules(self.rules_with_opener());
+        Ok(strategy)
+    }
+}
@@ -0,0 +1,111 @@
+//! Today's puzzle from the NYT itself, for dates past the bundled
+//! [answers](crate::nyt::ANSWERS). Each date is fetched once and kept in a
+//! cache folder as the NYT sent it, so a day's puzzle can be played again
+//! offline.
+
+use crate::nyt::Date;
+use crate::word::*;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Where the NYT serves each day's puzzle, as `{ENDPOINT}/YYYY-MM-DD.json`.
+pub const ENDPOINT: &str = "https://www.nytimes.com/svc/wordle/v2";
+
+#[derive(Debug)]
+pub enum FetchError {
+    Io(io::Error),
+    /// The request failed, or the NYT answered with an error.
+    Http(String),
+    /// The NYT, or the cache, sent something that isn't a puzzle.
+    Malformed(String),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::Http(message) => write!(f, "couldn't fetch the puzzle: {}", message),
+            Self::Malformed(message) => write!(f, "not a puzzle: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<io::Error> for FetchError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// One day's puzzle, as the NYT describes it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct DailyPuzzle {
+    #[serde(rename = "days_since_launch")]
+    pub puzzle: u32,
+    #[serde(rename = "print_date")]
+    pub date: String,
+    pub solution: String,
+    #[serde(default)]
+    pub editor: Option<String>,
+}
+
+impl DailyPuzzle {
+    /// Reads the NYT's JSON for a puzzle.
+    pub fn from_json(text: &str) -> Result<Self, FetchError> {
+        serde_json::from_str(text).map_err(|error| FetchError::Malformed(error.to_string()))
+    }
+
+    /// The solution as a word, if it's one of `WORD_LENGTH` letters.
+    pub fn answer<const WORD_LENGTH: usize>(&self) -> Option<Word<WORD_LENGTH>> {
+        Word::try_from(self.solution.to_ascii_lowercase().as_str()).ok()
+    }
+}
+
+/// Fetches the puzzle for `date`, bypassing any cache.
+pub fn fetch(date: Date) -> Result<(DailyPuzzle, String), FetchError> {
+    let text = ureq::get(&format!("{}/{}.json", ENDPOINT, date))
+        .call()
+        .map_err(|error| FetchError::Http(error.to_string()))?
+        .into_string()?;
+    Ok((DailyPuzzle::from_json(&text)?, text))
+}
+
+/// Fetched puzzles, one file per date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyCache {
+    dir: PathBuf,
+}
+
+impl DailyCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, date: Date) -> PathBuf {
+        self.dir.join(format!("{}.json", date))
+    }
+
+    /// The puzzle for `date`, if it has been fetched before.
+    pub fn cached(&self, date: Date) -> Result<Option<DailyPuzzle>, FetchError> {
+        match std::fs::read_to_string(self.path(date)) {
+            Ok(text) => DailyPuzzle::from_json(&text).map(Some),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// The puzzle for `date`, from the cache if it's there, and fetched and
+    /// cached otherwise.
+    pub fn get(&self, date: Date) -> Result<DailyPuzzle, FetchError> {
+        if let Some(puzzle) = self.cached(date)? {
+            return Ok(puzzle);
+        }
+        let (puzzle, text) = fetch(date)?;
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.path(date), text)?;
+        Ok(puzzle)
+    }
+}
@@ -0,0 +1,91 @@
+//! Bulk scoring for building datasets of Wordle feedback: reads CSV rows
+//! that start with a secret and a guess, and writes them back out with the
+//! score appended.
+//!
+//! ```text
+//! secret,guess           secret,guess,score
+//! crane,caper      =>    crane,caper,gybyy
+//! abbey,babes            abbey,babes,yyggb
+//! ```
+
+use crate::word::{score, ScoreError};
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+#[derive(Debug)]
+pub enum DatasetError {
+    Io(io::Error),
+    /// A row without both a secret and a guess.
+    MissingColumn {
+        line: usize,
+    },
+    Score {
+        line: usize,
+        error: ScoreError,
+    },
+}
+
+impl fmt::Display for DatasetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::MissingColumn { line } => {
+                write!(f, "line {}: expected a secret and a guess", line)
+            }
+            Self::Score { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for DatasetError {}
+
+impl From<io::Error> for DatasetError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+pub(crate) fn unquote(field: &str) -> &str {
+    let field = field.trim();
+    field
+        .strip_prefix('"')
+        .and_then(|field| field.strip_suffix('"'))
+        .unwrap_or(field)
+}
+
+/// Copies CSV rows from `input` to `out`, appending the score of each row's
+/// first two columns. A `secret,guess` header row gets a `score` column.
+/// Blank lines are kept as they are. Returns the number of rows scored.
+pub fn append_scores(input: impl BufRead, mut out: impl Write) -> Result<usize, DatasetError> {
+    let mut scored = 0;
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        let row = line.trim_end();
+        if row.is_empty() {
+            writeln!(out)?;
+            continue;
+        }
+
+        let mut fields = row.splitn(3, ',').map(unquote);
+        let line_number = index + 1;
+        let (secret, guess) = match (fields.next(), fields.next()) {
+            (Some(secret), Some(guess)) => (secret, guess),
+            _ => return Err(DatasetError::MissingColumn { line: line_number }),
+        };
+        if index == 0
+            && secret.eq_ignore_ascii_case("secret")
+            && guess.eq_ignore_ascii_case("guess")
+        {
+            writeln!(out, "{},score", row)?;
+            continue;
+        }
+        let score = score(secret, guess).map_err(|error| DatasetError::Score {
+            line: line_number,
+            error,
+        })?;
+        writeln!(out, "{},{}", row, score)?;
+        scored += 1;
+    }
+    out.flush()?;
+    Ok(scored)
+}
@@ -0,0 +1,302 @@
+//! Opening books: the guess to play for every score history that can come
+//! up, worked out once for a word list and saved, so a game can be played
+//! from it without any thinking.
+//!
+//! A book is saved as an [artifact](crate::artifact) followed by the tree,
+//! one guess per line. Each reply is indented under the guess it answers,
+//! with the score that leads to it:
+//!
+//! ```text
+//! WSLV 1 opening-book 3 9a3c0f27d1e4b865
+//! cat
+//!   bgg hat
+//!     bgg bat
+//!   ggb cab
+//! ```
+
+use crate::artifact::{ArtifactError, ArtifactKind, Header};
+use crate::ranking::Ranker;
+use crate::strategy::{EntropyStrategy, Strategy, StrategyError};
+use crate::word::*;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::io::{BufRead, Write};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DecisionTree<const WORD_LENGTH: usize> {
+    pub guess: Word<WORD_LENGTH>,
+    /// What to play next after each score the guess can get, other than the
+    /// winning one.
+    #[cfg_attr(feature = "serde", serde(with = "serde_replies"))]
+    pub replies: BTreeMap<Score<WORD_LENGTH>, DecisionTree<WORD_LENGTH>>,
+}
+
+impl<const WORD_LENGTH: usize> DecisionTree<WORD_LENGTH> {
+    /// Works out a tree that finds every word in `answers`, which mustn't be
+    /// empty, guessing from `guesses` with whatever `ranker` likes best. A
+    /// guess that wouldn't tell the remaining answers apart is passed over,
+    /// so the tree always ends.
+    pub fn build(
+        guesses: &WordList<WORD_LENGTH>,
+        answers: &WordList<WORD_LENGTH>,
+        ranker: &impl Ranker<WORD_LENGTH>,
+    ) -> Self {
+        let solved = [LetterScore::RightPlace; WORD_LENGTH];
+        let guess = if answers.0.len() <= 2 {
+            answers.0[0]
+        } else {
+            ranker
+                .rank(guesses, answers)
+                .into_iter()
+                .find(|guess| {
+                    let sizes = answers.partition_sizes(guess);
+                    sizes.len() > 1 || sizes.contains_key(&solved)
+                })
+                .unwrap_or(answers.0[0])
+        };
+        let replies = answers
+            .partition(&guess)
+            .into_iter()
+            .filter(|(score, _)| *score != solved)
+            .map(|(score, remaining)| (score, Self::build(guesses, &remaining, ranker)))
+            .collect();
+        Self { guess, replies }
+    }
+
+    /// The guess to play after `history`, the scores so far, or `None` if
+    /// the tree never gets there.
+    pub fn guess_after(&self, history: &[Score<WORD_LENGTH>]) -> Option<Word<WORD_LENGTH>> {
+        let mut node = self;
+        for score in history {
+            node = node.replies.get(score)?;
+        }
+        Some(node.guess)
+    }
+
+    /// The most guesses the tree ever needs.
+    pub fn depth(&self) -> usize {
+        1 + self.replies.values().map(Self::depth).max().unwrap_or(0)
+    }
+
+    /// Guesses the tree takes to find `secret`, or `None` if it never does.
+    pub fn guesses_for(&self, secret: &Word<WORD_LENGTH>) -> Option<usize> {
+        let mut node = self;
+        let mut turn = 1;
+        while node.guess != *secret {
+            node = node.replies.get(&secret.evaluate_guess(&node.guess))?;
+            turn += 1;
+        }
+        Some(turn)
+    }
+
+    /// Guesses needed to find each of `answers`, summed over all of them, or
+    /// `None` if the tree misses any.
+    pub fn total_guesses(&self, answers: &WordList<WORD_LENGTH>) -> Option<usize> {
+        answers
+            .0
+            .iter()
+            .map(|secret| self.guesses_for(secret))
+            .sum()
+    }
+
+    /// Saves the tree for `word_list`, which it will only load with again.
+    pub fn write(
+        &self,
+        mut out: impl Write,
+        word_list: &WordList<WORD_LENGTH>,
+    ) -> std::io::Result<()> {
+        Header::new(ArtifactKind::OpeningBook, word_list).write(&mut out)?;
+        writeln!(out, "{}", self.guess)?;
+        self.write_replies(&mut out, 1)
+    }
+
+    fn write_replies(&self, out: &mut impl Write, depth: usize) -> std::io::Result<()> {
+        for (score, reply) in &self.replies {
+            writeln!(
+                out,
+                "{:indent$}{} {}",
+                "",
+                format_score(score),
+                reply.guess,
+                indent = 2 * depth
+            )?;
+            reply.write_replies(out, depth + 1)?;
+        }
+        Ok(())
+    }
+
+    /// Loads a tree saved with [`DecisionTree::write`], refusing one made
+    /// for a different word list.
+    pub fn read(
+        mut input: impl BufRead,
+        word_list: &WordList<WORD_LENGTH>,
+    ) -> Result<Self, ArtifactError> {
+        Header::read(&mut input)?.check(ArtifactKind::OpeningBook, word_list)?;
+        let mut text = String::new();
+        input.read_to_string(&mut text)?;
+        // Line numbers in errors count from the top of the file, header
+        // included.
+        let malformed = |index: usize, message: &str| {
+            ArtifactError::Malformed(format!("line {}: {}", index + 2, message))
+        };
+        let word = |index: usize, text: &str| {
+            Word::try_from(text).map_err(|_| malformed(index, &format!("bad word {:?}", text)))
+        };
+
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let (index, root) = lines
+            .next()
+            .ok_or_else(|| malformed(0, "no opening guess"))?;
+        // The guesses from the root down to the line being read, each with
+        // the score that led to it.
+        let mut path: Vec<(Option<Score<WORD_LENGTH>>, Self)> = vec![(
+            None,
+            Self {
+                guess: word(index, root.trim())?,
+                replies: BTreeMap::new(),
+            },
+        )];
+        for (index, line) in lines {
+            let indent = line.len() - line.trim_start().len();
+            if indent % 2 != 0 || indent == 0 || indent / 2 > path.len() {
+                return Err(malformed(index, "bad indentation"));
+            }
+            let (score, guess) = line
+                .trim()
+                .split_once(' ')
+                .ok_or_else(|| malformed(index, "expected a score and a guess"))?;
+            let score = parse_score(score)
+                .ok_or_else(|| malformed(index, &format!("bad score {:?}", score)))?;
+            while path.len() > indent / 2 {
+                fold_last(&mut path);
+            }
+            path.push((
+                Some(score),
+                Self {
+                    guess: word(index, guess)?,
+                    replies: BTreeMap::new(),
+                },
+            ));
+        }
+        while path.len() > 1 {
+            fold_last(&mut path);
+        }
+        Ok(path.pop().expect("the root is never folded").1)
+    }
+}
+
+/// Keys the replies by score in tile shorthand, since JSON keys have to be
+/// strings.
+#[cfg(feature = "serde")]
+mod serde_replies {
+    use super::*;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    type Replies<const WORD_LENGTH: usize> =
+        BTreeMap<Score<WORD_LENGTH>, DecisionTree<WORD_LENGTH>>;
+
+    pub fn serialize<S: Serializer, const WORD_LENGTH: usize>(
+        replies: &Replies<WORD_LENGTH>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            replies
+                .iter()
+                .map(|(score, reply)| (format_score(score), reply)),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const WORD_LENGTH: usize>(
+        deserializer: D,
+    ) -> Result<Replies<WORD_LENGTH>, D::Error> {
+        BTreeMap::<String, DecisionTree<WORD_LENGTH>>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(score, reply)| match parse_score(&score) {
+                Some(score) => Ok((score, reply)),
+                None => Err(serde::de::Error::custom(format!("bad score {:?}", score))),
+            })
+            .collect()
+    }
+}
+
+/// Moves the deepest node on `path` into its parent's replies.
+fn fold_last<const WORD_LENGTH: usize>(
+    path: &mut Vec<(Option<Score<WORD_LENGTH>>, DecisionTree<WORD_LENGTH>)>,
+) {
+    let (score, node) = path.pop().expect("path isn't empty");
+    let parent = &mut path.last_mut().expect("the root is never folded").1;
+    parent
+        .replies
+        .insert(score.expect("only the root has no score"), node);
+}
+
+/// Plays straight from a [`DecisionTree`]. Should a game leave the tree,
+/// e.g. because the secret wasn't one of the answers it was built for, the
+/// rest is played by an [`EntropyStrategy`] told everything so far.
+pub struct DecisionTreeStrategy<const WORD_LENGTH: usize> {
+    tree: Arc<DecisionTree<WORD_LENGTH>>,
+    word_list: WordList<WORD_LENGTH>,
+    history: Vec<(Word<WORD_LENGTH>, Score<WORD_LENGTH>)>,
+    last_guess: Option<Word<WORD_LENGTH>>,
+    off_book: Option<EntropyStrategy<WORD_LENGTH>>,
+}
+
+impl<const WORD_LENGTH: usize> DecisionTreeStrategy<WORD_LENGTH> {
+    /// Plays from `tree`, falling back on guesses from `word_list`. The tree
+    /// is shared, so many games can play from one copy.
+    pub fn new(tree: Arc<DecisionTree<WORD_LENGTH>>, word_list: WordList<WORD_LENGTH>) -> Self {
+        Self {
+            tree,
+            word_list,
+            history: Vec::new(),
+            last_guess: None,
+            off_book: None,
+        }
+    }
+
+    /// Whether the game has left the tree.
+    pub fn off_book(&self) -> bool {
+        self.off_book.is_some()
+    }
+}
+
+impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for DecisionTreeStrategy<WORD_LENGTH> {
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        if self.off_book.is_none() {
+            let scores = self
+                .history
+                .iter()
+                .map(|(_, score)| *score)
+                .collect::<Vec<_>>();
+            if let Some(guess) = self.tree.guess_after(&scores) {
+                self.last_guess = Some(guess);
+                return Ok(guess);
+            }
+            let mut fallback = EntropyStrategy::new(self.word_list.clone());
+            let clues = self
+                .history
+                .iter()
+                .map(|(guess, score)| Clue::Scored(*guess, *score))
+                .collect::<Vec<_>>();
+            fallback.prime(&clues);
+            self.off_book = Some(fallback);
+        }
+        let fallback = self.off_book.as_mut().expect("just set up");
+        let guess = fallback.make_guess()?;
+        self.last_guess = Some(guess);
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        let last_guess = self.last_guess.expect("should've made a guess by now");
+        self.history.push((last_guess, *score));
+        if let Some(fallback) = &mut self.off_book {
+            fallback.receive_score(score);
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! Dictionaries to benchmark on, bundled or loaded from word files, so
+//! strategies can be checked on languages other than English.
+//!
+//! A word file has one word per line:
+//!
+//! ```text
+//! # Spanish answers
+//! árbol
+//! perro
+//! ```
+//!
+//! Blank lines and lines starting with `#` are ignored, and so are words of
+//! other lengths, so a general word list can be used as is.
+
+use crate::nyt;
+use crate::word::*;
+use crate::word_list::WORD_LIST;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+/// Dictionaries built into the crate.
+pub const BUNDLED: &[&str] = &["en-answers", "en-allowed"];
+
+#[derive(Debug, Clone)]
+pub struct Dictionary<const WORD_LENGTH: usize> {
+    pub name: String,
+    /// Words that can be the secret.
+    pub answers: WordList<WORD_LENGTH>,
+    /// Words that may be guessed, answers included.
+    pub allowed: WordList<WORD_LENGTH>,
+}
+
+impl<const WORD_LENGTH: usize> Dictionary<WORD_LENGTH> {
+    /// A dictionary with secrets from `answers`, which may be guessed along
+    /// with everything in `allowed`.
+    pub fn new(
+        name: &str,
+        answers: WordList<WORD_LENGTH>,
+        mut allowed: WordList<WORD_LENGTH>,
+    ) -> Self {
+        let mut seen = allowed.0.iter().copied().collect::<HashSet<_>>();
+        allowed
+            .0
+            .extend(answers.0.iter().filter(|word| seen.insert(**word)));
+        Self {
+            name: name.to_owned(),
+            answers,
+            allowed,
+        }
+    }
+}
+
+impl Dictionary<5> {
+    /// One of the [`BUNDLED`] dictionaries: the NYT's answers, guessed from
+    /// the full list, or the full list for both.
+    pub fn bundled(name: &str) -> Option<Self> {
+        let words = |list: &[&str]| {
+            list.iter()
+                .map(|word| Word::try_from(*word).expect("bundled words have five letters"))
+                .collect::<WordList<5>>()
+        };
+        match name {
+            "en-answers" => Some(Self::new(name, words(nyt::ANSWERS), words(WORD_LIST))),
+            "en-allowed" => Some(Self::new(name, words(WORD_LIST), words(WORD_LIST))),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for DictionaryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for DictionaryError {}
+
+/// Why a word list couldn't be loaded with [`WordList::from_reader`] or
+/// [`WordList::from_path`].
+#[derive(Debug)]
+pub enum LoadError {
+    Io(io::Error),
+    Invalid(DictionaryError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "{}", error),
+            Self::Invalid(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<DictionaryError> for LoadError {
+    fn from(error: DictionaryError) -> Self {
+        Self::Invalid(error)
+    }
+}
+
+impl<const WORD_LENGTH: usize> WordList<WORD_LENGTH> {
+    /// Loads a word list to play with in place of the bundled one, in the
+    /// word file format. Unlike [`parse_words`], every line has to be a
+    /// word of the right length, since the list is the whole dictionary and
+    /// a word quietly left out couldn't be played. Words are lowercased, and
+    /// ones listed twice are kept once.
+    pub fn from_reader(input: impl BufRead) -> Result<Self, LoadError> {
+        let mut seen = HashSet::new();
+        let mut words = Vec::new();
+        let mut lines = 0;
+        for (index, line) in input.lines().enumerate() {
+            let line = line?;
+            lines = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let word = Word::try_from(line.to_lowercase().as_str())
+                .ok()
+                .filter(|word| word.0.iter().all(|letter| letter.is_alphabetic()))
+                .ok_or_else(|| DictionaryError {
+                    line: index + 1,
+                    message: format!("{:?} isn't a {}-letter word", line, WORD_LENGTH),
+                })?;
+            if seen.insert(word) {
+                words.push(word);
+            }
+        }
+        if words.is_empty() {
+            return Err(DictionaryError {
+                line: lines,
+                message: "no words".to_owned(),
+            }
+            .into());
+        }
+        Ok(WordList(words))
+    }
+
+    /// [`WordList::from_reader`] on the file at `path`.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        Self::from_reader(BufReader::new(std::fs::File::open(path)?))
+    }
+}
+
+/// Reads a word file, lowercasing every word. A file with no words of the
+/// right length is an error, since nothing could be played from it.
+pub fn parse_words<const WORD_LENGTH: usize>(
+    text: &str,
+) -> Result<WordList<WORD_LENGTH>, DictionaryError> {
+    let mut seen = HashSet::new();
+    let mut words = Vec::new();
+    let mut lines = 0;
+    for (index, line) in text.lines().enumerate() {
+        lines = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.contains(char::is_whitespace) {
+            return Err(DictionaryError {
+                line: index + 1,
+                message: format!("expected one word, got {:?}", line),
+            });
+        }
+        if let Ok(word) = Word::try_from(line.to_lowercase().as_str()) {
+            if seen.insert(word) {
+                words.push(word);
+            }
+        }
+    }
+    if words.is_empty() {
+        return Err(DictionaryError {
+            line: lines,
+            message: format!("no {}-letter words", WORD_LENGTH),
+        });
+    }
+    Ok(WordList(words))
+}
+
+/// Reads a file of extra words to allow as guesses, e.g. ones a clone site
+/// accepts that the bundled list lacks. Unlike [`parse_words`], every word
+/// has to be usable, since a typo would otherwise go unnoticed.
+pub fn parse_extra_words<const WORD_LENGTH: usize>(
+    text: &str,
+) -> Result<WordList<WORD_LENGTH>, DictionaryError> {
+    let mut words = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let word = Word::try_from(line.to_lowercase().as_str())
+            .ok()
+            .filter(|word| word.0.iter().all(|letter| letter.is_alphabetic()))
+            .ok_or_else(|| DictionaryError {
+                line: index + 1,
+                message: format!("{:?} isn't a {}-letter word", line, WORD_LENGTH),
+            })?;
+        words.push(word);
+    }
+    Ok(WordList(words))
+}
+
+/// How one strategy did on one dictionary.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepResult {
+    pub dictionary: String,
+    pub strategy: String,
+    pub summary: crate::eval::EvaluationSummary,
+    /// 95% bootstrap interval for the guesses per win, if any game was won.
+    pub interval: Option<crate::stats::ConfidenceInterval>,
+}
+
+/// Benchmarks each of `strategies`, as named in a run manifest, on each of
+/// `dictionaries`. Every strategy plays the same seeded sample of `games`
+/// secrets from a dictionary's answers, and only knows its allowed words.
+#[cfg(feature = "serde")]
+pub fn sweep<const WORD_LENGTH: usize>(
+    dictionaries: &[Dictionary<WORD_LENGTH>],
+    strategies: &[String],
+    games: usize,
+    max_guesses: usize,
+    seed: u64,
+) -> Result<SweepReport, crate::manifest::ManifestError> {
+    use crate::manifest::RunManifest;
+    let mut results = Vec::new();
+    for dictionary in dictionaries {
+        for strategy in strategies {
+            let manifest = RunManifest::new(
+                strategy,
+                Default::default(),
+                &dictionary.allowed,
+                games,
+                max_guesses,
+                seed,
+            );
+            // Check the strategy once up front, so a bad one is an error
+            // rather than a panic inside the evaluation.
+            manifest.strategy(&dictionary.allowed)?;
+            let games = crate::eval::play_games(
+                |_| {
+                    manifest
+                        .strategy(&dictionary.allowed)
+                        .expect("already checked")
+                },
+                &manifest.secrets(&dictionary.answers),
+                &dictionary.allowed,
+                max_guesses,
+            );
+            let guesses = games
+                .iter()
+                .flatten()
+                .map(|guesses| *guesses as f64)
+                .collect::<Vec<_>>();
+            results.push(SweepResult {
+                dictionary: dictionary.name.clone(),
+                strategy: strategy.clone(),
+                summary: crate::eval::summarize(&games),
+                interval: crate::stats::bootstrap_mean(
+                    &guesses,
+                    0.95,
+                    crate::stats::BOOTSTRAP_RESAMPLES,
+                    seed,
+                ),
+            });
+        }
+    }
+    Ok(SweepReport(results))
+}
+
+/// Results of a [`sweep`], one row per dictionary and strategy. Strategies
+/// whose intervals overlap on a dictionary can't be told apart from that
+/// many games.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepReport(pub Vec<SweepResult>);
+
+#[cfg(feature = "serde")]
+impl fmt::Display for SweepReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = |column: fn(&SweepResult) -> &str, heading: &str| {
+            self.0
+                .iter()
+                .map(|result| column(result).chars().count())
+                .chain(std::iter::once(heading.len()))
+                .max()
+                .unwrap_or(0)
+        };
+        let dictionary_width = width(|result| &result.dictionary, "dictionary");
+        let strategy_width = width(|result| &result.strategy, "strategy");
+        writeln!(
+            f,
+            "{:<dw$}  {:<sw$}  games     won  guesses/win  95% interval",
+            "dictionary",
+            "strategy",
+            dw = dictionary_width,
+            sw = strategy_width
+        )?;
+        for result in &self.0 {
+            writeln!(
+                f,
+                "{:<dw$}  {:<sw$}  {:>5}  {:>6}  {:>11}  {:>12}",
+                result.dictionary,
+                result.strategy,
+                result.summary.games,
+                result
+                    .summary
+                    .win_rate()
+                    .map_or_else(|| "-".to_owned(), |rate| format!("{:.1}%", 100.0 * rate)),
+                result
+                    .summary
+                    .average_guesses()
+                    .map_or_else(|| "-".to_owned(), |guesses| format!("{:.3}", guesses)),
+                result
+                    .interval
+                    .map_or_else(|| "-".to_owned(), |interval| interval.to_string()),
+                dw = dictionary_width,
+                sw = strategy_width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// [`sweep`] results for one dictionary at each of several word lengths,
+/// shown as a table of strategies against lengths. A strategy whose
+/// guesses per win climb faster than the others' as words get longer is
+/// one that scales poorly.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LengthMatrix(pub Vec<(usize, SweepReport)>);
+
+#[cfg(feature = "serde")]
+impl fmt::Display for LengthMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut strategies: Vec<&str> = Vec::new();
+        for result in self.0.iter().flat_map(|(_, report)| &report.0) {
+            if !strategies.contains(&result.strategy.as_str()) {
+                strategies.push(&result.strategy);
+            }
+        }
+        let strategy_width = strategies
+            .iter()
+            .map(|strategy| strategy.chars().count())
+            .chain(std::iter::once("strategy".len()))
+            .max()
+            .unwrap_or(0);
+        const CELL_WIDTH: usize = 14;
+        write!(f, "{:<sw$}", "strategy", sw = strategy_width)?;
+        for (length, _) in &self.0 {
+            write!(
+                f,
+                "  {:>cw$}",
+                format!("{} letters", length),
+                cw = CELL_WIDTH
+            )?;
+        }
+        writeln!(f)?;
+        for strategy in strategies {
+            write!(f, "{:<sw$}", strategy, sw = strategy_width)?;
+            for (_, report) in &self.0 {
+                let cell = report
+                    .0
+                    .iter()
+                    .find(|result| result.strategy == strategy)
+                    .map_or_else(
+                        || "-".to_owned(),
+                        |result| {
+                            let summary = &result.summary;
+                            format!(
+                                "{} ({:.0}%)",
+                                summary.average_guesses().map_or_else(
+                                    || "-".to_owned(),
+                                    |guesses| format!("{:.3}", guesses)
+                                ),
+                                100.0 * summary.win_rate().unwrap_or(0.0)
+                            )
+                        },
+                    );
+                write!(f, "  {:>cw$}", cell, cw = CELL_WIDTH)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
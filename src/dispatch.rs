@@ -0,0 +1,54 @@
+//! Picking the const-generic word length from one only known at run time,
+//! such as a length given on the command line, so each frontend doesn't
+//! write out the same `match` over lengths.
+
+use std::ops::RangeInclusive;
+
+/// Word lengths [`with_word_length!`](crate::with_word_length) can pick.
+pub const WORD_LENGTHS: RangeInclusive<usize> = 4..=11;
+
+/// Evaluates a body with a const generic word length chosen at run time:
+/// `with_word_length!(length, |N| body)` runs `body` with `N` a `const
+/// usize` equal to `length`, giving `Some` of its value, or `None` if
+/// `length` is outside [`WORD_LENGTHS`]. Each length compiles the body
+/// separately, so every arm has to have the same type.
+#[macro_export]
+macro_rules! with_word_length {
+    ($length:expr, |$n:ident| $body:expr) => {
+        match $length {
+            4 => Some({
+                const $n: usize = 4;
+                $body
+            }),
+            5 => Some({
+                const $n: usize = 5;
+                $body
+            }),
+            6 => Some({
+                const $n: usize = 6;
+                $body
+            }),
+            7 => Some({
+                const $n: usize = 7;
+                $body
+            }),
+            8 => Some({
+                const $n: usize = 8;
+                $body
+            }),
+            9 => Some({
+                const $n: usize = 9;
+                $body
+            }),
+            10 => Some({
+                const $n: usize = 10;
+                $body
+            }),
+            11 => Some({
+                const $n: usize = 11;
+                $body
+            }),
+            _ => None,
+        }
+    };
+}
@@ -0,0 +1,102 @@
+//! Drawing the board as a game goes. Every guess so far is drawn again after
+//! each score, so the whole game stays on screen however much else was
+//! printed in between.
+
+use crate::board::Board;
+use crate::tiles::TileStyle;
+use crate::word::*;
+use std::io::IsTerminal;
+
+/// A way of drawing the board, e.g. in colour for a terminal or as plain
+/// text for a log.
+pub trait Renderer<const WORD_LENGTH: usize> {
+    /// Every row of `board`, one per line.
+    fn render(&self, board: &Board<WORD_LENGTH>) -> String;
+}
+
+/// Each letter on a tile coloured with ANSI escape codes, like the game
+/// itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnsiRenderer {
+    /// Orange and blue instead of green and yellow.
+    pub high_contrast: bool,
+}
+
+impl AnsiRenderer {
+    fn background(&self, tile: LetterScore) -> &'static str {
+        match (tile, self.high_contrast) {
+            (LetterScore::RightPlace, false) => "42",
+            (LetterScore::RightPlace, true) => "48;5;208",
+            (LetterScore::RightLetter, false) => "43",
+            (LetterScore::RightLetter, true) => "44",
+            (LetterScore::Wrong, _) => "100",
+        }
+    }
+}
+
+impl<const WORD_LENGTH: usize> Renderer<WORD_LENGTH> for AnsiRenderer {
+    fn render(&self, board: &Board<WORD_LENGTH>) -> String {
+        board
+            .rows()
+            .iter()
+            .map(|row| {
+                let tiles = row
+                    .guess
+                    .0
+                    .iter()
+                    .zip(&row.score)
+                    .map(|(letter, tile)| {
+                        format!(
+                            "\x1b[1;97;{}m {} \x1b[0m",
+                            self.background(*tile),
+                            letter.to_ascii_uppercase()
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                format!("{}\n", tiles.join(" "))
+            })
+            .collect()
+    }
+}
+
+/// Each row as a [`TileStyle`] draws it, e.g. `🟩⬛🟨⬛⬛ crane`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlainRenderer(pub TileStyle);
+
+impl<const WORD_LENGTH: usize> Renderer<WORD_LENGTH> for PlainRenderer {
+    fn render(&self, board: &Board<WORD_LENGTH>) -> String {
+        board
+            .rows()
+            .iter()
+            .map(|row| format!("{}\n", self.0.row(&row.guess, &row.score)))
+            .collect()
+    }
+}
+
+/// `guess,score` lines in tile shorthand under a header, for spreadsheets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CsvRenderer;
+
+impl<const WORD_LENGTH: usize> Renderer<WORD_LENGTH> for CsvRenderer {
+    fn render(&self, board: &Board<WORD_LENGTH>) -> String {
+        let mut csv = "guess,score\n".to_owned();
+        for row in board.rows() {
+            csv.push_str(&format!("{},{}\n", row.guess, format_score(&row.score)));
+        }
+        csv
+    }
+}
+
+/// How to draw the board in `style`: coloured tiles when stdout is a
+/// terminal and the style is a coloured one, and the style's own rows
+/// otherwise.
+pub fn renderer_for<const WORD_LENGTH: usize>(style: TileStyle) -> Box<dyn Renderer<WORD_LENGTH>> {
+    match style {
+        TileStyle::Color | TileStyle::HighContrast if std::io::stdout().is_terminal() => {
+            Box::new(AnsiRenderer {
+                high_contrast: style == TileStyle::HighContrast,
+            })
+        }
+        _ => Box::new(PlainRenderer(style)),
+    }
+}
@@ -0,0 +1,233 @@
+//! Self-checks behind `wordle-solve doctor`: that the bundled data loads,
+//! that files saved by earlier runs can still be read by this build, and
+//! that scoring gets known cases right. The report is short enough to paste
+//! into a bug report whole.
+
+use crate::artifact::{self, Header};
+use crate::dictionary;
+use crate::history::PlayedPuzzles;
+use crate::nyt;
+use crate::word::*;
+use crate::word_list::WORD_LIST;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::BufReader;
+use std::path::Path;
+
+/// Scores everyone agrees on, duplicate letters included, as (secret,
+/// guess, score).
+const SCORING_CASES: &[(&str, &str, &str)] = &[
+    ("crane", "crane", "ggggg"),
+    ("spilt", "crane", "bbbbb"),
+    ("abbey", "babes", "yyggb"),
+    ("speed", "eerie", "yybbb"),
+    ("those", "geese", "bbbgg"),
+    ("robot", "boost", "ygybg"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: String,
+    /// What was found if the check passed, or what went wrong.
+    pub result: Result<String, String>,
+}
+
+impl Check {
+    fn new(name: impl Into<String>, result: Result<String, String>) -> Self {
+        Self {
+            name: name.into(),
+            result,
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.result {
+            Ok(detail) => write!(f, "[ok]   {}: {}", self.name, detail),
+            Err(problem) => write!(f, "[FAIL] {}: {}", self.name, problem),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report(pub Vec<Check>);
+
+impl Report {
+    pub fn passed(&self) -> bool {
+        self.0.iter().all(|check| check.result.is_ok())
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "wordle-solve {} (file format {})",
+            env!("CARGO_PKG_VERSION"),
+            artifact::VERSION
+        )?;
+        for check in &self.0 {
+            writeln!(f, "{}", check)?;
+        }
+        let failed = self.0.iter().filter(|check| check.result.is_err()).count();
+        match failed {
+            0 => write!(f, "All {} checks passed.", self.0.len()),
+            _ => write!(f, "{} of {} checks failed.", failed, self.0.len()),
+        }
+    }
+}
+
+/// Runs every check. `extra_words` and `history` are where those files are
+/// looked for, and are fine to be missing; each of `artifacts` has to load
+/// with the word list the other commands would use.
+pub fn run(
+    extra_words: Option<&Path>,
+    history: Option<&Path>,
+    artifacts: &[impl AsRef<Path>],
+) -> Report {
+    let (word_list_check, mut word_list) = bundled_list("word list", WORD_LIST);
+    let (answers_check, _) = bundled_list("NYT answers", nyt::ANSWERS);
+    let mut checks = vec![features(), word_list_check, answers_check, scoring()];
+    if let Some(path) = extra_words {
+        let (check, extra) = extra_words_file(path);
+        checks.push(check);
+        if let (Some(list), Some(extra)) = (&mut word_list, extra) {
+            *list = WordList::merge(&[list.clone(), extra], Default::default());
+        }
+    }
+    if let Some(path) = history {
+        checks.push(history_file(path));
+    }
+    for path in artifacts {
+        let path = path.as_ref();
+        checks.push(match &word_list {
+            Some(word_list) => artifact_file(path, word_list),
+            None => Check::new(
+                path.display().to_string(),
+                Err("no word list to check it against".to_owned()),
+            ),
+        });
+    }
+    Report(checks)
+}
+
+/// Which optional features this build has.
+pub fn features() -> Check {
+    let features: &[(&str, bool)] = &[("serde", cfg!(feature = "serde"))];
+    let features = features
+        .iter()
+        .map(|(name, enabled)| format!("{} {}", name, if *enabled { "on" } else { "off" }))
+        .collect::<Vec<_>>();
+    Check::new("features", Ok(features.join(", ")))
+}
+
+/// Loads one of the lists built into the crate, checking every entry is a
+/// five-letter word listed once.
+pub fn bundled_list(name: &str, list: &[&str]) -> (Check, Option<WordList<5>>) {
+    let mut seen = HashSet::new();
+    let mut words = Vec::new();
+    for entry in list {
+        let word = match Word::<5>::try_from(*entry) {
+            Ok(word) => word,
+            Err(_) => return (Check::new(name, Err(format!("bad word {:?}", entry))), None),
+        };
+        if !seen.insert(word) {
+            return (
+                Check::new(name, Err(format!("{} is listed twice", word))),
+                None,
+            );
+        }
+        words.push(word);
+    }
+    if words.is_empty() {
+        return (Check::new(name, Err("empty".to_owned())), None);
+    }
+    (
+        Check::new(name, Ok(format!("{} words", words.len()))),
+        Some(WordList(words)),
+    )
+}
+
+/// Scores [`SCORING_CASES`], and every bundled word against itself.
+pub fn scoring() -> Check {
+    for (secret, guess, expected) in SCORING_CASES {
+        match crate::score(secret, guess) {
+            Ok(score) if score == *expected => {}
+            Ok(score) => {
+                return Check::new(
+                    "scoring",
+                    Err(format!(
+                        "{} against {} scored {}, expected {}",
+                        guess, secret, score, expected
+                    )),
+                )
+            }
+            Err(error) => return Check::new("scoring", Err(error.to_string())),
+        }
+    }
+    let solved = [LetterScore::RightPlace; 5];
+    for word in WORD_LIST
+        .iter()
+        .filter_map(|word| Word::<5>::try_from(*word).ok())
+    {
+        if word.evaluate_guess(&word) != solved {
+            return Check::new("scoring", Err(format!("{} doesn't solve itself", word)));
+        }
+    }
+    Check::new(
+        "scoring",
+        Ok(format!("{} known cases", SCORING_CASES.len())),
+    )
+}
+
+/// Reads the extra words file, if there is one.
+pub fn extra_words_file(path: &Path) -> (Check, Option<WordList<5>>) {
+    let name = format!("extra words ({})", path.display());
+    if !path.is_file() {
+        return (Check::new(name, Ok("none".to_owned())), None);
+    }
+    match std::fs::read_to_string(path)
+        .map_err(|error| error.to_string())
+        .and_then(|text| dictionary::parse_extra_words(&text).map_err(|error| error.to_string()))
+    {
+        Ok(words) => (
+            Check::new(name, Ok(format!("{} words", words.0.len()))),
+            Some(words),
+        ),
+        Err(error) => (Check::new(name, Err(error)), None),
+    }
+}
+
+/// Reads the played-puzzles history, if there is one.
+pub fn history_file(path: &Path) -> Check {
+    let name = format!("history ({})", path.display());
+    let result = match PlayedPuzzles::load(path) {
+        Ok(_) if !path.exists() => Ok("none".to_owned()),
+        Ok(played) => {
+            let (wins, losses) = played.distribution();
+            Ok(format!("{} puzzles", wins.values().sum::<usize>() + losses))
+        }
+        Err(error) => Err(error.to_string()),
+    };
+    Check::new(name, result)
+}
+
+/// Checks a saved artifact was written by this version of the format, for
+/// `word_list`.
+pub fn artifact_file(path: &Path, word_list: &WordList<5>) -> Check {
+    let result = std::fs::File::open(path)
+        .map_err(artifact::ArtifactError::from)
+        .and_then(|file| Header::read(BufReader::new(file)))
+        .and_then(|header| {
+            header.check(header.kind, word_list)?;
+            Ok(header)
+        });
+    Check::new(
+        path.display().to_string(),
+        result
+            .map(|header| format!("{} for the current word list", header.kind))
+            .map_err(|error| error.to_string()),
+    )
+}
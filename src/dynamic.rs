@@ -0,0 +1,204 @@
+//! Words, word lists and strategies whose word length is a value rather
+//! than a const generic, for frontends that only learn the length at run
+//! time, like `solve --length 6`. Each converts to and from its
+//! const-generic counterpart, and [`Solver::build_dyn`] picks the length
+//! with [`with_word_length!`](crate::with_word_length).
+
+use crate::dictionary::{self, DictionaryError};
+use crate::error::WordleError;
+use crate::strategy::{GuessRules, Solver, Strategy, StrategyError};
+use crate::word::*;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A word of any length.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DynWord(pub Vec<char>);
+
+impl DynWord {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The score `guess` gets against this word as the secret.
+    ///
+    /// Panics if the words aren't the same length.
+    pub fn evaluate_guess(&self, guess: &DynWord) -> Vec<LetterScore> {
+        assert_eq!(self.len(), guess.len(), "scored a guess of another length");
+        let mut score = vec![LetterScore::Wrong; self.len()];
+        score_letters(&self.0, &guess.0, &mut score);
+        score
+    }
+}
+
+impl TryFrom<&str> for DynWord {
+    type Error = WordError;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        letters(s).map(DynWord)
+    }
+}
+
+impl<const WORD_LENGTH: usize> From<Word<WORD_LENGTH>> for DynWord {
+    fn from(word: Word<WORD_LENGTH>) -> Self {
+        Self(word.0.to_vec())
+    }
+}
+
+impl<const WORD_LENGTH: usize> TryFrom<&DynWord> for Word<WORD_LENGTH> {
+    type Error = WordError;
+    fn try_from(word: &DynWord) -> Result<Self, Self::Error> {
+        <[char; WORD_LENGTH]>::try_from(word.0.as_slice())
+            .map(Word)
+            .map_err(|_| WordError::Length {
+                expected: WORD_LENGTH,
+                found: word.len(),
+            })
+    }
+}
+
+impl fmt::Display for DynWord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.iter().collect::<String>())
+    }
+}
+
+/// A word list whose words are all `length` letters long.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynWordList {
+    length: usize,
+    words: Vec<DynWord>,
+}
+
+impl DynWordList {
+    /// A list of `words`, or the first word that isn't `length` letters
+    /// long.
+    pub fn new(length: usize, words: Vec<DynWord>) -> Result<Self, WordError> {
+        match words.iter().find(|word| word.len() != length) {
+            Some(word) => Err(WordError::Length {
+                expected: length,
+                found: word.len(),
+            }),
+            None => Ok(Self { length, words }),
+        }
+    }
+
+    /// The `length`-letter words of a word file, as
+    /// [`dictionary::parse_words`] reads them, or `None` if `length` is
+    /// outside [`WORD_LENGTHS`](crate::dispatch::WORD_LENGTHS).
+    pub fn parse_words(length: usize, text: &str) -> Option<Result<Self, DictionaryError>> {
+        crate::with_word_length!(length, |N| dictionary::parse_words::<N>(text)
+            .map(Self::from))
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn words(&self) -> &[DynWord] {
+        &self.words
+    }
+
+    pub fn contains(&self, word: &DynWord) -> bool {
+        self.words.contains(word)
+    }
+
+    /// The list as `WORD_LENGTH`-letter words, if that's their length.
+    pub fn to_list<const WORD_LENGTH: usize>(&self) -> Result<WordList<WORD_LENGTH>, WordError> {
+        self.words.iter().map(Word::try_from).collect()
+    }
+}
+
+impl<const WORD_LENGTH: usize> From<WordList<WORD_LENGTH>> for DynWordList {
+    fn from(list: WordList<WORD_LENGTH>) -> Self {
+        Self {
+            length: WORD_LENGTH,
+            words: list.0.into_iter().map(DynWord::from).collect(),
+        }
+    }
+}
+
+/// A [`Strategy`] for words of a length fixed when it was made, rather than
+/// when it was compiled.
+pub trait DynStrategy {
+    fn word_length(&self) -> usize;
+
+    fn make_guess(&mut self) -> Result<DynWord, StrategyError>;
+
+    /// Panics if `score` isn't [`word_length`](Self::word_length) tiles.
+    fn receive_score(&mut self, score: &[LetterScore]);
+
+    /// Panics if `played` isn't [`word_length`](Self::word_length) letters.
+    fn guess_replaced(&mut self, played: &DynWord) -> Result<(), StrategyError>;
+}
+
+/// A const-generic strategy as a [`DynStrategy`].
+pub struct Erased<S, const WORD_LENGTH: usize>(pub S);
+
+impl<S: Strategy<WORD_LENGTH>, const WORD_LENGTH: usize> DynStrategy for Erased<S, WORD_LENGTH> {
+    fn word_length(&self) -> usize {
+        WORD_LENGTH
+    }
+
+    fn make_guess(&mut self) -> Result<DynWord, StrategyError> {
+        self.0.make_guess().map(DynWord::from)
+    }
+
+    fn receive_score(&mut self, score: &[LetterScore]) {
+        let score = Score::<WORD_LENGTH>::try_from(score)
+            .unwrap_or_else(|_| panic!("expected {} tiles, got {}", WORD_LENGTH, score.len()));
+        self.0.receive_score(&score);
+    }
+
+    fn guess_replaced(&mut self, played: &DynWord) -> Result<(), StrategyError> {
+        let played = Word::try_from(played).unwrap_or_else(|error| panic!("{}", error));
+        self.0.guess_replaced(&played)
+    }
+}
+
+impl Solver {
+    /// Like [`build`](Self::build), for a word list whose length is only
+    /// known at run time; `None` if it's outside
+    /// [`WORD_LENGTHS`](crate::dispatch::WORD_LENGTHS).
+    pub fn build_dyn(self, word_list: &DynWordList) -> Option<Box<dyn DynStrategy>> {
+        crate::with_word_length!(word_list.length(), |N| {
+            let word_list = word_list
+                .to_list::<N>()
+                .expect("the words are all the list's length");
+            Box::new(Erased(self.build(word_list, GuessRules::default()))) as Box<dyn DynStrategy>
+        })
+    }
+}
+
+/// Everything that happened in a game played by [`play`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynTranscript {
+    pub turns: Vec<(DynWord, Vec<LetterScore>)>,
+    pub won: bool,
+}
+
+/// Plays `strategy` against `secret` until it's found or `num_guesses` run
+/// out. Unlike the [engines](crate::engine), any guess of the right length
+/// is scored, since the strategy guesses from its own word list anyway.
+///
+/// Panics if `secret` isn't the strategy's word length.
+pub fn play(
+    secret: &DynWord,
+    strategy: &mut dyn DynStrategy,
+    num_guesses: usize,
+) -> Result<DynTranscript, WordleError> {
+    let mut turns = Vec::new();
+    loop {
+        let guess = strategy.make_guess()?;
+        let score = secret.evaluate_guess(&guess);
+        let won = guess == *secret;
+        turns.push((guess, score));
+        if won || turns.len() == num_guesses {
+            return Ok(DynTranscript { turns, won });
+        }
+        strategy.receive_score(&turns[turns.len() - 1].1);
+    }
+}
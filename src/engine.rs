@@ -1,24 +1,279 @@
+use crate::board::Board;
+use crate::error::{read_stdin_line, WordleError};
+use crate::frequency::Frequencies;
+use crate::record::GameRecord;
+use crate::strategy::StrategyError;
+use crate::tiles::TileStyle;
 use crate::word::*;
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::cell::RefCell;
+use std::collections::HashSet;
 use std::convert::TryInto;
 
-use std::io;
-
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum GuessResult<const WORD_LENGTH: usize> {
-    Done(bool),
+    /// The game is over. `score` is what the last guess scored, when the
+    /// engine scored it on a single board, so a lost game's final row can
+    /// still be shown.
+    Done {
+        won: bool,
+        score: Option<Score<WORD_LENGTH>>,
+    },
     Continue(Score<WORD_LENGTH>),
+    /// The engine refused the guess without using up a turn; the strategy
+    /// should guess again.
+    Rejected(GuessError<WORD_LENGTH>),
+    /// The engine refused the guess but still counted it as a turn; the
+    /// strategy should guess again.
+    Wasted(GuessError<WORD_LENGTH>),
+    /// A multi-board engine scored the guess on every board, and the game
+    /// goes on. Boards solved on an earlier turn get `None`.
+    Boards(Vec<Option<Score<WORD_LENGTH>>>),
+}
+
+/// A guess made during a game and what the engine made of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptTurn<const WORD_LENGTH: usize> {
+    pub guess: Word<WORD_LENGTH>,
+    pub result: GuessResult<WORD_LENGTH>,
+}
+
+/// Everything that happened in a game played by
+/// [`run_game_recorded`](crate::run_game_recorded), refused guesses included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameTranscript<const WORD_LENGTH: usize> {
+    pub turns: Vec<TranscriptTurn<WORD_LENGTH>>,
+    pub won: bool,
+    /// Why the strategy stopped guessing before the game was over, if it
+    /// did. The game counts as lost.
+    pub gave_up: Option<StrategyError>,
+}
+
+/// How a game ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameOutcome {
+    Won,
+    /// The guesses ran out.
+    Lost,
+    /// The strategy couldn't come up with a guess.
+    GaveUp(StrategyError),
+}
+
+impl<const WORD_LENGTH: usize> GameTranscript<WORD_LENGTH> {
+    pub fn outcome(&self) -> GameOutcome {
+        match (self.won, &self.gave_up) {
+            (true, _) => GameOutcome::Won,
+            (false, Some(error)) => GameOutcome::GaveUp(error.clone()),
+            (false, None) => GameOutcome::Lost,
+        }
+    }
+
+    /// Turns used up, which leaves out guesses refused for free.
+    pub fn guesses_used(&self) -> usize {
+        self.turns
+            .iter()
+            .filter(|turn| !matches!(turn.result, GuessResult::Rejected(_)))
+            .count()
+    }
+
+    /// The guesses that got a single score, with it, the one that ended the
+    /// game included. Guesses scored on several boards are left out.
+    pub fn scored_guesses(&self) -> Vec<(Word<WORD_LENGTH>, Score<WORD_LENGTH>)> {
+        self.turns
+            .iter()
+            .filter_map(|turn| match turn.result {
+                GuessResult::Continue(score)
+                | GuessResult::Done {
+                    score: Some(score), ..
+                } => Some((turn.guess, score)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The game as a record to save or analyze, for a game that allowed
+    /// `max_guesses`.
+    pub fn to_record(&self, max_guesses: usize) -> GameRecord<WORD_LENGTH> {
+        let mut board = Board::new();
+        for (guess, score) in self.scored_guesses() {
+            board.push(guess, score, None);
+        }
+        GameRecord {
+            date: None,
+            puzzle: None,
+            max_guesses,
+            board,
+            think_times: Vec::new(),
+        }
+    }
+
+    /// The game's share grid, drawn with `tiles`, headed like the NYT's
+    /// `Wordle 1,210 3/6` when `puzzle` is given.
+    pub fn share_grid(&self, puzzle: Option<u32>, max_guesses: usize, tiles: TileStyle) -> String {
+        GameRecord {
+            puzzle,
+            ..self.to_record(max_guesses)
+        }
+        .share_grid(tiles)
+    }
+}
+
+/// What an engine does with a guess it won't score, since clones differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidGuessPolicy {
+    /// Refuse it and let the player try again for free.
+    #[default]
+    Reject,
+    /// Refuse it, and count it as a turn.
+    ConsumeTurn,
+    /// Lose the game.
+    EndGame,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuessError<const WORD_LENGTH: usize> {
+    /// The word isn't one the engine accepts.
+    NotInWordList(Word<WORD_LENGTH>),
+    /// The word was already guessed this game.
+    Repeated(Word<WORD_LENGTH>),
+    /// Hard mode: a green from an earlier guess was moved or dropped.
+    MissingGreen { letter: char, position: usize },
+    /// Hard mode: a yellow from an earlier guess was left out.
+    MissingYellow { letter: char },
+    /// The word can't be the secret given the score for an earlier guess.
+    Inconsistent { earlier: Word<WORD_LENGTH> },
+}
+
+impl<const WORD_LENGTH: usize> std::fmt::Display for GuessError<WORD_LENGTH> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotInWordList(word) => write!(f, "{} isn't in the word list", word),
+            Self::Repeated(word) => write!(f, "{} was already guessed", word),
+            Self::MissingGreen { letter, position } => {
+                write!(f, "letter {} must be {}", position + 1, letter)
+            }
+            Self::MissingYellow { letter } => write!(f, "guess must contain {}", letter),
+            Self::Inconsistent { earlier } => {
+                write!(f, "guess doesn't fit the clues from {}", earlier)
+            }
+        }
+    }
+}
+
+impl<const WORD_LENGTH: usize> std::error::Error for GuessError<WORD_LENGTH> {}
+
+/// Checks `guess` against hard mode's rules: every green revealed in
+/// `history` stays in place, and every yellow is used somewhere. A letter
+/// revealed more than once in a row has to appear at least that many times.
+pub fn check_hard_mode<const WORD_LENGTH: usize>(
+    guess: &Word<WORD_LENGTH>,
+    history: &[(Word<WORD_LENGTH>, Score<WORD_LENGTH>)],
+) -> Result<(), GuessError<WORD_LENGTH>> {
+    for (previous, score) in history {
+        for (position, (letter, tile)) in previous.0.iter().zip(score).enumerate() {
+            if *tile == LetterScore::RightPlace && guess.0[position] != *letter {
+                return Err(GuessError::MissingGreen {
+                    letter: *letter,
+                    position,
+                });
+            }
+        }
+        for (letter, tile) in previous.0.iter().zip(score) {
+            if *tile != LetterScore::RightLetter {
+                continue;
+            }
+            let revealed = previous
+                .0
+                .iter()
+                .zip(score)
+                .filter(|(other, tile)| *other == letter && **tile != LetterScore::Wrong)
+                .count();
+            let used = guess.0.iter().filter(|other| *other == letter).count();
+            if used < revealed {
+                return Err(GuessError::MissingYellow { letter: *letter });
+            }
+        }
+    }
+    Ok(())
 }
 
 pub trait Engine<const WORD_LENGTH: usize> {
+    /// What becomes of `guess`. Engines that can fail to score panic here
+    /// with the error [`Engine::try_score_guess`] would return.
     fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH>;
+
+    /// Like [`Engine::score_guess`], but an engine that can fail to score,
+    /// such as one asking the player, reports it instead of panicking.
+    /// Engines that can't fail keep the default.
+    fn try_score_guess(
+        &self,
+        guess: &Word<WORD_LENGTH>,
+    ) -> Result<GuessResult<WORD_LENGTH>, WordleError> {
+        Ok(self.score_guess(guess))
+    }
+
+    /// Letters of the secret given away before the first guess. Most
+    /// engines give none.
+    fn free_letters(&self) -> Vec<FreeLetter> {
+        Vec::new()
+    }
+}
+
+impl<E, const WORD_LENGTH: usize> Engine<WORD_LENGTH> for &E
+where
+    E: Engine<WORD_LENGTH> + ?Sized,
+{
+    fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
+        (**self).score_guess(guess)
+    }
+
+    fn try_score_guess(
+        &self,
+        guess: &Word<WORD_LENGTH>,
+    ) -> Result<GuessResult<WORD_LENGTH>, WordleError> {
+        (**self).try_score_guess(guess)
+    }
+
+    fn free_letters(&self) -> Vec<FreeLetter> {
+        (**self).free_letters()
+    }
+}
+
+impl<E, const WORD_LENGTH: usize> Engine<WORD_LENGTH> for Box<E>
+where
+    E: Engine<WORD_LENGTH> + ?Sized,
+{
+    fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
+        (**self).score_guess(guess)
+    }
+
+    fn try_score_guess(
+        &self,
+        guess: &Word<WORD_LENGTH>,
+    ) -> Result<GuessResult<WORD_LENGTH>, WordleError> {
+        (**self).try_score_guess(guess)
+    }
+
+    fn free_letters(&self) -> Vec<FreeLetter> {
+        (**self).free_letters()
+    }
 }
 
 pub struct StandardEngine<const WORD_LENGTH: usize> {
     word: Word<WORD_LENGTH>,
     word_list: WordList<WORD_LENGTH>,
     guesses_remaining: std::cell::Cell<usize>,
+    reject_repeats: bool,
+    guessed: RefCell<HashSet<Word<WORD_LENGTH>>>,
+    free_letters: Vec<FreeLetter>,
+    /// Guesses scored so far, if hard mode's rules are being enforced.
+    hard_mode: Option<RefCell<Vec<(Word<WORD_LENGTH>, Score<WORD_LENGTH>)>>>,
+    invalid_guesses: InvalidGuessPolicy,
 }
 
 impl<const WORD_LENGTH: usize> StandardEngine<WORD_LENGTH> {
+    /// A game of `num_guesses` guesses, or of one if that's zero.
     pub fn new(
         secret_word: Word<WORD_LENGTH>,
         word_list: WordList<WORD_LENGTH>,
@@ -27,77 +282,405 @@ impl<const WORD_LENGTH: usize> StandardEngine<WORD_LENGTH> {
         Self {
             word: secret_word,
             word_list,
-            guesses_remaining: std::cell::Cell::new(num_guesses),
+            guesses_remaining: std::cell::Cell::new(num_guesses.max(1)),
+            reject_repeats: false,
+            guessed: RefCell::new(HashSet::new()),
+            free_letters: Vec::new(),
+            hard_mode: None,
+            invalid_guesses: InvalidGuessPolicy::default(),
+        }
+    }
+
+    /// Refuses words that were already guessed this game instead of letting
+    /// them waste a turn.
+    pub fn reject_repeated_guesses(mut self) -> Self {
+        self.reject_repeats = true;
+        self
+    }
+
+    /// Sets what happens to guesses that are refused: words outside the word
+    /// list, hard mode violations, and repeats if those are refused.
+    pub fn on_invalid_guess(mut self, policy: InvalidGuessPolicy) -> Self {
+        self.invalid_guesses = policy;
+        self
+    }
+
+    fn refuse(&self, error: GuessError<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
+        match self.invalid_guesses {
+            InvalidGuessPolicy::Reject => GuessResult::Rejected(error),
+            InvalidGuessPolicy::ConsumeTurn => {
+                let guesses_remaining = self.guesses_remaining.get() - 1;
+                self.guesses_remaining.set(guesses_remaining);
+                if guesses_remaining == 0 {
+                    GuessResult::Done {
+                        won: false,
+                        score: None,
+                    }
+                } else {
+                    GuessResult::Wasted(error)
+                }
+            }
+            InvalidGuessPolicy::EndGame => GuessResult::Done {
+                won: false,
+                score: None,
+            },
         }
     }
+
+    /// Plays hard mode: refuses guesses that ignore a green or yellow from an
+    /// earlier guess.
+    pub fn hard_mode(mut self) -> Self {
+        self.hard_mode = Some(RefCell::new(Vec::new()));
+        self
+    }
+
+    /// Gives away one more letter of the secret, at a position picked with
+    /// `seed` from those not given away yet, before the first guess.
+    pub fn reveal_free_letter(mut self, seed: u64) -> Self {
+        let hidden = (0..WORD_LENGTH)
+            .filter(|position| {
+                self.free_letters
+                    .iter()
+                    .all(|free_letter| free_letter.position != *position)
+            })
+            .collect::<Vec<_>>();
+        if let Some(position) = hidden.choose(&mut ChaCha8Rng::seed_from_u64(seed)) {
+            self.free_letters.push(FreeLetter {
+                position: *position,
+                letter: self.word.0[*position],
+            });
+        }
+        self
+    }
 }
 
 impl<const WORD_LENGTH: usize> Engine<WORD_LENGTH> for StandardEngine<WORD_LENGTH> {
     fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
-        let score = self
-            .word_list
+        if !self.word_list.0.contains(guess) {
+            return self.refuse(GuessError::NotInWordList(*guess));
+        }
+        if let Some(history) = &self.hard_mode {
+            if let Err(error) = check_hard_mode(guess, &history.borrow()) {
+                return self.refuse(error);
+            }
+        }
+        if !self.guessed.borrow_mut().insert(*guess) && self.reject_repeats {
+            return self.refuse(GuessError::Repeated(*guess));
+        }
+        let score = self.word.evaluate_guess(guess);
+        if let Some(history) = &self.hard_mode {
+            history.borrow_mut().push((*guess, score));
+        }
+
+        let guesses_remaining = self.guesses_remaining.get() - 1;
+        self.guesses_remaining.set(guesses_remaining);
+
+        let won = score
+            .iter()
+            .all(|annotation| *annotation == LetterScore::RightPlace);
+        if won || guesses_remaining == 0 {
+            GuessResult::Done {
+                won,
+                score: Some(score),
+            }
+        } else {
+            GuessResult::Continue(score)
+        }
+    }
+
+    fn free_letters(&self) -> Vec<FreeLetter> {
+        self.free_letters.clone()
+    }
+}
+
+/// Picks a secret from `answers` with `seed`, uniformly, or in proportion to
+/// each word's weight in `frequencies` if given. `None` if there are no
+/// answers.
+pub fn random_secret<const WORD_LENGTH: usize>(
+    answers: &WordList<WORD_LENGTH>,
+    seed: u64,
+    frequencies: Option<&Frequencies<WORD_LENGTH>>,
+) -> Option<Word<WORD_LENGTH>> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    match frequencies {
+        Some(frequencies) => answers
             .0
-            .contains(&guess)
-            .then(|| self.word.evaluate_guess(&guess))
-            .expect(&format!("guess not in wordlist: {}", guess));
+            .choose_weighted(&mut rng, |word| frequencies.weight(word))
+            .ok()
+            .copied(),
+        None => answers.0.choose(&mut rng).copied(),
+    }
+}
+
+/// A [`StandardEngine`] that picks its own secret with a seeded RNG, so a
+/// game against a random word can be played again.
+pub struct RandomSecretEngine<const WORD_LENGTH: usize> {
+    engine: StandardEngine<WORD_LENGTH>,
+    secret: Word<WORD_LENGTH>,
+}
+
+impl<const WORD_LENGTH: usize> RandomSecretEngine<WORD_LENGTH> {
+    /// A game of `num_guesses` guesses against a word picked uniformly from
+    /// `word_list` with `seed`. `None` if `word_list` is empty.
+    pub fn new(word_list: WordList<WORD_LENGTH>, num_guesses: usize, seed: u64) -> Option<Self> {
+        Self::pick(word_list, num_guesses, seed, None)
+    }
+
+    /// Like [`RandomSecretEngine::new`], but more common words are more
+    /// likely to be picked, going by `frequencies`.
+    pub fn weighted(
+        word_list: WordList<WORD_LENGTH>,
+        num_guesses: usize,
+        seed: u64,
+        frequencies: &Frequencies<WORD_LENGTH>,
+    ) -> Option<Self> {
+        Self::pick(word_list, num_guesses, seed, Some(frequencies))
+    }
+
+    fn pick(
+        word_list: WordList<WORD_LENGTH>,
+        num_guesses: usize,
+        seed: u64,
+        frequencies: Option<&Frequencies<WORD_LENGTH>>,
+    ) -> Option<Self> {
+        let secret = random_secret(&word_list, seed, frequencies)?;
+        Some(Self {
+            engine: StandardEngine::new(secret, word_list, num_guesses),
+            secret,
+        })
+    }
+
+    /// The word that was picked, to reveal after the game.
+    pub fn secret(&self) -> Word<WORD_LENGTH> {
+        self.secret
+    }
+}
+
+impl<const WORD_LENGTH: usize> Engine<WORD_LENGTH> for RandomSecretEngine<WORD_LENGTH> {
+    fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
+        self.engine.score_guess(guess)
+    }
+}
+
+/// Engine for Antiwordle, where the player loses by finding the secret. Every
+/// guess has to be consistent with all the clues revealed so far, so the
+/// player is forced ever closer to the answer.
+pub struct AntiWordleEngine<const WORD_LENGTH: usize> {
+    word: Word<WORD_LENGTH>,
+    word_list: WordList<WORD_LENGTH>,
+    board: RefCell<Board<WORD_LENGTH>>,
+}
+
+impl<const WORD_LENGTH: usize> AntiWordleEngine<WORD_LENGTH> {
+    pub fn new(secret_word: Word<WORD_LENGTH>, word_list: WordList<WORD_LENGTH>) -> Self {
+        Self {
+            word: secret_word,
+            word_list,
+            board: RefCell::new(Board::new()),
+        }
+    }
+
+    /// Number of guesses played so far, including the one that hit the secret.
+    pub fn turns_taken(&self) -> usize {
+        self.board.borrow().len()
+    }
+
+    pub fn board(&self) -> Board<WORD_LENGTH> {
+        self.board.borrow().clone()
+    }
+}
+
+impl<const WORD_LENGTH: usize> Engine<WORD_LENGTH> for AntiWordleEngine<WORD_LENGTH> {
+    fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
+        if !self.word_list.0.contains(guess) {
+            return GuessResult::Rejected(GuessError::NotInWordList(*guess));
+        }
+        if let Some(row) = self
+            .board
+            .borrow()
+            .rows()
+            .iter()
+            .find(|row| !guess.is_consistent_with(&row.guess, &row.score))
+        {
+            return GuessResult::Rejected(GuessError::Inconsistent { earlier: row.guess });
+        }
+
+        let score = self.word.evaluate_guess(guess);
+        self.board.borrow_mut().push(*guess, score, None);
+
+        if *guess == self.word {
+            GuessResult::Done {
+                won: false,
+                score: Some(score),
+            }
+        } else {
+            GuessResult::Continue(score)
+        }
+    }
+}
+
+/// Engine for Absurdle, which never picks a secret: each guess gets the score
+/// that keeps the most words possible, and the game is only won once the
+/// guess is the last word left. There's no turn limit.
+pub struct AbsurdleEngine<const WORD_LENGTH: usize> {
+    word_list: WordList<WORD_LENGTH>,
+    candidates: RefCell<WordList<WORD_LENGTH>>,
+    turns_taken: std::cell::Cell<usize>,
+}
+
+impl<const WORD_LENGTH: usize> AbsurdleEngine<WORD_LENGTH> {
+    pub fn new(word_list: WordList<WORD_LENGTH>) -> Self {
+        Self {
+            candidates: RefCell::new(word_list.clone()),
+            word_list,
+            turns_taken: std::cell::Cell::new(0),
+        }
+    }
+
+    /// Number of guesses played so far, including the winning one.
+    pub fn turns_taken(&self) -> usize {
+        self.turns_taken.get()
+    }
+
+    /// The words that could still be the secret.
+    pub fn candidates(&self) -> WordList<WORD_LENGTH> {
+        self.candidates.borrow().clone()
+    }
+}
 
-        let guesses_remaining = self.guesses_remaining.get();
+impl<const WORD_LENGTH: usize> Engine<WORD_LENGTH> for AbsurdleEngine<WORD_LENGTH> {
+    fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
+        if !self.word_list.0.contains(guess) {
+            return GuessResult::Rejected(GuessError::NotInWordList(*guess));
+        }
+        self.turns_taken.set(self.turns_taken.get() + 1);
+        let (score, remaining) = self
+            .candidates
+            .borrow()
+            .adversarial_score(guess)
+            .expect("some word always fits the scores given so far");
+        *self.candidates.borrow_mut() = remaining;
 
         if score
             .iter()
             .all(|annotation| *annotation == LetterScore::RightPlace)
         {
-            GuessResult::Done(true)
-        } else if guesses_remaining == 0 {
-            GuessResult::Done(false)
+            GuessResult::Done {
+                won: true,
+                score: Some(score),
+            }
         } else {
-            self.guesses_remaining.set(guesses_remaining - 1);
             GuessResult::Continue(score)
         }
     }
 }
 
-pub struct StdinEvaluator<const WORD_LENGTH: usize>;
+/// Engine for Dordle, Quordle and the like: every guess is played on
+/// `BOARDS` boards at once, each with its own secret, and the game is won
+/// once all of them are solved within the turn limit.
+pub struct MultiBoardEngine<const WORD_LENGTH: usize, const BOARDS: usize> {
+    words: [Word<WORD_LENGTH>; BOARDS],
+    word_list: WordList<WORD_LENGTH>,
+    guesses_remaining: std::cell::Cell<usize>,
+    solved: std::cell::Cell<[bool; BOARDS]>,
+}
 
-impl<const WORD_LENGTH: usize> StdinEvaluator<WORD_LENGTH> {
-    fn letter_score_of_char(c: char) -> Option<LetterScore> {
-        use LetterScore::*;
-        match c.to_ascii_lowercase() {
-            'g' => Some(RightPlace),
-            'y' => Some(RightLetter),
-            'b' => Some(Wrong),
-            _ => None,
+impl<const WORD_LENGTH: usize, const BOARDS: usize> MultiBoardEngine<WORD_LENGTH, BOARDS> {
+    /// A game of `num_guesses` guesses, or of one if that's zero.
+    pub fn new(
+        secret_words: [Word<WORD_LENGTH>; BOARDS],
+        word_list: WordList<WORD_LENGTH>,
+        num_guesses: usize,
+    ) -> Self {
+        Self {
+            words: secret_words,
+            word_list,
+            guesses_remaining: std::cell::Cell::new(num_guesses.max(1)),
+            solved: std::cell::Cell::new([false; BOARDS]),
         }
     }
 
-    fn read_score(&self) -> Option<Score<WORD_LENGTH>> {
-        let mut buffer = String::new();
-        io::stdin()
-            .read_line(&mut buffer)
-            .expect("Failed to read line.");
+    /// Which boards have been solved so far.
+    pub fn solved(&self) -> [bool; BOARDS] {
+        self.solved.get()
+    }
+}
 
-        println!("buffer: {:?}", buffer);
+impl<const WORD_LENGTH: usize, const BOARDS: usize> Engine<WORD_LENGTH>
+    for MultiBoardEngine<WORD_LENGTH, BOARDS>
+{
+    fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
+        if !self.word_list.0.contains(guess) {
+            return GuessResult::Rejected(GuessError::NotInWordList(*guess));
+        }
+        let mut solved = self.solved.get();
+        let scores = self
+            .words
+            .iter()
+            .zip(solved.iter_mut())
+            .map(|(word, solved)| {
+                if *solved {
+                    return None;
+                }
+                *solved = word == guess;
+                Some(word.evaluate_guess(guess))
+            })
+            .collect();
+        self.solved.set(solved);
 
-        let score_vec = buffer
-            .trim_end()
-            .chars()
-            .filter_map(Self::letter_score_of_char)
-            .collect::<Vec<_>>();
+        let guesses_remaining = self.guesses_remaining.get() - 1;
+        self.guesses_remaining.set(guesses_remaining);
 
-        println!("score_vec: {:?}", score_vec);
+        let won = solved.iter().all(|solved| *solved);
+        if won || guesses_remaining == 0 {
+            GuessResult::Done { won, score: None }
+        } else {
+            GuessResult::Boards(scores)
+        }
+    }
+}
+
+/// Asks for each score on stdin, for playing along with a game elsewhere. A
+/// `!` instead of a score says the game didn't accept the guess.
+pub struct StdinEvaluator<const WORD_LENGTH: usize>;
 
-        (score_vec.len() == WORD_LENGTH).then(|| score_vec.try_into().unwrap())
+impl<const WORD_LENGTH: usize> StdinEvaluator<WORD_LENGTH> {
+    /// `Some(None)` if the guess was refused.
+    fn read_score(&self) -> Result<Option<Option<Score<WORD_LENGTH>>>, WordleError> {
+        let buffer = read_stdin_line()?;
+        if buffer.trim() == "!" {
+            return Ok(Some(None));
+        }
+
+        // Tiles pasted from a share grid, or letters like `bygbb`.
+        let score_vec = TileStyle::parse_tiles(buffer.trim()).unwrap_or_else(|| {
+            buffer
+                .trim_end()
+                .chars()
+                .filter_map(LetterScore::from_char)
+                .collect()
+        });
+        Ok(score_vec.try_into().ok().map(Some))
     }
 }
 
 impl<const WORD_LENGTH: usize> Engine<WORD_LENGTH> for StdinEvaluator<WORD_LENGTH> {
     fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
-        println!("Enter score for {}:", guess);
+        self.try_score_guess(guess)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_score_guess(
+        &self,
+        guess: &Word<WORD_LENGTH>,
+    ) -> Result<GuessResult<WORD_LENGTH>, WordleError> {
+        println!("Enter score for {} (or ! if it wasn't accepted):", guess);
         loop {
-            if let Some(score) = self.read_score() {
-                break GuessResult::Continue(score);
+            match self.read_score()? {
+                Some(Some(score)) => break Ok(GuessResult::Continue(score)),
+                Some(None) => break Ok(GuessResult::Rejected(GuessError::NotInWordList(*guess))),
+                None => println!("Invalid score, try again:"),
             }
-            println!("Invalid score, try again:");
         }
     }
 }
@@ -0,0 +1,117 @@
+//! A strategy that blends the rankings of several others, e.g. a fast
+//! heuristic with a slower exact method.
+
+use crate::ranking::Ranker;
+use crate::strategy::{Strategy, StrategyError};
+use crate::word::*;
+use std::collections::{HashMap, HashSet};
+
+/// How the members' rankings are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vote {
+    /// Each member gives a candidate one point for every candidate it ranks
+    /// below it, scaled by the member's weight.
+    Borda,
+    /// Each member votes for its top candidate with its weight.
+    Plurality,
+}
+
+pub struct EnsembleStrategy<const WORD_LENGTH: usize> {
+    word_list: WordList<WORD_LENGTH>,
+    viable_words: WordList<WORD_LENGTH>,
+    members: Vec<(Box<dyn Ranker<WORD_LENGTH>>, f64)>,
+    vote: Vote,
+    guessed: HashSet<Word<WORD_LENGTH>>,
+    last_guess: Option<Word<WORD_LENGTH>>,
+}
+
+impl<const WORD_LENGTH: usize> EnsembleStrategy<WORD_LENGTH> {
+    /// An ensemble with no members yet; add some with
+    /// [`EnsembleStrategy::with_member`].
+    pub fn new(word_list: WordList<WORD_LENGTH>, vote: Vote) -> Self {
+        Self {
+            viable_words: word_list.clone(),
+            word_list,
+            members: Vec::new(),
+            vote,
+            guessed: HashSet::new(),
+            last_guess: None,
+        }
+    }
+
+    pub fn with_member(mut self, ranker: impl Ranker<WORD_LENGTH> + 'static, weight: f64) -> Self {
+        self.members.push((Box::new(ranker), weight));
+        self
+    }
+
+    /// Total points for each candidate. Candidates no member mentioned get
+    /// nothing.
+    fn tally(&self, candidates: &WordList<WORD_LENGTH>) -> HashMap<Word<WORD_LENGTH>, f64> {
+        let mut points = HashMap::new();
+        for (ranker, weight) in &self.members {
+            let ranking = ranker.rank(candidates, &self.viable_words);
+            match self.vote {
+                Vote::Borda => {
+                    let len = ranking.len();
+                    for (position, word) in ranking.into_iter().enumerate() {
+                        *points.entry(word).or_insert(0.0) += weight * (len - position - 1) as f64;
+                    }
+                }
+                Vote::Plurality => {
+                    if let Some(word) = ranking.first() {
+                        *points.entry(*word).or_insert(0.0) += weight;
+                    }
+                }
+            }
+        }
+        points
+    }
+}
+
+impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for EnsembleStrategy<WORD_LENGTH> {
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        assert!(!self.members.is_empty(), "ensemble has no members");
+        let guess = if let [only] = self.viable_words.0[..] {
+            only
+        } else {
+            let mut candidates = self.word_list.clone();
+            candidates.0.retain(|word| !self.guessed.contains(word));
+            let points = self.tally(&candidates);
+            // Exact ties go to the earliest word in the list.
+            *candidates
+                .0
+                .iter()
+                .rev()
+                .max_by(|a, b| {
+                    let points = |word| points.get(word).copied().unwrap_or(0.0);
+                    points(a).total_cmp(&points(b))
+                })
+                .ok_or(StrategyError::OutOfCandidates)?
+        };
+        self.guessed.insert(guess);
+        self.last_guess = Some(guess);
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        let last_guess = self.last_guess.expect("should've made a guess by now");
+        self.viable_words.retain_viable_words(&last_guess, score);
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        if let Some(suggested) = self.last_guess.replace(*played) {
+            self.guessed.remove(&suggested);
+        }
+        self.guessed.insert(*played);
+        Ok(())
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.viable_words.retain_fitting(clues);
+        for clue in clues {
+            if let Clue::Scored(guess, _) = clue {
+                self.guessed.insert(*guess);
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Failures that can end a game early without being a bug in the solver,
+//! such as the player's input running out.
+
+use crate::strategy::StrategyError;
+use std::fmt;
+use std::io;
+use std::sync::Arc;
+
+#[derive(Debug)]
+pub enum WordleError {
+    Io(io::Error),
+    /// Stdin closed while waiting for the player, or another program's
+    /// output ended while waiting for it.
+    InputClosed,
+    /// The solver was asked to take over a game it was already playing.
+    SolverAlreadyStarted,
+    /// The strategy gave up; games played by
+    /// [`try_run_game`](crate::try_run_game) end as lost instead.
+    Strategy(StrategyError),
+    /// Another program sent something it shouldn't have.
+    Protocol(String),
+}
+
+impl fmt::Display for WordleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "couldn't read input: {}", error),
+            Self::InputClosed => f.write_str("input ended before the game did"),
+            Self::SolverAlreadyStarted => f.write_str("the solver has already taken over"),
+            Self::Strategy(error) => error.fmt(f),
+            Self::Protocol(message) => write!(f, "bad message: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for WordleError {}
+
+impl From<io::Error> for WordleError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<StrategyError> for WordleError {
+    fn from(error: StrategyError) -> Self {
+        match error {
+            // Hand back what went wrong with the input rather than wrapping
+            // it twice.
+            StrategyError::Input(error) => Arc::try_unwrap(error)
+                .unwrap_or_else(|error| Self::Strategy(StrategyError::Input(error))),
+            error => Self::Strategy(error),
+        }
+    }
+}
+
+/// Reads a line the player typed, newline included.
+pub(crate) fn read_stdin_line() -> Result<String, WordleError> {
+    let mut buffer = String::new();
+    if io::stdin().read_line(&mut buffer)? == 0 {
+        return Err(WordleError::InputClosed);
+    }
+    Ok(buffer)
+}
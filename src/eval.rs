@@ -0,0 +1,377 @@
+use crate::engine::{GuessError, StandardEngine};
+use crate::letter_stats::LetterStats;
+use crate::metrics::Metrics;
+use crate::strategy::{HumanLikeStrategy, SimpleStrategy, Strategy, StrategyError};
+use crate::word::*;
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+/// Outcome of playing a strategy against every secret in an answer list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EvaluationSummary {
+    pub games: usize,
+    pub wins: usize,
+    /// Guesses used across all won games.
+    pub guesses_in_wins: usize,
+}
+
+impl EvaluationSummary {
+    /// `None` if no games were played.
+    pub fn win_rate(&self) -> Option<f64> {
+        (self.games > 0).then(|| self.wins as f64 / self.games as f64)
+    }
+
+    /// `None` if no games were won.
+    pub fn average_guesses(&self) -> Option<f64> {
+        (self.wins > 0).then(|| self.guesses_in_wins as f64 / self.wins as f64)
+    }
+}
+
+/// How a strategy fares when it may only guess from a restricted dictionary,
+/// compared to guessing from the full one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RestrictedGuessReport {
+    pub full: EvaluationSummary,
+    pub restricted: EvaluationSummary,
+}
+
+impl RestrictedGuessReport {
+    /// Drop in win rate caused by the restriction, `None` if no games were
+    /// played.
+    pub fn win_rate_loss(&self) -> Option<f64> {
+        Some(self.full.win_rate()? - self.restricted.win_rate()?)
+    }
+
+    /// Extra guesses per won game caused by the restriction, `None` if
+    /// either side won no games to compare.
+    pub fn extra_guesses(&self) -> Option<f64> {
+        Some(self.restricted.average_guesses()? - self.full.average_guesses()?)
+    }
+}
+
+/// Counts the guesses made by the strategy it wraps, and totals up the work
+/// that went into them.
+struct Counted<S> {
+    strategy: S,
+    guesses: usize,
+    metrics: Metrics,
+}
+
+impl<S> Counted<S> {
+    fn new(strategy: S) -> Self {
+        Self {
+            strategy,
+            guesses: 0,
+            metrics: Metrics::default(),
+        }
+    }
+
+    fn count<const WORD_LENGTH: usize>(&mut self)
+    where
+        S: Strategy<WORD_LENGTH>,
+    {
+        self.guesses += 1;
+        if let Some(metrics) = self.strategy.last_metrics() {
+            self.metrics += metrics;
+        }
+    }
+}
+
+impl<S, const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for Counted<S>
+where
+    S: Strategy<WORD_LENGTH>,
+{
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        let guess = self.strategy.make_guess()?;
+        self.count();
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        self.strategy.receive_score(score)
+    }
+
+    fn receive_scores(
+        &mut self,
+        scores: &[Option<Score<WORD_LENGTH>>],
+    ) -> Result<(), StrategyError> {
+        self.strategy.receive_scores(scores)
+    }
+
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        // A refused guess doesn't use up a turn.
+        self.guesses -= 1;
+        self.strategy.guess_rejected(guess, error)
+    }
+
+    fn guess_wasted(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        // Still a turn, so it stays counted.
+        self.strategy.guess_wasted(guess, error)
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        self.strategy.guess_replaced(played)
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.strategy.prime(clues)
+    }
+
+    fn last_metrics(&self) -> Option<Metrics> {
+        self.strategy.last_metrics()
+    }
+}
+
+/// Plays a fresh strategy from `make_strategy` against every word in
+/// `secrets`, with the engine accepting any word in `allowed_guesses`.
+pub fn evaluate<S, F, const WORD_LENGTH: usize>(
+    make_strategy: F,
+    secrets: &WordList<WORD_LENGTH>,
+    allowed_guesses: &WordList<WORD_LENGTH>,
+    num_guesses: usize,
+) -> EvaluationSummary
+where
+    S: Strategy<WORD_LENGTH>,
+    F: Fn() -> S + Sync,
+{
+    evaluate_games(|_| make_strategy(), secrets, allowed_guesses, num_guesses)
+}
+
+/// Like [`evaluate`], but `make_strategy` is told which game it's for, by
+/// the secret's index in `secrets`, so e.g. each game can be seeded
+/// differently.
+pub fn evaluate_games<S, F, const WORD_LENGTH: usize>(
+    make_strategy: F,
+    secrets: &WordList<WORD_LENGTH>,
+    allowed_guesses: &WordList<WORD_LENGTH>,
+    num_guesses: usize,
+) -> EvaluationSummary
+where
+    S: Strategy<WORD_LENGTH>,
+    F: Fn(usize) -> S + Sync,
+{
+    summarize(&play_games(
+        make_strategy,
+        secrets,
+        allowed_guesses,
+        num_guesses,
+    ))
+}
+
+/// Totals up games as returned by [`play_games`].
+pub(crate) fn summarize(games: &[Option<usize>]) -> EvaluationSummary {
+    games
+        .iter()
+        .fold(EvaluationSummary::default(), |mut summary, guesses| {
+            summary.games += 1;
+            if let Some(guesses) = guesses {
+                summary.wins += 1;
+                summary.guesses_in_wins += guesses;
+            }
+            summary
+        })
+}
+
+/// Plays every game in parallel and returns, in the order of `secrets`, the
+/// number of guesses each won game took, or `None` for a loss. Games don't
+/// share any state, so the results are the same however they're scheduled.
+pub(crate) fn play_games<S, F, const WORD_LENGTH: usize>(
+    make_strategy: F,
+    secrets: &WordList<WORD_LENGTH>,
+    allowed_guesses: &WordList<WORD_LENGTH>,
+    num_guesses: usize,
+) -> Vec<Option<usize>>
+where
+    S: Strategy<WORD_LENGTH>,
+    F: Fn(usize) -> S + Sync,
+{
+    play_games_metered(make_strategy, secrets, allowed_guesses, num_guesses)
+        .into_iter()
+        .map(|(guesses, _)| guesses)
+        .collect()
+}
+
+/// Like [`play_games`], but with the work each game's guesses took, for
+/// strategies that keep count.
+pub(crate) fn play_games_metered<S, F, const WORD_LENGTH: usize>(
+    make_strategy: F,
+    secrets: &WordList<WORD_LENGTH>,
+    allowed_guesses: &WordList<WORD_LENGTH>,
+    num_guesses: usize,
+) -> Vec<(Option<usize>, Metrics)>
+where
+    S: Strategy<WORD_LENGTH>,
+    F: Fn(usize) -> S + Sync,
+{
+    secrets
+        .0
+        .par_iter()
+        .enumerate()
+        .map(|(game, secret)| {
+            let engine = StandardEngine::new(*secret, allowed_guesses.clone(), num_guesses);
+            let mut strategy = Counted::new(make_strategy(game));
+            let won = crate::run_game(engine, &mut strategy);
+            (won.then_some(strategy.guesses), strategy.metrics)
+        })
+        .collect()
+}
+
+/// Result of tuning a strategy on one half of the answers and then playing
+/// it on the other half, which it never saw while being tuned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossValidation<P> {
+    /// The candidate that did best on the training half.
+    pub chosen: P,
+    pub train: EvaluationSummary,
+    pub test: EvaluationSummary,
+}
+
+impl<P> CrossValidation<P> {
+    /// Extra guesses per win on the held-out answers compared to the
+    /// training ones; well above zero means the tuning overfit. `None` if
+    /// either half won no games to compare.
+    pub fn generalization_gap(&self) -> Option<f64> {
+        Some(self.test.average_guesses()? - self.train.average_guesses()?)
+    }
+}
+
+/// Shuffles `answers` with `seed` and splits them in half, for training and
+/// testing.
+pub fn split_answers<const WORD_LENGTH: usize>(
+    answers: &WordList<WORD_LENGTH>,
+    seed: u64,
+) -> (WordList<WORD_LENGTH>, WordList<WORD_LENGTH>) {
+    let mut shuffled = answers.0.clone();
+    shuffled.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+    let test = shuffled.split_off(shuffled.len() / 2);
+    (WordList(shuffled), WordList(test))
+}
+
+/// Plays a strategy from `make_strategy` for each of `candidates` (e.g.
+/// different openers) on a random half of `answers`, picks the one that wins
+/// most often in the fewest guesses, and evaluates it on the other half.
+/// Exact ties go to the earliest candidate. `None` if there are no
+/// candidates.
+pub fn cross_validate<P, S, F, const WORD_LENGTH: usize>(
+    candidates: &[P],
+    make_strategy: F,
+    answers: &WordList<WORD_LENGTH>,
+    allowed_guesses: &WordList<WORD_LENGTH>,
+    num_guesses: usize,
+    seed: u64,
+) -> Option<CrossValidation<P>>
+where
+    P: Clone + Sync,
+    S: Strategy<WORD_LENGTH>,
+    F: Fn(&P) -> S + Sync,
+{
+    let (train, test) = split_answers(answers, seed);
+    let (chosen, train) = candidates
+        .iter()
+        .map(|candidate| {
+            let summary = evaluate(
+                || make_strategy(candidate),
+                &train,
+                allowed_guesses,
+                num_guesses,
+            );
+            (candidate, summary)
+        })
+        .rev()
+        .max_by(|(_, a), (_, b)| {
+            // Winning nothing is as bad as it gets on both counts.
+            let win_rate = |summary: &EvaluationSummary| summary.win_rate().unwrap_or(0.0);
+            let guesses =
+                |summary: &EvaluationSummary| summary.average_guesses().unwrap_or(f64::INFINITY);
+            win_rate(a)
+                .total_cmp(&win_rate(b))
+                .then(guesses(b).total_cmp(&guesses(a)))
+        })?;
+    let test = evaluate(
+        || make_strategy(chosen),
+        &test,
+        allowed_guesses,
+        num_guesses,
+    );
+    Some(CrossValidation {
+        chosen: chosen.clone(),
+        train,
+        test,
+    })
+}
+
+/// Measures how much [`SimpleStrategy`] degrades when it may only guess from
+/// `restricted_guesses` (e.g. common words only) instead of `full_guesses`,
+/// with secrets drawn from `answers` either way.
+pub fn evaluate_restricted_guesses<const WORD_LENGTH: usize>(
+    answers: &WordList<WORD_LENGTH>,
+    full_guesses: &WordList<WORD_LENGTH>,
+    restricted_guesses: &WordList<WORD_LENGTH>,
+    num_guesses: usize,
+) -> RestrictedGuessReport {
+    let full = evaluate(
+        || SimpleStrategy::with_guess_list(full_guesses.clone(), answers.clone()),
+        answers,
+        full_guesses,
+        num_guesses,
+    );
+    let restricted = evaluate(
+        || SimpleStrategy::with_guess_list(restricted_guesses.clone(), answers.clone()),
+        answers,
+        full_guesses,
+        num_guesses,
+    );
+    RestrictedGuessReport { full, restricted }
+}
+
+/// Average number of guesses [`HumanLikeStrategy`] needs to find `secret`
+/// over `trials` seeded games (counting a loss as `num_guesses + 1`), as a
+/// baseline for judging how a particular game went.
+pub fn human_baseline<const WORD_LENGTH: usize>(
+    secret: &Word<WORD_LENGTH>,
+    word_list: &WordList<WORD_LENGTH>,
+    num_guesses: usize,
+    mistake_rate: f64,
+    trials: u64,
+) -> f64 {
+    let letter_stats = Arc::new(LetterStats::new(word_list));
+    let total: usize = (0..trials)
+        .map(|seed| {
+            let engine = StandardEngine::new(*secret, word_list.clone(), num_guesses);
+            let mut strategy = Counted::new(HumanLikeStrategy::with_letter_stats(
+                word_list.clone(),
+                letter_stats.clone(),
+                mistake_rate,
+                seed,
+            ));
+            if crate::run_game(engine, &mut strategy) {
+                strategy.guesses
+            } else {
+                num_guesses + 1
+            }
+        })
+        .sum();
+    total as f64 / trials as f64
+}
+
+/// How many fewer guesses `guesses_used` is than the typical human would
+/// need for `secret`; positive means the game beat the human baseline.
+pub fn guesses_saved_over_human<const WORD_LENGTH: usize>(
+    secret: &Word<WORD_LENGTH>,
+    guesses_used: usize,
+    word_list: &WordList<WORD_LENGTH>,
+    num_guesses: usize,
+) -> f64 {
+    human_baseline(secret, word_list, num_guesses, 0.2, 20) - guesses_used as f64
+}
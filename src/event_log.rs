@@ -0,0 +1,161 @@
+//! A timestamped record of what an engine was asked and what it answered,
+//! for working out afterwards where a game went wrong, such as a score the
+//! player typed differently from what the solver believed.
+
+use crate::board::Board;
+use crate::engine::{Engine, GuessError, GuessResult};
+use crate::error::WordleError;
+use crate::word::*;
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineEvent<const WORD_LENGTH: usize> {
+    GuessReceived(Word<WORD_LENGTH>),
+    /// The engine refused the guess, using up a turn if `wasted`.
+    Refused {
+        error: GuessError<WORD_LENGTH>,
+        wasted: bool,
+    },
+    /// The engine took the guess and gave this score.
+    Scored(Score<WORD_LENGTH>),
+    /// The guess was scored on every board; `None` for boards already solved.
+    ScoredBoards(Vec<Option<Score<WORD_LENGTH>>>),
+    GameOver {
+        won: bool,
+    },
+    /// The engine couldn't score the guess at all.
+    Failed(String),
+}
+
+impl<const WORD_LENGTH: usize> fmt::Display for EngineEvent<WORD_LENGTH> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::GuessReceived(guess) => write!(f, "guess received: {}", guess),
+            Self::Refused { error, wasted } => write!(
+                f,
+                "refused{}: {}",
+                if *wasted { ", turn used" } else { "" },
+                error
+            ),
+            Self::Scored(score) => write!(f, "scored: {}", format_score(score)),
+            Self::ScoredBoards(scores) => {
+                let scores = scores
+                    .iter()
+                    .map(|score| score.as_ref().map_or("-".to_owned(), format_score))
+                    .collect::<Vec<_>>();
+                write!(f, "scored: {}", scores.join(" "))
+            }
+            Self::GameOver { won } => write!(f, "game over: {}", if *won { "won" } else { "lost" }),
+            Self::Failed(error) => write!(f, "failed: {}", error),
+        }
+    }
+}
+
+/// Wraps an engine and records every event, with how long after the game
+/// started it happened. Pass the engine by reference to read the log after
+/// the game.
+pub struct LoggedEngine<E, const WORD_LENGTH: usize> {
+    engine: E,
+    started: Instant,
+    events: RefCell<Vec<(Duration, EngineEvent<WORD_LENGTH>)>>,
+}
+
+impl<E, const WORD_LENGTH: usize> LoggedEngine<E, WORD_LENGTH>
+where
+    E: Engine<WORD_LENGTH>,
+{
+    pub fn new(engine: E) -> Self {
+        Self {
+            engine,
+            started: Instant::now(),
+            events: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Everything recorded so far, oldest first.
+    pub fn events(&self) -> Vec<(Duration, EngineEvent<WORD_LENGTH>)> {
+        self.events.borrow().clone()
+    }
+
+    /// The rows scored so far, as the player's board would show them.
+    pub fn board(&self) -> Board<WORD_LENGTH> {
+        let mut board = Board::new();
+        let mut guess = None;
+        for (_, event) in self.events.borrow().iter() {
+            match event {
+                EngineEvent::GuessReceived(received) => guess = Some(*received),
+                EngineEvent::Scored(score) => {
+                    if let Some(guess) = guess.take() {
+                        board.push(guess, *score, None);
+                    }
+                }
+                EngineEvent::GameOver { won: true } => {
+                    if let Some(guess) = guess.take() {
+                        board.push(guess, [LetterScore::RightPlace; WORD_LENGTH], None);
+                    }
+                }
+                _ => {}
+            }
+        }
+        board
+    }
+
+    /// Writes the log one event per line, e.g. `  1.234s scored: bybbg`.
+    pub fn write_log<W: Write + ?Sized>(&self, out: &mut W) -> io::Result<()> {
+        for (at, event) in self.events.borrow().iter() {
+            writeln!(out, "{:>8.3}s {}", at.as_secs_f64(), event)?;
+        }
+        out.flush()
+    }
+
+    fn record(&self, event: EngineEvent<WORD_LENGTH>) {
+        self.events
+            .borrow_mut()
+            .push((self.started.elapsed(), event));
+    }
+}
+
+impl<E, const WORD_LENGTH: usize> Engine<WORD_LENGTH> for LoggedEngine<E, WORD_LENGTH>
+where
+    E: Engine<WORD_LENGTH>,
+{
+    fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
+        self.try_score_guess(guess)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_score_guess(
+        &self,
+        guess: &Word<WORD_LENGTH>,
+    ) -> Result<GuessResult<WORD_LENGTH>, WordleError> {
+        self.record(EngineEvent::GuessReceived(*guess));
+        let result = match self.engine.try_score_guess(guess) {
+            Ok(result) => result,
+            Err(error) => {
+                self.record(EngineEvent::Failed(error.to_string()));
+                return Err(error);
+            }
+        };
+        self.record(match &result {
+            GuessResult::Continue(score) => EngineEvent::Scored(*score),
+            GuessResult::Rejected(error) => EngineEvent::Refused {
+                error: *error,
+                wasted: false,
+            },
+            GuessResult::Wasted(error) => EngineEvent::Refused {
+                error: *error,
+                wasted: true,
+            },
+            GuessResult::Boards(scores) => EngineEvent::ScoredBoards(scores.clone()),
+            GuessResult::Done { won, .. } => EngineEvent::GameOver { won: *won },
+        });
+        Ok(result)
+    }
+
+    fn free_letters(&self) -> Vec<FreeLetter> {
+        self.engine.free_letters()
+    }
+}
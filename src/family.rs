@@ -0,0 +1,107 @@
+//! Spotting answer families: groups of viable words alike in all but one
+//! position, like the "_ight" words. Guessing through one a word at a time
+//! can take a turn per member, so they're worth noticing while there's
+//! still room to probe for several of the letters at once.
+
+use crate::word::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// The smallest family worth warning about; with four or more members,
+/// guessing them in turn can run a game out of guesses.
+pub const DANGEROUS_FAMILY_SIZE: usize = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Family<const WORD_LENGTH: usize> {
+    /// The position the members differ in.
+    pub position: usize,
+    pub members: Vec<Word<WORD_LENGTH>>,
+}
+
+impl<const WORD_LENGTH: usize> Family<WORD_LENGTH> {
+    /// The letters filling the open position, one per member.
+    pub fn letters(&self) -> Vec<char> {
+        self.members
+            .iter()
+            .map(|word| word.0[self.position])
+            .collect()
+    }
+
+    /// What the members share, with `_` in the open position.
+    pub fn pattern(&self) -> String {
+        let mut pattern = self.members[0];
+        pattern.0[self.position] = '_';
+        pattern.to_string()
+    }
+}
+
+impl<const WORD_LENGTH: usize> fmt::Display for Family<WORD_LENGTH> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let members = self
+            .members
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        write!(f, "{} ({})", self.pattern(), members.join("/"))
+    }
+}
+
+/// The number of different letters each position takes across `words`.
+pub fn positional_variety<const WORD_LENGTH: usize>(
+    words: &WordList<WORD_LENGTH>,
+) -> [usize; WORD_LENGTH] {
+    std::array::from_fn(|position| {
+        words
+            .0
+            .iter()
+            .map(|word| word.0[position])
+            .collect::<HashSet<_>>()
+            .len()
+    })
+}
+
+/// Every family of at least `min_size` among `words`, largest first. Only
+/// positions taking at least `min_size` letters can hold one, so the rest
+/// aren't searched.
+pub fn find_families<const WORD_LENGTH: usize>(
+    words: &WordList<WORD_LENGTH>,
+    min_size: usize,
+) -> Vec<Family<WORD_LENGTH>> {
+    let variety = positional_variety(words);
+    let mut families = Vec::new();
+    for position in (0..WORD_LENGTH).filter(|position| variety[*position] >= min_size.max(2)) {
+        let mut groups = HashMap::<_, Vec<_>>::new();
+        for word in &words.0 {
+            let mut pattern = *word;
+            pattern.0[position] = '_';
+            groups.entry(pattern).or_default().push(*word);
+        }
+        families.extend(
+            groups
+                .into_values()
+                .filter(|members| members.len() >= min_size.max(2))
+                .map(|members| Family { position, members }),
+        );
+    }
+    // Sort fully, so the order doesn't depend on the hash maps.
+    families.sort_by(|a, b| {
+        b.members
+            .len()
+            .cmp(&a.members.len())
+            .then_with(|| a.members.cmp(&b.members))
+    });
+    families
+}
+
+/// The largest family among `viable_words` big enough to worry about: at
+/// least [`DANGEROUS_FAMILY_SIZE`] members, making up at least a quarter of
+/// the viable words. A smaller share is likely to be split up anyway by
+/// guesses aimed at the rest.
+pub fn dangerous_family<const WORD_LENGTH: usize>(
+    viable_words: &WordList<WORD_LENGTH>,
+) -> Option<Family<WORD_LENGTH>> {
+    find_families(viable_words, DANGEROUS_FAMILY_SIZE)
+        .into_iter()
+        .next()
+        .filter(|family| 4 * family.members.len() >= viable_words.0.len())
+}
@@ -0,0 +1,122 @@
+//! Word-frequency priors: how likely each word is to be the answer, going
+//! by how often it turns up in a corpus. Puzzle setters pick words people
+//! know, so weighting by frequency keeps obscure dictionary words from
+//! counting as much as common ones.
+//!
+//! Frequencies are read from CSV, a word and its count per row, with an
+//! optional header:
+//!
+//! ```text
+//! word,count
+//! about,1226734006
+//! crane,3285217
+//! ```
+//!
+//! Words of other lengths are skipped, so a general corpus list can be used
+//! as is.
+
+use crate::dataset::unquote;
+use crate::word::*;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrequencyError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for FrequencyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for FrequencyError {}
+
+/// A weight for every word, proportional to how likely it is to be the
+/// answer. Words the corpus doesn't have get the weight of its rarest word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frequencies<const WORD_LENGTH: usize> {
+    counts: HashMap<Word<WORD_LENGTH>, f64>,
+    floor: f64,
+}
+
+impl<const WORD_LENGTH: usize> Frequencies<WORD_LENGTH> {
+    /// Reads `word,count` rows. A count has to be a positive number; a word
+    /// listed twice has its counts added up.
+    pub fn parse_csv(text: &str) -> Result<Self, FrequencyError> {
+        let mut counts = HashMap::new();
+        let mut first_row = true;
+        for (index, row) in text.lines().enumerate() {
+            if row.trim().is_empty() || row.trim_start().starts_with('#') {
+                continue;
+            }
+            let error = |message: String| FrequencyError {
+                line: index + 1,
+                message,
+            };
+            let (word, count) = row
+                .split_once(',')
+                .map(|(word, count)| (unquote(word), unquote(count)))
+                .ok_or_else(|| error("expected a word and a count".to_owned()))?;
+            let header = std::mem::replace(&mut first_row, false);
+            let count = match count.parse::<f64>() {
+                Ok(count) if count.is_finite() && count > 0.0 => count,
+                Err(_) if header => continue,
+                _ => return Err(error(format!("bad count {:?}", count))),
+            };
+            if let Ok(word) = Word::try_from(word.to_lowercase().as_str()) {
+                *counts.entry(word).or_insert(0.0) += count;
+            }
+        }
+        if counts.is_empty() {
+            return Err(FrequencyError {
+                line: text.lines().count(),
+                message: format!("no {}-letter words", WORD_LENGTH),
+            });
+        }
+        let floor = counts.values().copied().fold(f64::INFINITY, f64::min);
+        Ok(Self { counts, floor })
+    }
+
+    /// How much `word` counts for, relative to other words.
+    pub fn weight(&self, word: &Word<WORD_LENGTH>) -> f64 {
+        self.counts.get(word).copied().unwrap_or(self.floor)
+    }
+
+    /// The chance `word` is the answer, if the answer is one of
+    /// `viable_words`.
+    pub fn probability(
+        &self,
+        word: &Word<WORD_LENGTH>,
+        viable_words: &WordList<WORD_LENGTH>,
+    ) -> f64 {
+        if !viable_words.0.contains(word) {
+            return 0.0;
+        }
+        self.weight(word) / self.total(viable_words)
+    }
+
+    /// Like [`WordList::entropy`], but with the secret drawn from
+    /// `viable_words` in proportion to each word's weight.
+    pub fn entropy(&self, viable_words: &WordList<WORD_LENGTH>, guess: &Word<WORD_LENGTH>) -> f64 {
+        let total = self.total(viable_words);
+        let mut masses = HashMap::new();
+        for word in &viable_words.0 {
+            *masses.entry(word.evaluate_guess(guess)).or_insert(0.0) += self.weight(word);
+        }
+        masses
+            .values()
+            .map(|mass| {
+                let p = mass / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    fn total(&self, words: &WordList<WORD_LENGTH>) -> f64 {
+        words.0.iter().map(|word| self.weight(word)).sum()
+    }
+}
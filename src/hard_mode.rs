@@ -0,0 +1,213 @@
+//! Hard mode for any strategy: guesses that don't fit the clues so far are
+//! swapped for ones that do, so a hard-mode engine never has to refuse one.
+//! Also finds the traps hard mode sets after a given opener.
+
+use crate::advice::Advisor;
+use crate::engine::{check_hard_mode, GuessError};
+use crate::family::{find_families, Family};
+use crate::ranking::Ranker;
+use crate::strategy::{Strategy, StrategyError};
+use crate::word::*;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Wraps a strategy so it only ever plays words that could still be the
+/// secret. When the inner strategy wants to probe with anything else, the
+/// most informative word that fits is played instead and the inner strategy
+/// is told through [`Strategy::guess_replaced`], so it has to support that.
+pub struct HardMode<S, const WORD_LENGTH: usize> {
+    inner: S,
+    viable_words: WordList<WORD_LENGTH>,
+    guessed: HashSet<Word<WORD_LENGTH>>,
+    last_guess: Option<Word<WORD_LENGTH>>,
+    replaced: usize,
+}
+
+impl<S, const WORD_LENGTH: usize> HardMode<S, WORD_LENGTH>
+where
+    S: Strategy<WORD_LENGTH>,
+{
+    pub fn new(inner: S, word_list: WordList<WORD_LENGTH>) -> Self {
+        Self {
+            inner,
+            viable_words: word_list,
+            guessed: HashSet::new(),
+            last_guess: None,
+            replaced: 0,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// How many of the inner strategy's guesses had to be swapped.
+    pub fn replaced(&self) -> usize {
+        self.replaced
+    }
+}
+
+impl<S, const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for HardMode<S, WORD_LENGTH>
+where
+    S: Strategy<WORD_LENGTH>,
+{
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        let mut guess = self.inner.make_guess()?;
+        if !self.viable_words.0.contains(&guess) {
+            let mut fitting = self.viable_words.clone();
+            fitting.0.retain(|word| !self.guessed.contains(word));
+            if let Some(fits) = Advisor::Entropy.rank(&fitting, &self.viable_words).first() {
+                guess = *fits;
+                self.replaced += 1;
+                self.inner.guess_replaced(&guess)?;
+            }
+        }
+        self.guessed.insert(guess);
+        self.last_guess = Some(guess);
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        let guess = self.last_guess.expect("should've made a guess by now");
+        self.viable_words.retain_viable_words(&guess, score);
+        self.inner.receive_score(score)
+    }
+
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        self.guessed.remove(guess);
+        self.inner.guess_rejected(guess, error)
+    }
+
+    fn guess_wasted(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        // Unlike a rejected guess, this one stays guessed: trying it again
+        // would only waste another turn.
+        self.inner.guess_wasted(guess, error)
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        if let Some(suggested) = self.last_guess.replace(*played) {
+            self.guessed.remove(&suggested);
+        }
+        self.guessed.insert(*played);
+        self.inner.guess_replaced(played)
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.viable_words.retain_fitting(clues);
+        for clue in clues {
+            if let Clue::Scored(guess, _) = clue {
+                self.guessed.insert(*guess);
+            }
+        }
+        self.inner.prime(clues)
+    }
+}
+
+/// A way into a family of answers that hard mode can't get out of in time.
+/// After the opener scores `score`, guessing `entry`, one of the family,
+/// turns every letter the family shares green. Hard mode then makes every
+/// guess keep those greens, so each guess rules out only one member, and
+/// `left` has more members than there are guesses to try them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trap<const WORD_LENGTH: usize> {
+    pub score: Score<WORD_LENGTH>,
+    pub family: Family<WORD_LENGTH>,
+    pub entry: Word<WORD_LENGTH>,
+    /// The members `entry` can't tell apart, one of which is the secret.
+    pub left: Vec<Word<WORD_LENGTH>>,
+}
+
+impl<const WORD_LENGTH: usize> fmt::Display for Trap<WORD_LENGTH> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {} leaves {}",
+            format_score(&self.score),
+            self.family,
+            self.entry,
+            self.left.len()
+        )
+    }
+}
+
+/// The traps hard mode sets after `opener`, largest family first: for each
+/// family among the answers left by one of the opener's scores, the first
+/// member that, guessed next, can leave more members than guesses when no
+/// guess from `guess_list` that hard mode allows splits them small enough
+/// to try one by one.
+///
+/// Only one splitting guess is looked for, so members that two guesses in a
+/// row could split still count as trapped.
+pub fn traps<const WORD_LENGTH: usize>(
+    opener: &Word<WORD_LENGTH>,
+    guess_list: &WordList<WORD_LENGTH>,
+    answer_list: &WordList<WORD_LENGTH>,
+    max_guesses: usize,
+) -> Vec<Trap<WORD_LENGTH>> {
+    // Left after the opener and the entry.
+    let guesses_left = max_guesses.saturating_sub(2);
+    let mut traps = Vec::new();
+    for (score, answers) in answer_list.partition(opener) {
+        for mut family in find_families(&answers, guesses_left + 2) {
+            family.members.sort();
+            let trap = family.members.iter().find_map(|entry| {
+                let mut groups = HashMap::<_, Vec<_>>::new();
+                for member in family.members.iter().filter(|member| *member != entry) {
+                    groups
+                        .entry(member.evaluate_guess(entry))
+                        .or_default()
+                        .push(*member);
+                }
+                groups.into_iter().find_map(|(entry_score, left)| {
+                    let history = [(*opener, score), (*entry, entry_score)];
+                    let escapes = |guess: &Word<WORD_LENGTH>| {
+                        check_hard_mode(guess, &history).is_ok()
+                            && splits(guess, &left, guesses_left.saturating_sub(1))
+                    };
+                    (left.len() > guesses_left && !guess_list.0.iter().any(escapes))
+                        .then_some((*entry, left))
+                })
+            });
+            if let Some((entry, mut left)) = trap {
+                left.sort();
+                traps.push(Trap {
+                    score,
+                    family,
+                    entry,
+                    left,
+                });
+            }
+        }
+    }
+    // Sort fully, so the order doesn't depend on the partition's hash map.
+    traps.sort_by(|a, b| {
+        b.family
+            .members
+            .len()
+            .cmp(&a.family.members.len())
+            .then_with(|| a.family.members.cmp(&b.family.members))
+    });
+    traps
+}
+
+/// Whether `guess` leaves no more than `turns` of `words` behind any one
+/// score, apart from the word it finds itself.
+fn splits<const WORD_LENGTH: usize>(
+    guess: &Word<WORD_LENGTH>,
+    words: &[Word<WORD_LENGTH>],
+    turns: usize,
+) -> bool {
+    let mut groups = HashMap::<_, usize>::new();
+    for word in words.iter().filter(|word| *word != guess) {
+        *groups.entry(word.evaluate_guess(guess)).or_default() += 1;
+    }
+    groups.values().all(|size| *size <= turns)
+}
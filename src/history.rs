@@ -0,0 +1,186 @@
+//! Which daily puzzles have already been played on this machine, so replaying
+//! one by accident doesn't spoil it. Stored as one puzzle per line with the
+//! number of guesses it took, or `X` for a loss, and the seconds it took for
+//! timed games:
+//!
+//! ```text
+//! 210 4
+//! 211 X
+//! 212 3 41.5
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// Formats a time as `m:ss`.
+pub fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlayedPuzzles {
+    /// Guesses taken for each puzzle, or `None` if it was lost.
+    results: BTreeMap<u32, Option<usize>>,
+    /// Total thinking time for puzzles played timed.
+    times: BTreeMap<u32, Duration>,
+}
+
+impl PlayedPuzzles {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the history at `path`; a missing file is an empty history.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let mut played = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let bad_line = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "line {}: expected a puzzle and a result, got {:?}",
+                        index + 1,
+                        line
+                    ),
+                )
+            };
+            let mut fields = line.split_whitespace();
+            let (puzzle, result) = match (fields.next(), fields.next()) {
+                (Some(puzzle), Some(result)) => (puzzle, result),
+                _ => return Err(bad_line()),
+            };
+            let puzzle = puzzle.parse().map_err(|_| bad_line())?;
+            let result = match result {
+                "X" => None,
+                guesses => Some(guesses.parse().map_err(|_| bad_line())?),
+            };
+            if let Some(seconds) = fields.next() {
+                let seconds = seconds
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                    .ok_or_else(bad_line)?;
+                played
+                    .times
+                    .insert(puzzle, Duration::from_secs_f64(seconds));
+            }
+            played.results.insert(puzzle, result);
+        }
+        Ok(played)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, self.to_string())
+    }
+
+    /// How a played puzzle went: `Some(Some(guesses))` for a win,
+    /// `Some(None)` for a loss, or `None` if it hasn't been played.
+    pub fn result(&self, puzzle: u32) -> Option<Option<usize>> {
+        self.results.get(&puzzle).copied()
+    }
+
+    /// Records a game, keeping the first result if the puzzle was replayed.
+    pub fn record(&mut self, puzzle: u32, guesses: Option<usize>) {
+        self.results.entry(puzzle).or_insert(guesses);
+    }
+
+    /// Like [`PlayedPuzzles::record`], for a game played against the clock.
+    pub fn record_timed(&mut self, puzzle: u32, guesses: Option<usize>, time: Duration) {
+        if !self.results.contains_key(&puzzle) {
+            self.times.insert(puzzle, time);
+        }
+        self.record(puzzle, guesses);
+    }
+
+    /// How long a timed puzzle took.
+    pub fn time(&self, puzzle: u32) -> Option<Duration> {
+        self.times.get(&puzzle).copied()
+    }
+
+    /// Win count for each number of guesses, plus the number of losses.
+    pub fn distribution(&self) -> (BTreeMap<usize, usize>, usize) {
+        let mut wins = BTreeMap::new();
+        let mut losses = 0;
+        for result in self.results.values() {
+            match result {
+                Some(guesses) => *wins.entry(*guesses).or_insert(0) += 1,
+                None => losses += 1,
+            }
+        }
+        (wins, losses)
+    }
+
+    /// A short summary of every game played, for showing instead of a puzzle
+    /// that was already played.
+    pub fn stats(&self) -> String {
+        let (wins, losses) = self.distribution();
+        let won = wins.values().sum::<usize>();
+        let mut stats = format!(
+            "Played {}, won {} ({:.0}%)\n",
+            self.results.len(),
+            won,
+            100.0 * won as f64 / self.results.len().max(1) as f64
+        );
+        for (guesses, count) in wins {
+            stats.push_str(&format!("  {}: {}\n", guesses, count));
+        }
+        if losses > 0 {
+            stats.push_str(&format!("  X: {}\n", losses));
+        }
+
+        // Speed only counts wins; a loss ends on the guess limit, not on
+        // finding the word.
+        let timed_wins = self
+            .times
+            .iter()
+            .filter_map(|(puzzle, time)| Some((self.results.get(puzzle).copied()??, *time)))
+            .collect::<Vec<_>>();
+        if let Some(fastest) = timed_wins.iter().map(|(_, time)| *time).min() {
+            let total = timed_wins.iter().map(|(_, time)| *time).sum::<Duration>();
+            let guesses = timed_wins.iter().map(|(guesses, _)| guesses).sum::<usize>();
+            stats.push_str(&format!(
+                "Timed wins: {}, average {}, fastest {}, {:.1}s per guess\n",
+                timed_wins.len(),
+                format_time(total / timed_wins.len() as u32),
+                format_time(fastest),
+                total.as_secs_f64() / guesses.max(1) as f64
+            ));
+        }
+        stats
+    }
+}
+
+impl fmt::Display for PlayedPuzzles {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (puzzle, result) in &self.results {
+            match result {
+                Some(guesses) => write!(f, "{} {}", puzzle, guesses)?,
+                None => write!(f, "{} X", puzzle)?,
+            }
+            match self.times.get(puzzle) {
+                Some(time) => writeln!(f, " {:.1}", time.as_secs_f64())?,
+                None => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+//! Game records from the CSV exports of other Wordle trackers, so a history
+//! kept elsewhere can go through the same analysis as games played here.
+//! Two layouts are understood, told apart by their header row.
+//!
+//! One row per game, with the guesses in a single column, separated by
+//! spaces, `|` or `;`. Scores are worked out from the answer:
+//!
+//! ```text
+//! Date,Wordle #,Answer,Guesses
+//! 2022-01-15,210,spilt,crane|spilt
+//! ```
+//!
+//! One row per guess, with the score as tile shorthand or emoji. Consecutive
+//! rows with the same date and puzzle make up a game:
+//!
+//! ```text
+//! date,puzzle,guess,score
+//! 2022-01-15,210,crane,bbbbb
+//! 2022-01-15,210,spilt,ggggg
+//! ```
+//!
+//! Column names are matched without regard to case, and a few common
+//! spellings of each are accepted; other columns are ignored.
+
+use crate::board::Board;
+use crate::dataset::unquote;
+use crate::record::GameRecord;
+use crate::tiles::TileStyle;
+use crate::word::*;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrackerFormat {
+    /// One row per game, scored from its answer.
+    Games,
+    /// One row per guess, with its score.
+    Guesses,
+}
+
+/// Where each column the importer cares about is in a row.
+struct Columns {
+    date: Option<usize>,
+    puzzle: Option<usize>,
+    answer: Option<usize>,
+    guesses: Option<usize>,
+    guess: Option<usize>,
+    score: Option<usize>,
+}
+
+impl Columns {
+    fn find(header: &[&str]) -> Self {
+        let find = |names: &[&str]| {
+            header
+                .iter()
+                .position(|column| names.iter().any(|name| column.eq_ignore_ascii_case(name)))
+        };
+        Self {
+            date: find(&["date", "day", "played"]),
+            puzzle: find(&["puzzle", "wordle", "wordle #", "game", "number"]),
+            answer: find(&["answer", "solution", "word", "secret"]),
+            guesses: find(&["guesses", "attempts"]),
+            guess: find(&["guess"]),
+            score: find(&["score", "pattern", "result", "tiles"]),
+        }
+    }
+
+    fn format(&self) -> Option<TrackerFormat> {
+        match self {
+            Self {
+                answer: Some(_),
+                guesses: Some(_),
+                ..
+            } => Some(TrackerFormat::Games),
+            Self {
+                guess: Some(_),
+                score: Some(_),
+                ..
+            } => Some(TrackerFormat::Guesses),
+            _ => None,
+        }
+    }
+}
+
+/// Which layout a CSV export with this header row is in, if either.
+pub fn detect_format(header: &str) -> Option<TrackerFormat> {
+    Columns::find(&split_row(header)).format()
+}
+
+fn split_row(row: &str) -> Vec<&str> {
+    row.split(',').map(unquote).collect()
+}
+
+/// Reads every game in a tracker's CSV export, in the order they appear.
+/// Games don't say how many guesses they allowed, so six is assumed.
+pub fn import_csv<const WORD_LENGTH: usize>(
+    text: &str,
+) -> Result<Vec<GameRecord<WORD_LENGTH>>, ImportError> {
+    let mut rows = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let (_, header) = rows.next().ok_or_else(|| ImportError {
+        line: 1,
+        message: "the export is empty".to_owned(),
+    })?;
+    let columns = Columns::find(&split_row(header));
+    let format = columns.format().ok_or_else(|| ImportError {
+        line: 1,
+        message: "expected answer and guesses columns, or guess and score columns".to_owned(),
+    })?;
+
+    let mut records: Vec<GameRecord<WORD_LENGTH>> = Vec::new();
+    for (index, row) in rows {
+        let error = |message: String| ImportError {
+            line: index + 1,
+            message,
+        };
+        let fields = split_row(row);
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|column| fields.get(column).copied())
+                .filter(|field| !field.is_empty())
+        };
+        let word = |text: &str| {
+            Word::try_from(text.to_ascii_lowercase().as_str())
+                .map_err(|_| error(format!("bad word {:?}", text)))
+        };
+        let date = field(columns.date).map(str::to_owned);
+        let puzzle = field(columns.puzzle)
+            .map(|puzzle| {
+                puzzle
+                    .trim_start_matches('#')
+                    .parse()
+                    .map_err(|_| error(format!("bad puzzle number {:?}", puzzle)))
+            })
+            .transpose()?;
+
+        match format {
+            TrackerFormat::Games => {
+                let answer = word(
+                    field(columns.answer).ok_or_else(|| error("missing the answer".to_owned()))?,
+                )?;
+                let mut board = Board::new();
+                for guess in field(columns.guesses)
+                    .unwrap_or_default()
+                    .split(|c: char| c.is_whitespace() || c == '|' || c == ';')
+                    .filter(|guess| !guess.is_empty())
+                {
+                    let guess = word(guess)?;
+                    board.push(guess, answer.evaluate_guess(&guess), None);
+                }
+                records.push(new_record(date, puzzle, board));
+            }
+            TrackerFormat::Guesses => {
+                let guess = word(
+                    field(columns.guess).ok_or_else(|| error("missing the guess".to_owned()))?,
+                )?;
+                let score_text =
+                    field(columns.score).ok_or_else(|| error("missing the score".to_owned()))?;
+                let score = parse_score(score_text)
+                    .or_else(|| TileStyle::parse_tiles(score_text)?.try_into().ok())
+                    .ok_or_else(|| error(format!("bad score {:?}", score_text)))?;
+                let same_game = records.last().is_some_and(|record| {
+                    record.date == date && record.puzzle == puzzle && !record.won()
+                });
+                if !same_game {
+                    records.push(new_record(date, puzzle, Board::new()));
+                }
+                let record = records.last_mut().expect("just made sure there's one");
+                record.board.push(guess, score, None);
+            }
+        }
+    }
+    Ok(records)
+}
+
+fn new_record<const WORD_LENGTH: usize>(
+    date: Option<String>,
+    puzzle: Option<u32>,
+    board: Board<WORD_LENGTH>,
+) -> GameRecord<WORD_LENGTH> {
+    GameRecord {
+        date,
+        puzzle,
+        max_guesses: 6,
+        board,
+        think_times: Vec::new(),
+    }
+}
@@ -0,0 +1,148 @@
+//! Working back from a finished game to its secret: given the guesses and
+//! the grid they scored, which words could the secret have been? Recovers a
+//! forgotten answer, and catches a transcript no secret could have produced.
+//! Turned around, it also says how lucky a solve in two was.
+
+use crate::word::*;
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InverseError {
+    /// There has to be a guess for every row, or for every row but a
+    /// winning last one.
+    RowCount { guesses: usize, rows: usize },
+    /// A row, counting from 1, with the wrong number of tiles.
+    RowLength { row: usize, tiles: usize },
+}
+
+impl fmt::Display for InverseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::RowCount { guesses, rows } => write!(
+                f,
+                "{} guesses for {} rows; give one per row, leaving out at most the winning one",
+                guesses, rows
+            ),
+            Self::RowLength { row, tiles } => write!(f, "row {} has {} tiles", row, tiles),
+        }
+    }
+}
+
+impl std::error::Error for InverseError {}
+
+/// Pairs each guess with its row of `grid`. A winning last row whose guess
+/// was left out is dropped, since all it says is that the secret is a word
+/// fitting the rows before it.
+pub fn pair_rows<const WORD_LENGTH: usize>(
+    guesses: &[Word<WORD_LENGTH>],
+    grid: &[Vec<LetterScore>],
+) -> Result<Vec<(Word<WORD_LENGTH>, Score<WORD_LENGTH>)>, InverseError> {
+    let solved =
+        |tiles: &Vec<LetterScore>| tiles.iter().all(|tile| *tile == LetterScore::RightPlace);
+    let rows = match grid.split_last() {
+        Some((last, rest)) if guesses.len() == rest.len() && solved(last) => rest,
+        _ if guesses.len() == grid.len() => grid,
+        _ => {
+            return Err(InverseError::RowCount {
+                guesses: guesses.len(),
+                rows: grid.len(),
+            })
+        }
+    };
+    guesses
+        .iter()
+        .zip(rows)
+        .enumerate()
+        .map(|(index, (guess, tiles))| {
+            let score = Score::try_from(tiles.as_slice()).map_err(|_| InverseError::RowLength {
+                row: index + 1,
+                tiles: tiles.len(),
+            })?;
+            Ok((*guess, score))
+        })
+        .collect()
+}
+
+/// The words of `word_list` that `guesses` would have scored as `grid`.
+/// With every guess given, a won game's only secret is its last guess, so
+/// this checks the transcript; leaving out the winning guess finds the
+/// secrets it could have been.
+pub fn consistent_secrets<const WORD_LENGTH: usize>(
+    guesses: &[Word<WORD_LENGTH>],
+    grid: &[Vec<LetterScore>],
+    word_list: &WordList<WORD_LENGTH>,
+) -> Result<WordList<WORD_LENGTH>, InverseError> {
+    let history = pair_rows(guesses, grid)?;
+    Ok(word_list
+        .0
+        .iter()
+        .filter(|word| is_consistent(word, &history))
+        .copied()
+        .collect())
+}
+
+/// An opener that left `answer`, the secret of a game solved in two, among
+/// `remaining` answers: a second guess picked from them at random finds it
+/// with a chance of one in `remaining`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Witness<const WORD_LENGTH: usize> {
+    pub opener: Word<WORD_LENGTH>,
+    pub score: Score<WORD_LENGTH>,
+    pub remaining: usize,
+}
+
+impl<const WORD_LENGTH: usize> Witness<WORD_LENGTH> {
+    /// How likely a second guess among the remaining answers was to be right.
+    pub fn chance(&self) -> f64 {
+        1.0 / self.remaining as f64
+    }
+}
+
+impl<const WORD_LENGTH: usize> fmt::Display for Witness<WORD_LENGTH> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: 1 in {} ({:.1}%)",
+            self.opener,
+            format_score(&self.score),
+            self.remaining,
+            100.0 * self.chance()
+        )
+    }
+}
+
+/// Every opener in `guess_list` that could have led to solving for `answer`
+/// in two, which is any but `answer` itself, with the answers its score
+/// leaves in `answer_list`: the surest solves first, where the opener left
+/// the fewest to choose from.
+pub fn two_guess_witnesses<const WORD_LENGTH: usize>(
+    answer: &Word<WORD_LENGTH>,
+    guess_list: &WordList<WORD_LENGTH>,
+    answer_list: &WordList<WORD_LENGTH>,
+) -> Vec<Witness<WORD_LENGTH>> {
+    let mut witnesses = guess_list
+        .0
+        .iter()
+        .filter(|opener| *opener != answer)
+        .map(|opener| {
+            let score = answer.evaluate_guess(opener);
+            let grid = [score.to_vec()];
+            // The answer is one of them even if the list leaves it out.
+            let remaining = consistent_secrets(&[*opener], &grid, answer_list)
+                .map(|secrets| secrets.0.iter().filter(|word| *word != answer).count() + 1)
+                .expect("one guess for one row");
+            Witness {
+                opener: *opener,
+                score,
+                remaining,
+            }
+        })
+        .collect::<Vec<_>>();
+    witnesses.sort_by(|a, b| {
+        a.remaining
+            .cmp(&b.remaining)
+            .then_with(|| a.opener.cmp(&b.opener))
+    });
+    witnesses
+}
@@ -0,0 +1,288 @@
+//! Lightweight leagues: collect several players' share grids for each puzzle
+//! and keep running standings. A league is stored one result per line, as
+//! puzzle, guesses (or `X`), seconds taken (or `-`), then the player:
+//!
+//! ```text
+//! 210 3 95 alice
+//! 210 X - bob
+//! ```
+
+use crate::tiles::TileStyle;
+use crate::word::LetterScore;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeagueError {
+    pub message: String,
+}
+
+impl LeagueError {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for LeagueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for LeagueError {}
+
+/// What a pasted share grid says about a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SharedResult {
+    /// Puzzle number, if the grid's header has one.
+    pub puzzle: Option<u32>,
+    /// Guesses taken, or `None` for a loss.
+    pub guesses: Option<usize>,
+    pub max_guesses: usize,
+}
+
+/// Reads a share grid like the NYT's `Wordle 210 3/6*` or this crate's
+/// `Wordle 3/6 (2 hints)`, followed by its rows of tiles in any [`TileStyle`]. The number of rows
+/// has to match the result in the header.
+pub fn parse_share_grid(text: &str) -> Result<SharedResult, LeagueError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    let header = lines
+        .next()
+        .filter(|line| line.starts_with("Wordle"))
+        .ok_or_else(|| LeagueError::new("share grid should start with a \"Wordle\" line"))?;
+
+    let mut puzzle = None;
+    let mut result = None;
+    for token in header.split_whitespace().skip(1) {
+        if let Some((guesses, max_guesses)) = token.trim_end_matches('*').split_once('/') {
+            let max_guesses = max_guesses
+                .parse::<usize>()
+                .map_err(|_| LeagueError::new(format!("bad result {:?}", token)))?;
+            let guesses = match guesses {
+                "X" => None,
+                guesses => Some(
+                    guesses
+                        .parse()
+                        .map_err(|_| LeagueError::new(format!("bad result {:?}", token)))?,
+                ),
+            };
+            result = Some((guesses, max_guesses));
+            break;
+        }
+        // Puzzle numbers past 999 are shared with a thousands separator.
+        puzzle = token.replace(',', "").parse().ok();
+    }
+    let (guesses, max_guesses) =
+        result.ok_or_else(|| LeagueError::new(format!("no result in {:?}", header)))?;
+
+    let rows = lines.filter_map(TileStyle::parse_tiles).collect::<Vec<_>>();
+    let expected_rows = guesses.unwrap_or(max_guesses);
+    if rows.len() != expected_rows {
+        return Err(LeagueError::new(format!(
+            "header says {} rows but the grid has {}",
+            expected_rows,
+            rows.len()
+        )));
+    }
+    let solved = rows
+        .last()
+        .is_some_and(|row| row.iter().all(|tile| *tile == LetterScore::RightPlace));
+    if solved != guesses.is_some() {
+        return Err(LeagueError::new(
+            "the last row doesn't match the result in the header",
+        ));
+    }
+
+    Ok(SharedResult {
+        puzzle,
+        guesses,
+        max_guesses,
+    })
+}
+
+/// Reads a time as `m:ss` or as plain seconds.
+pub fn parse_time(text: &str) -> Option<u32> {
+    match text.split_once(':') {
+        Some((minutes, seconds)) => {
+            let seconds = seconds
+                .parse::<u32>()
+                .ok()
+                .filter(|seconds| *seconds < 60)?;
+            Some(minutes.parse::<u32>().ok()? * 60 + seconds)
+        }
+        None => text.parse().ok(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub guesses: Option<usize>,
+    pub seconds: Option<u32>,
+}
+
+impl Entry {
+    /// Points for the standings: one for a win in six, up to six for a hole
+    /// in one, and nothing for a loss.
+    pub fn points(&self) -> usize {
+        self.guesses
+            .map_or(0, |guesses| 7usize.saturating_sub(guesses))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub player: String,
+    pub played: usize,
+    pub wins: usize,
+    pub points: usize,
+    /// Total time over the games with a time, if any had one.
+    pub seconds: Option<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct League {
+    results: BTreeMap<(u32, String), Entry>,
+}
+
+impl League {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the league at `path`; a missing file is a new league.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::new()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, LeagueError> {
+        let mut league = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let bad_line =
+                || LeagueError::new(format!("line {}: can't read {:?}", index + 1, line));
+            let mut fields = line.splitn(4, ' ');
+            let mut field = || fields.next().ok_or_else(bad_line);
+            let (puzzle, guesses, seconds, player) = (field()?, field()?, field()?, field()?);
+            let entry = Entry {
+                guesses: match guesses {
+                    "X" => None,
+                    guesses => Some(guesses.parse().map_err(|_| bad_line())?),
+                },
+                seconds: match seconds {
+                    "-" => None,
+                    seconds => Some(seconds.parse().map_err(|_| bad_line())?),
+                },
+            };
+            let puzzle = puzzle.parse().map_err(|_| bad_line())?;
+            league.results.insert((puzzle, player.to_owned()), entry);
+        }
+        Ok(league)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+    /// Records `player`'s result for `puzzle`, replacing any earlier one.
+    pub fn record(&mut self, puzzle: u32, player: &str, entry: Entry) {
+        self.results.insert((puzzle, player.to_owned()), entry);
+    }
+
+    /// Standings, best first: most points, then least total time, then by
+    /// name.
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings = BTreeMap::<&str, Standing>::new();
+        for ((_, player), entry) in &self.results {
+            let standing = standings.entry(player).or_insert_with(|| Standing {
+                player: player.clone(),
+                played: 0,
+                wins: 0,
+                points: 0,
+                seconds: None,
+            });
+            standing.played += 1;
+            standing.wins += entry.guesses.is_some() as usize;
+            standing.points += entry.points();
+            if let Some(seconds) = entry.seconds {
+                *standing.seconds.get_or_insert(0) += seconds;
+            }
+        }
+        let mut standings = standings.into_values().collect::<Vec<_>>();
+        standings.sort_by(|a, b| {
+            b.points
+                .cmp(&a.points)
+                .then(
+                    a.seconds
+                        .unwrap_or(u32::MAX)
+                        .cmp(&b.seconds.unwrap_or(u32::MAX)),
+                )
+                .then(a.player.cmp(&b.player))
+        });
+        standings
+    }
+
+    /// The standings as a text table.
+    pub fn standings_table(&self) -> String {
+        let standings = self.standings();
+        let width = standings
+            .iter()
+            .map(|standing| standing.player.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("player".len());
+        let mut table = format!(
+            "{:>4}  {:<width$}  {:>6}  {:>4}  {:>6}  {:>7}\n",
+            "rank",
+            "player",
+            "played",
+            "wins",
+            "points",
+            "time",
+            width = width
+        );
+        for (rank, standing) in standings.iter().enumerate() {
+            let time = standing.seconds.map_or("-".to_owned(), |seconds| {
+                format!("{}:{:02}", seconds / 60, seconds % 60)
+            });
+            table.push_str(&format!(
+                "{:>4}  {:<width$}  {:>6}  {:>4}  {:>6}  {:>7}\n",
+                rank + 1,
+                standing.player,
+                standing.played,
+                standing.wins,
+                standing.points,
+                time,
+                width = width
+            ));
+        }
+        table
+    }
+}
+
+impl fmt::Display for League {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for ((puzzle, player), entry) in &self.results {
+            match entry.guesses {
+                Some(guesses) => write!(f, "{} {} ", puzzle, guesses)?,
+                None => write!(f, "{} X ", puzzle)?,
+            }
+            match entry.seconds {
+                Some(seconds) => writeln!(f, "{} {}", seconds, player)?,
+                None => writeln!(f, "- {}", player)?,
+            }
+        }
+        Ok(())
+    }
+}
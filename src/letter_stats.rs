@@ -0,0 +1,100 @@
+//! How often each letter, and each pair of neighbouring letters, turns up in
+//! a word list, counted from the list itself rather than assumed from
+//! English. Heuristics that favour common letters, the difficulty estimator
+//! and the `heatmap` command all read their counts from here. Counting a big
+//! list isn't free, so count it once and share the tables.
+
+use crate::word::*;
+use std::collections::{HashMap, HashSet};
+
+/// Letter and bigram counts for one word list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetterStats<const WORD_LENGTH: usize> {
+    words: usize,
+    occurrences: HashMap<char, usize>,
+    containing: HashMap<char, usize>,
+    positions: [HashMap<char, usize>; WORD_LENGTH],
+    bigrams: HashMap<(char, char), usize>,
+}
+
+impl<const WORD_LENGTH: usize> LetterStats<WORD_LENGTH> {
+    pub fn new(word_list: &WordList<WORD_LENGTH>) -> Self {
+        let mut stats = Self {
+            words: word_list.0.len(),
+            occurrences: HashMap::new(),
+            containing: HashMap::new(),
+            positions: std::array::from_fn(|_| HashMap::new()),
+            bigrams: HashMap::new(),
+        };
+        for word in &word_list.0 {
+            for (position, letter) in word.0.iter().enumerate() {
+                *stats.occurrences.entry(*letter).or_insert(0) += 1;
+                *stats.positions[position].entry(*letter).or_insert(0) += 1;
+            }
+            for letter in HashSet::<char>::from(word) {
+                *stats.containing.entry(letter).or_insert(0) += 1;
+            }
+            for pair in word.0.windows(2) {
+                *stats.bigrams.entry((pair[0], pair[1])).or_insert(0) += 1;
+            }
+        }
+        stats
+    }
+
+    /// How many words were counted.
+    pub fn words(&self) -> usize {
+        self.words
+    }
+
+    /// Every appearance of `letter`, twice for a word that has it twice.
+    pub fn occurrences(&self, letter: char) -> usize {
+        self.occurrences.get(&letter).copied().unwrap_or(0)
+    }
+
+    /// How many words have `letter` at least once.
+    pub fn containing(&self, letter: char) -> usize {
+        self.containing.get(&letter).copied().unwrap_or(0)
+    }
+
+    /// How many words have `letter` at `position`, counting from 0.
+    ///
+    /// Panics if `position` is past the end of the words.
+    pub fn at(&self, position: usize, letter: char) -> usize {
+        self.positions[position].get(&letter).copied().unwrap_or(0)
+    }
+
+    /// How many times `first` is directly followed by `second`.
+    pub fn bigram(&self, first: char, second: char) -> usize {
+        self.bigrams.get(&(first, second)).copied().unwrap_or(0)
+    }
+
+    /// Every letter in the list, most common first.
+    pub fn letters(&self) -> Vec<char> {
+        let mut letters = self.occurrences.keys().copied().collect::<Vec<_>>();
+        letters.sort_by_key(|letter| (std::cmp::Reverse(self.occurrences(*letter)), *letter));
+        letters
+    }
+
+    /// The `n` least common letters, rarest first.
+    pub fn rarest(&self, n: usize) -> Vec<char> {
+        let mut letters = self.occurrences.iter().collect::<Vec<_>>();
+        letters.sort_by_key(|(letter, count)| (**count, **letter));
+        letters
+            .into_iter()
+            .take(n)
+            .map(|(letter, _)| *letter)
+            .collect()
+    }
+
+    /// The `n` most common bigrams with their counts, most common first.
+    pub fn top_bigrams(&self, n: usize) -> Vec<((char, char), usize)> {
+        let mut bigrams = self
+            .bigrams
+            .iter()
+            .map(|(pair, count)| (*pair, *count))
+            .collect::<Vec<_>>();
+        bigrams.sort_by_key(|(pair, count)| (std::cmp::Reverse(*count), *pair));
+        bigrams.truncate(n);
+        bigrams
+    }
+}
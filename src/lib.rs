@@ -1,37 +1,221 @@
+//! Wordle engines to play against, and strategies to play them with.
+//!
+//! Nothing in the library panics on untrusted input. Words, scores, share
+//! grids, game records, tracker exports and saved files that don't parse
+//! come back as errors, and engines accept any guess, refusing the ones the
+//! rules don't allow. What can still panic is the calling code breaking a
+//! documented contract, such as telling a strategy a score before it has
+//! guessed, and the infallible entry points that have a `try_` twin:
+//! [`run_game`], [`run_game_recorded`] and [`Engine::score_guess`] panic
+//! with the error their twin would return. Strategies never panic for
+//! having nothing to guess: they return a [`StrategyError`] and the game is
+//! lost.
+
+pub mod adaptive;
+pub mod advice;
+pub mod analysis;
+pub mod artifact;
+pub mod asynchronous;
+pub mod bench;
+pub mod board;
+pub mod builder;
+#[cfg(feature = "fetch")]
+pub mod daily;
+pub mod dataset;
+pub mod decision_tree;
+pub mod dictionary;
+pub mod dispatch;
+pub mod display;
+pub mod doctor;
+pub mod dynamic;
 pub mod engine;
+pub mod ensemble;
+pub mod error;
+pub mod eval;
+pub mod event_log;
+pub mod family;
+pub mod frequency;
+pub mod hard_mode;
+pub mod history;
+pub mod import;
+pub mod inverse;
+pub mod league;
+pub mod letter_stats;
+#[cfg(feature = "serde")]
+pub mod manifest;
+pub mod metrics;
+pub mod multi_board;
+pub mod noise;
+pub mod nyt;
+pub mod observer;
+pub mod optimal;
+pub mod oracle;
+pub mod play;
+pub mod policy;
+pub mod practice;
+pub mod profile;
+#[cfg(feature = "serde")]
+pub mod protocol;
+pub mod ranking;
+pub mod record;
+pub mod replies;
+pub mod schedule;
+#[cfg(feature = "serde")]
+pub mod schema;
+pub mod score_matrix;
+pub mod session;
+pub mod stats;
 pub mod strategy;
+pub mod tiles;
+pub mod trace;
 pub mod word;
 pub mod word_list;
 
-use engine::{Engine, GuessResult};
-use strategy::Strategy;
+pub use word::{is_consistent, score, score_tiles, ScoreError};
+
+use engine::{Engine, GameTranscript, GuessResult, TranscriptTurn};
+use error::WordleError;
+use observer::Observer;
+use strategy::{Strategy, StrategyError};
+use word::Word;
 
-fn run_round<E, S, const WORD_LENGTH: usize>(
+fn run_round<E, S, O, const WORD_LENGTH: usize>(
     engine: &E,
     strategy: &mut S,
-) -> GuessResult<WORD_LENGTH>
+    observer: &mut O,
+) -> Result<TranscriptTurn<WORD_LENGTH>, WordleError>
 where
     E: Engine<WORD_LENGTH>,
     S: Strategy<WORD_LENGTH>,
+    O: Observer<WORD_LENGTH>,
+{
+    let guess = strategy.make_guess()?;
+    observer.on_guess(&guess, strategy.last_metrics());
+    let result = engine.try_score_guess(&guess)?;
+    tracing::debug!(%guess, ?result, "guess scored");
+    observer.on_score(&guess, &result);
+    Ok(TranscriptTurn { guess, result })
+}
+
+/// Tells `strategy` what the engine made of `guess`, and whether it can
+/// carry on from there.
+pub(crate) fn report<S, const WORD_LENGTH: usize>(
+    strategy: &mut S,
+    guess: &Word<WORD_LENGTH>,
+    result: &GuessResult<WORD_LENGTH>,
+) -> Result<(), StrategyError>
+where
+    S: Strategy<WORD_LENGTH> + ?Sized,
 {
-    let guess = strategy.make_guess();
-    let score = engine.score_guess(&guess);
-    if let GuessResult::Continue(score) = score {
-        strategy.receive_score(&score);
+    match result {
+        GuessResult::Continue(score) => strategy.receive_score(score),
+        GuessResult::Rejected(error) => return strategy.guess_rejected(guess, error),
+        GuessResult::Wasted(error) => return strategy.guess_wasted(guess, error),
+        GuessResult::Boards(scores) => return strategy.receive_scores(scores),
+        GuessResult::Done { .. } => {}
     }
-    score
+    Ok(())
 }
 
-pub fn run_game<E, S, const WORD_LENGTH: usize>(engine: E, mut strategy: S) -> bool
+/// Plays `strategy` against `engine` to the end, and says whether it won. A
+/// strategy that gives up loses.
+///
+/// Panics if the engine or the strategy fails otherwise, such as the
+/// player's input running out; [`try_run_game`] returns the error instead.
+pub fn run_game<E, S, const WORD_LENGTH: usize>(engine: E, strategy: S) -> bool
 where
     E: Engine<WORD_LENGTH>,
     S: Strategy<WORD_LENGTH>,
 {
-    loop {
-        if let GuessResult::Done(did_win) = run_round(&engine, &mut strategy) {
-            break did_win;
-        }
+    run_game_recorded(engine, strategy).won
+}
+
+/// Like [`run_game`], but keeps every guess and what became of it. Panics
+/// where [`run_game`] does.
+pub fn run_game_recorded<E, S, const WORD_LENGTH: usize>(
+    engine: E,
+    strategy: S,
+) -> GameTranscript<WORD_LENGTH>
+where
+    E: Engine<WORD_LENGTH>,
+    S: Strategy<WORD_LENGTH>,
+{
+    try_run_game(engine, strategy).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Like [`run_game_recorded`], but stops at the first error from the engine
+/// or the strategy, such as the player's input running out, instead of
+/// panicking. A strategy giving up isn't an error: the game ends as lost,
+/// with [`GameTranscript::gave_up`] saying why.
+pub fn try_run_game<E, S, const WORD_LENGTH: usize>(
+    engine: E,
+    strategy: S,
+) -> Result<GameTranscript<WORD_LENGTH>, WordleError>
+where
+    E: Engine<WORD_LENGTH>,
+    S: Strategy<WORD_LENGTH>,
+{
+    run_game_with_observer(engine, strategy, ())
+}
+
+/// Like [`try_run_game`], but tells `observer` about each guess, each score
+/// and the end of the game as they happen.
+pub fn run_game_with_observer<E, S, O, const WORD_LENGTH: usize>(
+    engine: E,
+    mut strategy: S,
+    mut observer: O,
+) -> Result<GameTranscript<WORD_LENGTH>, WordleError>
+where
+    E: Engine<WORD_LENGTH>,
+    S: Strategy<WORD_LENGTH>,
+    O: Observer<WORD_LENGTH>,
+{
+    let _game = tracing::debug_span!("game").entered();
+    let clues = engine
+        .free_letters()
+        .into_iter()
+        .map(word::Clue::Letter)
+        .collect::<Vec<_>>();
+    if !clues.is_empty() {
+        strategy.prime(&clues);
     }
+    let mut turns = Vec::new();
+    let error = loop {
+        let turn = match run_round(&engine, &mut strategy, &mut observer) {
+            Ok(turn) => turn,
+            Err(error) => break error,
+        };
+        let reported = report(&mut strategy, &turn.guess, &turn.result);
+        let done = match turn.result {
+            GuessResult::Done { won, .. } => Some(won),
+            _ => None,
+        };
+        turns.push(turn);
+        if let Some(won) = done {
+            let transcript = GameTranscript {
+                turns,
+                won,
+                gave_up: None,
+            };
+            observer.on_end(&transcript);
+            return Ok(transcript);
+        }
+        if let Err(error) = reported {
+            break error.into();
+        }
+    };
+    let error = match error {
+        WordleError::Strategy(error) => error,
+        error => return Err(error),
+    };
+    tracing::debug!(%error, "strategy gave up");
+    let transcript = GameTranscript {
+        turns,
+        won: false,
+        gave_up: Some(error),
+    };
+    observer.on_end(&transcript);
+    Ok(transcript)
 }
 
 #[cfg(test)]
@@ -46,6 +230,25 @@ mod tests {
             .evaluate_guess(&Word::try_from(guess).unwrap())
     }
 
+    /// A trace sink the test can read back after handing it to a strategy.
+    #[derive(Clone, Default)]
+    struct SharedSink(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl SharedSink {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    impl std::io::Write for SharedSink {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
     #[test]
     fn test_evaluate() {
         assert_eq!(evaluate("xxx", "aaa"), [Wrong, Wrong, Wrong]);
@@ -67,6 +270,628 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_score_api() {
+        assert_eq!(score("crane", "caper"), Ok("gybyy".to_owned()));
+        assert_eq!(score("CRANE", "crane"), Ok("ggggg".to_owned()));
+        assert_eq!(score("abbey", "babes"), Ok("yyggb".to_owned()));
+        assert_eq!(score_tiles("ab", "ba"), Ok(vec![RightLetter, RightLetter]));
+        assert_eq!(
+            score("crane", "cranes"),
+            Err(ScoreError::LengthMismatch {
+                secret: 5,
+                guess: 6
+            })
+        );
+        assert_eq!(score("", ""), Err(ScoreError::Empty));
+        assert_eq!(score("cr4ne", "crane"), Err(ScoreError::NotALetter('4')));
+        // Same answer as the engines give.
+        assert_eq!(
+            score("abbey", "babes").unwrap(),
+            format_score(&evaluate::<5>("babes", "abbey"))
+        );
+    }
+
+    #[test]
+    fn test_is_consistent() {
+        let clue = |guess: &str, score: &str| {
+            (
+                Word::<5>::try_from(guess).unwrap(),
+                parse_score(score).unwrap(),
+            )
+        };
+        let history = vec![clue("crane", "bbybb"), clue("slate", "ybygb")];
+        let word = |word: &str| Word::<5>::try_from(word).unwrap();
+        assert!(is_consistent(&word("hasty"), &history));
+        assert!(!is_consistent(&word("soare"), &history));
+        assert_eq!(word::first_conflict(&word("soare"), &history), Some(0));
+        assert_eq!(word::first_conflict(&word("tapas"), &history), Some(1));
+        assert_eq!(
+            word::conflict_reasons(&word("soare"), &history[0].0, &history[0].1),
+            vec![
+                "has too many rs for the grey in position 2",
+                "has a in position 3, which was yellow",
+                "has too many es for the grey in position 5"
+            ]
+        );
+        assert_eq!(
+            word::conflict_reasons(&word("tapas"), &history[1].0, &history[1].1),
+            vec!["doesn't have t in position 4, which was green"]
+        );
+        assert!(word::conflict_reasons(&word("hasty"), &history[1].0, &history[1].1).is_empty());
+    }
+
+    #[test]
+    fn test_bucket_histogram() {
+        let words: WordList<3> = vec!["bat", "cat", "hat", "tab", "cab", "ant"]
+            .into_iter()
+            .map(|word| Word::try_from(word).unwrap())
+            .collect();
+        let mut out = Vec::new();
+        trace::write_bucket_histogram(
+            &mut out,
+            &Word::try_from("cat").unwrap(),
+            &words,
+            &parse_score("bgg").unwrap(),
+            2,
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "cat split 6 viable words into 5 scores:\n\
+             \x20 bgg      2  ########################################  <- this turn\n\
+             \x20 ggg      1  ####################\n\
+             \x20 ... and 3 more\n"
+        );
+    }
+
+    #[test]
+    fn test_frequencies() {
+        use frequency::Frequencies;
+        let word_list: WordList<3> = ["bat", "cat", "hat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let word = |s: &str| Word::<3>::try_from(s).unwrap();
+        let frequencies =
+            Frequencies::<3>::parse_csv("word,count\nHAT,90\n\"cat\",5\nmouse,100\nbat,5\n")
+                .unwrap();
+        assert_eq!(frequencies.weight(&word("hat")), 90.0);
+        assert_eq!(frequencies.weight(&word("cot")), 5.0);
+        assert_eq!(frequencies.probability(&word("hat"), &word_list), 0.9);
+        assert_eq!(frequencies.probability(&word("cot"), &word_list), 0.0);
+        assert!(frequencies.entropy(&word_list, &word("bat")) < word_list.entropy(&word("bat")));
+        let even = Frequencies::<3>::parse_csv("bat,1\ncat,1\nhat,1\n").unwrap();
+        assert!(
+            (even.entropy(&word_list, &word("bat")) - word_list.entropy(&word("bat"))).abs() < 1e-9
+        );
+
+        let error = Frequencies::<3>::parse_csv("bat,1\ncat,lots\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: bad count \"lots\"");
+        assert!(Frequencies::<3>::parse_csv("bat,-1\n").is_err());
+        assert!(Frequencies::<3>::parse_csv("mouse,1\n").is_err());
+
+        // Every guess splits these apart, so the likeliest answer goes first.
+        let mut strategy = strategy::EntropyStrategy::new(word_list.clone());
+        assert_eq!(strategy.make_guess().unwrap(), word("bat"));
+        let mut strategy = strategy::EntropyStrategy::new(word_list);
+        strategy.set_frequencies(std::sync::Arc::new(frequencies));
+        assert_eq!(strategy.make_guess().unwrap(), word("hat"));
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn test_daily_cache() {
+        use daily::{DailyCache, DailyPuzzle};
+        let json = r#"{"id":1780,"solution":"crane","print_date":"2024-05-06","days_since_launch":1052,"editor":"Tracy Bennett"}"#;
+        let puzzle = DailyPuzzle::from_json(json).unwrap();
+        assert_eq!(puzzle.puzzle, 1052);
+        assert_eq!(puzzle.answer::<5>(), Word::try_from("crane").ok());
+        assert!(DailyPuzzle::from_json("{}").is_err());
+
+        let dir = std::env::temp_dir().join(format!("wordle-solve-daily-{}", std::process::id()));
+        let date = "2024-05-06".parse().unwrap();
+        let cache = DailyCache::new(&dir);
+        assert_eq!(cache.cached(date).unwrap(), None);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("2024-05-06.json"), json).unwrap();
+        // A cached date is never fetched.
+        assert_eq!(cache.get(date).unwrap(), puzzle);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_eliminated() {
+        let mut words: WordList<3> = vec!["bat", "cat", "hat", "tab", "cab", "ant"]
+            .into_iter()
+            .map(|word| Word::try_from(word).unwrap())
+            .collect();
+        let eliminated = words.eliminate(
+            &Word::try_from("cat").unwrap(),
+            &parse_score("bgg").unwrap(),
+        );
+        assert_eq!(words.0.len(), 2);
+        assert_eq!(eliminated.len(), 4);
+        assert_eq!(eliminated[0], Word::try_from("cat").unwrap());
+
+        let write = |max_words| {
+            let mut out = Vec::new();
+            trace::write_eliminated(&mut out, &eliminated, max_words).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        assert_eq!(write(2), "Ruled out 4 words: cat, tab and 2 more\n");
+        assert_eq!(write(10), "Ruled out 4 words: cat, tab, cab, ant\n");
+        assert_eq!(write(0), "Ruled out 4 words\n");
+    }
+
+    #[test]
+    fn test_human_errors() {
+        use noise::{HumanErrors, Noisy};
+        let word_list: WordList<3> = vec!["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .into_iter()
+            .map(|s| Word::try_from(s).unwrap())
+            .collect();
+        let run = |errors, seed| {
+            eval::evaluate(
+                || {
+                    Noisy::new(
+                        || strategy::SimpleStrategy::new(word_list.clone()),
+                        word_list.clone(),
+                        errors,
+                        seed,
+                    )
+                },
+                &word_list,
+                &word_list,
+                6,
+            )
+        };
+        let perfect = eval::evaluate(
+            || strategy::SimpleStrategy::new(word_list.clone()),
+            &word_list,
+            &word_list,
+            6,
+        );
+        assert_eq!(run(HumanErrors::default(), 0), perfect);
+
+        let sloppy = HumanErrors {
+            guess_error: 0.5,
+            score_error: 0.5,
+        };
+        let results = run(sloppy, 3);
+        assert_eq!(results.games, 8);
+        assert_eq!(run(sloppy, 3), results);
+    }
+
+    #[test]
+    fn test_guess_rules() {
+        use adaptive::AdaptiveStrategy;
+        use strategy::{GuessRules, HumanLikeStrategy, SimpleStrategy};
+        let word_list: WordList<3> = vec!["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .into_iter()
+            .map(|s| Word::try_from(s).unwrap())
+            .collect();
+        let word = |s: &str| Word::<3>::try_from(s).unwrap();
+        let rules = GuessRules {
+            never_guess: vec![word("cat"), word("cog")].into_iter().collect(),
+            always_open: Some(word("dog")),
+        };
+
+        fn play<S: Strategy<3>>(
+            mut strategy: S,
+            secret: Word<3>,
+            word_list: &WordList<3>,
+        ) -> Vec<Word<3>> {
+            let engine = engine::StandardEngine::new(secret, word_list.clone(), 4);
+            let mut guesses = Vec::new();
+            loop {
+                let guess = strategy.make_guess().unwrap();
+                guesses.push(guess);
+                match engine.score_guess(&guess) {
+                    GuessResult::Continue(score) => strategy.receive_score(&score),
+                    _ => return guesses,
+                }
+            }
+        }
+
+        for secret in &word_list.0 {
+            let mut simple = SimpleStrategy::new(word_list.clone());
+            simple.set_guess_rules(rules.clone());
+            let mut adaptive = AdaptiveStrategy::new(word_list.clone());
+            adaptive.set_guess_rules(rules.clone());
+            let mut human = HumanLikeStrategy::new(word_list.clone(), 0.5, 1);
+            human.set_guess_rules(rules.clone());
+            for guesses in [
+                play(simple, *secret, &word_list),
+                play(adaptive, *secret, &word_list),
+                play(human, *secret, &word_list),
+            ] {
+                assert_eq!(guesses[0], word("dog"));
+                assert!(guesses.iter().all(|guess| rules.allows(guess)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_free_letters() {
+        let word_list: WordList<3> = vec!["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .into_iter()
+            .map(|s| Word::try_from(s).unwrap())
+            .collect();
+        let secret = Word::try_from("cog").unwrap();
+        let engine = engine::StandardEngine::new(secret, word_list.clone(), 6)
+            .reveal_free_letter(0)
+            .reveal_free_letter(1);
+        let free_letters = engine.free_letters();
+        assert_eq!(free_letters.len(), 2);
+        assert_ne!(free_letters[0].position, free_letters[1].position);
+        assert!(free_letters
+            .iter()
+            .all(|free_letter| free_letter.fits(&secret)));
+
+        // Every letter is given away at most once.
+        let engine = (0..5).fold(
+            engine::StandardEngine::new(secret, word_list.clone(), 6),
+            |engine, seed| engine.reveal_free_letter(seed),
+        );
+        assert_eq!(engine.free_letters().len(), 3);
+
+        let free_letter = FreeLetter {
+            position: 1,
+            letter: 'a',
+        };
+        let mut strategy = strategy::HumanLikeStrategy::new(word_list.clone(), 0.0, 0);
+        strategy.prime(&[Clue::Letter(free_letter)]);
+        assert!(free_letter.fits(&strategy.make_guess().unwrap()));
+        assert!(run_game(
+            engine::StandardEngine::new(secret, word_list.clone(), 6).reveal_free_letter(3),
+            strategy::HumanLikeStrategy::new(word_list, 0.0, 0)
+        ));
+    }
+
+    #[test]
+    fn test_event_log() {
+        use event_log::{EngineEvent, LoggedEngine};
+        let word_list: WordList<3> = ["bat", "cat", "hat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let word = |s: &str| Word::<3>::try_from(s).unwrap();
+        let engine = LoggedEngine::new(
+            engine::StandardEngine::new(word("hat"), word_list.clone(), 6)
+                .reject_repeated_guesses(),
+        );
+        for guess in ["bat", "bat", "hat"] {
+            engine.score_guess(&word(guess));
+        }
+        let events = engine.events();
+        assert_eq!(
+            events
+                .iter()
+                .map(|(_, event)| event.clone())
+                .collect::<Vec<_>>(),
+            [
+                EngineEvent::GuessReceived(word("bat")),
+                EngineEvent::Scored(parse_score("bgg").unwrap()),
+                EngineEvent::GuessReceived(word("bat")),
+                EngineEvent::Refused {
+                    error: engine::GuessError::Repeated(word("bat")),
+                    wasted: false
+                },
+                EngineEvent::GuessReceived(word("hat")),
+                EngineEvent::GameOver { won: true },
+            ]
+        );
+        assert!(events.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+
+        let mut out = Vec::new();
+        engine.write_log(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out
+            .lines()
+            .map(|line| line.split_once("s ").unwrap().1)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "guess received: bat",
+                "scored: bgg",
+                "guess received: bat",
+                "refused: bat was already guessed",
+                "guess received: hat",
+                "game over: won",
+            ]
+        );
+
+        let record = record::GameRecord {
+            date: None,
+            puzzle: None,
+            max_guesses: 6,
+            board: engine.board(),
+            think_times: Vec::new(),
+        };
+        assert_eq!(record.to_string(), "guesses: 6\nbat bgg\nhat ggg\n");
+        let grid = |text: &str| {
+            text.lines()
+                .filter_map(tiles::TileStyle::parse_tiles)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            record.first_divergence(&grid("Wordle 2/6\n⬛🟩🟩\n🟩🟩🟩")),
+            None
+        );
+        let divergence = record.first_divergence(&grid("🟨🟩🟩\n🟩🟩🟩")).unwrap();
+        assert_eq!(divergence.row, 1);
+        assert_eq!(
+            divergence.to_string(),
+            "row 1: bat was entered as bgg but the game scored it ygg"
+        );
+        assert_eq!(
+            record
+                .first_divergence(&grid("⬛🟩🟩"))
+                .unwrap()
+                .to_string(),
+            "row 2: hat ggg was entered but the game has no such row"
+        );
+    }
+
+    #[test]
+    fn test_observer() {
+        #[derive(Default)]
+        struct Recorder {
+            events: Vec<String>,
+            transcript: Option<GameTranscript<3>>,
+        }
+
+        impl observer::Observer<3> for Recorder {
+            fn on_guess(&mut self, guess: &Word<3>, metrics: Option<metrics::Metrics>) {
+                self.events
+                    .push(format!("guess {} {}", guess, metrics.is_some()));
+            }
+
+            fn on_score(&mut self, guess: &Word<3>, result: &GuessResult<3>) {
+                let result = match result {
+                    GuessResult::Continue(score) => format_score(score),
+                    GuessResult::Done { won, .. } => format!("done {}", won),
+                    _ => "other".to_owned(),
+                };
+                self.events.push(format!("score {} {}", guess, result));
+            }
+
+            fn on_end(&mut self, transcript: &GameTranscript<3>) {
+                self.transcript = Some(transcript.clone());
+            }
+        }
+
+        let word_list: WordList<3> = ["bat", "cat", "hat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let engine =
+            engine::StandardEngine::new(Word::try_from("hat").unwrap(), word_list.clone(), 6);
+        let mut recorder = Recorder::default();
+        let transcript = run_game_with_observer(
+            engine,
+            strategy::SimpleStrategy::new(word_list),
+            &mut recorder,
+        )
+        .unwrap();
+        let guesses = transcript
+            .turns
+            .iter()
+            .map(|turn| turn.guess.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(recorder.events.len(), 2 * guesses.len());
+        for (events, guess) in recorder.events.chunks(2).zip(&guesses) {
+            assert_eq!(events[0], format!("guess {} true", guess));
+            assert!(events[1].starts_with(&format!("score {} ", guess)));
+        }
+        assert_eq!(recorder.events.last().unwrap(), "score hat done true");
+        assert_eq!(recorder.transcript, Some(transcript));
+    }
+
+    #[test]
+    fn test_trait_objects() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "mat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let secret = Word::try_from("mat").unwrap();
+        for name in ["simple", "adaptive", "entropy", "optimal"] {
+            let solver: strategy::Solver = name.parse().unwrap();
+            let engines: Vec<Box<dyn Engine<3>>> = vec![
+                Box::new(engine::StandardEngine::new(secret, word_list.clone(), 6)),
+                Box::new(event_log::LoggedEngine::new(engine::StandardEngine::new(
+                    secret,
+                    word_list.clone(),
+                    6,
+                ))),
+            ];
+            for engine in engines {
+                let strategy: Box<dyn Strategy<3>> =
+                    solver.build(word_list.clone(), Default::default());
+                assert!(run_game(engine, strategy), "{} lost", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_strategy_builders() {
+        use builder::BuildError;
+        use strategy::{EntropyStrategy, SimpleStrategy, TieBreak};
+        let word = |s: &str| Word::<3>::try_from(s).unwrap();
+        let guess_list: WordList<3> = ["bog", "bat", "cat"].iter().map(|s| word(s)).collect();
+        let answer_list: WordList<3> = ["bat", "cat"].iter().map(|s| word(s)).collect();
+
+        assert_eq!(
+            SimpleStrategy::<3>::builder().build().err(),
+            Some(BuildError::NoWordList)
+        );
+        assert_eq!(
+            EntropyStrategy::builder()
+                .word_list(guess_list.clone())
+                .opener(word("dog"))
+                .build()
+                .err(),
+            Some(BuildError::OpenerNotInWordList(word("dog")))
+        );
+
+        // Every guess splits the answers in two; the tie break decides.
+        let mut likeliest = EntropyStrategy::builder()
+            .word_list(guess_list.clone())
+            .answer_list(answer_list.clone())
+            .build()
+            .unwrap();
+        assert_eq!(likeliest.make_guess().unwrap(), word("bat"));
+        let mut first = EntropyStrategy::builder()
+            .word_list(guess_list.clone())
+            .answer_list(answer_list.clone())
+            .tie_break(TieBreak::First)
+            .build()
+            .unwrap();
+        assert_eq!(first.make_guess().unwrap(), word("bog"));
+
+        let mut simple = SimpleStrategy::builder()
+            .word_list(guess_list)
+            .answer_list(answer_list)
+            .opener(word("cat"))
+            .build()
+            .unwrap();
+        assert_eq!(simple.make_guess().unwrap(), word("cat"));
+    }
+
+    #[test]
+    fn test_random_secret_engine() {
+        use engine::RandomSecretEngine;
+        let word_list: WordList<3> = vec!["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .into_iter()
+            .map(|s| Word::try_from(s).unwrap())
+            .collect();
+        let secrets = |seed: u64| {
+            (0..20)
+                .map(|game| {
+                    RandomSecretEngine::new(word_list.clone(), 6, seed + game)
+                        .unwrap()
+                        .secret()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(secrets(0), secrets(0));
+        assert_ne!(secrets(0), secrets(100));
+        assert!(secrets(0).iter().all(|secret| word_list.0.contains(secret)));
+
+        let engine = RandomSecretEngine::new(word_list.clone(), 6, 7).unwrap();
+        let secret = engine.secret();
+        let transcript =
+            run_game_recorded(&engine, strategy::SimpleStrategy::new(word_list.clone()));
+        assert!(transcript.won);
+        assert_eq!(transcript.turns.last().unwrap().guess, secret);
+
+        // A word the corpus makes much more common comes up most of the time.
+        let frequencies =
+            frequency::Frequencies::<3>::parse_csv("word,count\ndog,1000000\ncat,1\n").unwrap();
+        let dogs = (0..50)
+            .filter(|seed| {
+                let engine =
+                    RandomSecretEngine::weighted(word_list.clone(), 6, *seed, &frequencies)
+                        .unwrap();
+                engine.secret() == Word::try_from("dog").unwrap()
+            })
+            .count();
+        assert!(dogs > 45, "{}", dogs);
+        assert_eq!(
+            engine::random_secret(&WordList::<3>(Vec::new()), 0, None),
+            None
+        );
+        assert!(RandomSecretEngine::new(WordList::<3>(Vec::new()), 6, 0).is_none());
+        assert!(RandomSecretEngine::weighted(WordList(Vec::new()), 6, 0, &frequencies).is_none());
+    }
+
+    #[test]
+    fn test_prime() {
+        let word_list: WordList<3> = vec!["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .into_iter()
+            .map(|s| Word::try_from(s).unwrap())
+            .collect();
+        let word = |s: &str| Word::<3>::try_from(s).unwrap();
+        let clues = [
+            Clue::Scored(word("bat"), parse_score("bbb").unwrap()),
+            Clue::Letter(FreeLetter {
+                position: 0,
+                letter: 'c',
+            }),
+        ];
+        let mut viable = word_list.clone();
+        viable.retain_fitting(&clues);
+        assert_eq!(viable.0, vec![word("cog")]);
+
+        // Resuming after those clues finds the answer straight away.
+        let mut simple = strategy::SimpleStrategy::new(word_list.clone());
+        simple.prime(&clues);
+        assert_eq!(simple.make_guess().unwrap(), word("cog"));
+        let mut adaptive = adaptive::AdaptiveStrategy::new(word_list.clone());
+        adaptive.prime(&clues);
+        assert_eq!(adaptive.make_guess().unwrap(), word("cog"));
+        let mut human = strategy::HumanLikeStrategy::new(word_list, 0.0, 0);
+        human.prime(&clues);
+        assert_eq!(human.make_guess().unwrap(), word("cog"));
+    }
+
+    #[test]
+    fn test_timed_games() {
+        use std::time::Duration;
+        let record =
+            record::GameRecord::<3>::parse("guesses: 6\nbat bgg 4.5s\ncat ggg 12.0s\n").unwrap();
+        assert_eq!(
+            record.think_times,
+            vec![Duration::from_millis(4500), Duration::from_secs(12)]
+        );
+        assert_eq!(
+            record.total_think_time(),
+            Some(Duration::from_millis(16500))
+        );
+        assert_eq!(record::GameRecord::parse(&record.to_string()), Ok(record));
+        assert!(record::GameRecord::<3>::parse("bat bgg 4.5s\ncat ggg\n").is_err());
+        assert!(record::GameRecord::<3>::parse("bat bgg\ncat ggg 1s\n").is_err());
+        assert!(record::GameRecord::<3>::parse("bat bgg soon\n").is_err());
+
+        let mut played = history::PlayedPuzzles::parse("210 4 80.5\n211 X 200\n").unwrap();
+        assert_eq!(played.time(210), Some(Duration::from_millis(80500)));
+        played.record_timed(212, Some(2), Duration::from_secs(30));
+        // A replay doesn't change the time any more than the result.
+        played.record_timed(212, Some(1), Duration::from_secs(5));
+        assert_eq!(played.time(212), Some(Duration::from_secs(30)));
+        assert_eq!(
+            history::PlayedPuzzles::parse(&played.to_string()).unwrap(),
+            played
+        );
+        assert!(played
+            .stats()
+            .ends_with("Timed wins: 2, average 0:55, fastest 0:30, 18.4s per guess\n"));
+    }
+
+    #[test]
+    fn test_append_scores() {
+        let input = "Secret,Guess,source\ncrane,caper,forum\n\n\"abbey\",babes\n";
+        let mut out = Vec::new();
+        assert_eq!(
+            dataset::append_scores(input.as_bytes(), &mut out).unwrap(),
+            2
+        );
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Secret,Guess,source,score\ncrane,caper,forum,gybyy\n\n\"abbey\",babes,yyggb\n"
+        );
+        assert!(matches!(
+            dataset::append_scores("crane,caper\ncrane\n".as_bytes(), Vec::new()),
+            Err(dataset::DatasetError::MissingColumn { line: 2 })
+        ));
+        assert!(matches!(
+            dataset::append_scores("crane,cape\n".as_bytes(), Vec::new()),
+            Err(dataset::DatasetError::Score { line: 1, .. })
+        ));
+    }
+
     #[test]
     fn test_simple_strategy() {
         let word_list: WordList<5> = word_list::WORD_LIST
@@ -76,6 +901,2705 @@ mod tests {
         let strategy = strategy::SimpleStrategy::new(word_list.clone());
         let word = Word::try_from("favor").unwrap();
         let engine = engine::StandardEngine::new(word, word_list, 10);
-        assert!(matches!(run_game(engine, strategy), true));
+        assert!(run_game(engine, strategy));
+    }
+
+    #[test]
+    fn test_simple_strategy_opener() {
+        use strategy::SimpleStrategy;
+        let word = |s: &str| Word::<5>::try_from(s).unwrap();
+        let word_list: WordList<5> = ["arose", "crane", "slate", "plumb", "fight"]
+            .iter()
+            .map(|s| word(s))
+            .collect();
+        assert_eq!(
+            SimpleStrategy::new(word_list.clone()).make_guess().unwrap(),
+            word(strategy::DEFAULT_OPENER)
+        );
+        let mut strategy = SimpleStrategy::new(word_list.clone())
+            .with_opener(word("plumb"))
+            .unwrap();
+        assert_eq!(strategy.make_guess().unwrap(), word("plumb"));
+        assert!(matches!(
+            SimpleStrategy::new(word_list.clone()).with_opener(word("zzzzz")),
+            Err(engine::GuessError::NotInWordList(_))
+        ));
+
+        let mut computed = SimpleStrategy::new(word_list.clone()).with_computed_opener();
+        let best = computed.best_opener().unwrap();
+        assert_eq!(computed.make_guess().unwrap(), best);
+
+        // Without the default opener in the list, the first guess is worked out.
+        let short: WordList<3> = ["bat", "cat", "dog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let mut strategy = SimpleStrategy::new(short);
+        let best = strategy.best_opener().unwrap();
+        assert_eq!(strategy.make_guess().unwrap(), best);
+
+        // An empty word list has no opener to give.
+        let empty = SimpleStrategy::<3>::new(WordList(Vec::new())).with_computed_opener();
+        assert_eq!(empty.best_opener(), Err(StrategyError::OutOfCandidates));
+    }
+
+    #[test]
+    fn test_ensemble_strategy() {
+        use advice::Advisor;
+        use ensemble::{EnsembleStrategy, Vote};
+        use ranking::{LetterFrequency, Ranker};
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        for secret in &word_list.0 {
+            let strategy = EnsembleStrategy::new(word_list.clone(), Vote::Borda)
+                .with_member(Advisor::Entropy, 1.0)
+                .with_member(Advisor::Minimax, 1.0)
+                .with_member(LetterFrequency, 0.5);
+            let engine = engine::StandardEngine::new(*secret, word_list.clone(), 6);
+            assert!(run_game(engine, strategy), "lost on {}", secret);
+        }
+
+        // With plurality voting, the heaviest member's favourite wins.
+        let favourite = LetterFrequency.rank(&word_list, &word_list)[0];
+        let mut strategy = EnsembleStrategy::new(word_list.clone(), Vote::Plurality)
+            .with_member(Advisor::Entropy, 1.0)
+            .with_member(LetterFrequency, 2.0);
+        assert_eq!(strategy.make_guess().unwrap(), favourite);
+    }
+
+    #[test]
+    fn test_run_benchmark() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "can", "tan", "ant", "nab"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let report = bench::run_benchmark(
+            || strategy::EntropyStrategy::new(word_list.clone()),
+            &word_list,
+        );
+        assert_eq!(report.games(), 7);
+        assert_eq!(report.losses, 0);
+        assert_eq!(report.histogram.len(), bench::MAX_GUESSES);
+        assert_eq!(report.histogram.iter().sum::<usize>(), report.wins);
+        assert!(report.to_string().ends_with(&format!(
+            "won 7 of 7, {:.3} guesses per win",
+            report.average_guesses().unwrap()
+        )));
+        let all_lost = bench::BenchmarkReport {
+            wins: 0,
+            losses: 2,
+            histogram: vec![0; bench::MAX_GUESSES],
+        };
+        assert_eq!(all_lost.average_guesses(), None);
+        assert!(all_lost.to_string().ends_with("X: 2\nwon 0 of 2"));
+
+        // Games run in parallel, but the report comes out the same.
+        let simple = || {
+            bench::run_benchmark(
+                || strategy::SimpleStrategy::new(word_list.clone()),
+                &word_list,
+            )
+        };
+        assert_eq!(simple(), simple());
+
+        // Both solvers weigh up every unguessed word each turn, scoring it
+        // against every viable word.
+        let (report, metrics) = bench::run_benchmark_metered(
+            || strategy::EntropyStrategy::new(word_list.clone()),
+            &word_list,
+        );
+        assert_eq!(report.wins, 7);
+        assert!(metrics.candidates_evaluated >= report.histogram.iter().sum::<usize>());
+        assert!(metrics.scores_computed >= metrics.candidates_evaluated);
+        assert_eq!(metrics.cache_hits, 0);
+
+        let mut simple = strategy::SimpleStrategy::new(word_list.clone());
+        assert_eq!(simple.last_metrics(), None);
+        simple.make_guess().unwrap();
+        let metrics = simple.last_metrics().unwrap();
+        assert_eq!(metrics.candidates_evaluated, 7);
+        assert_eq!(metrics.scores_computed, 49);
+        let mut cached = strategy::SimpleStrategy::new(word_list.clone());
+        cached.set_score_matrix(std::sync::Arc::new(score_matrix::ScoreMatrix::new(
+            &word_list, &word_list,
+        )));
+        cached.make_guess().unwrap();
+        assert_eq!(cached.last_metrics().unwrap().cache_hits, 49);
+        assert_eq!(cached.last_metrics().unwrap().scores_computed, 0);
+    }
+
+    #[test]
+    fn test_score_matrix() {
+        use score_matrix::{pattern_id, solved_pattern, ScoreMatrix};
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        assert_eq!(pattern_id(&[Wrong, Wrong, Wrong]), 0);
+        assert_eq!(pattern_id(&[RightLetter, Wrong, Wrong]), 1);
+        assert_eq!(pattern_id(&[Wrong, Wrong, RightPlace]), 18);
+        assert_eq!(solved_pattern::<3>(), 26);
+
+        let secrets = WordList(word_list.0[2..].to_vec());
+        let matrix = ScoreMatrix::new(&word_list, &secrets);
+        for guess in &word_list.0 {
+            for secret in &secrets.0 {
+                assert_eq!(
+                    matrix.pattern(
+                        matrix.guess_index(guess).unwrap(),
+                        matrix.secret_index(secret).unwrap()
+                    ),
+                    pattern_id(&secret.evaluate_guess(guess))
+                );
+            }
+        }
+        assert_eq!(matrix.secret_index(&word_list.0[0]), None);
+
+        // Looking scores up changes nothing about how the strategy plays.
+        let matrix = std::sync::Arc::new(ScoreMatrix::new(&word_list, &word_list));
+        let summary = |matrix: Option<&std::sync::Arc<ScoreMatrix<3>>>| {
+            eval::evaluate(
+                || {
+                    let mut strategy = strategy::SimpleStrategy::new(word_list.clone());
+                    if let Some(matrix) = matrix {
+                        strategy.set_score_matrix(matrix.clone());
+                    }
+                    strategy
+                },
+                &word_list,
+                &word_list,
+                6,
+            )
+        };
+        assert_eq!(summary(Some(&matrix)), summary(None));
+    }
+
+    #[test]
+    fn test_entropy_strategy() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        for secret in &word_list.0 {
+            let strategy = strategy::EntropyStrategy::new(word_list.clone());
+            let engine = engine::StandardEngine::new(*secret, word_list.clone(), 6);
+            assert!(run_game(engine, strategy), "lost on {}", secret);
+        }
+
+        // The opener splits the words as evenly as any word can.
+        let mut strategy = strategy::EntropyStrategy::new(word_list.clone());
+        let opener = strategy.make_guess().unwrap();
+        let best = word_list
+            .0
+            .iter()
+            .map(|word| word_list.entropy(word))
+            .fold(0.0, f64::max);
+        assert_eq!(word_list.entropy(&opener), best);
+    }
+
+    #[test]
+    fn test_adaptive_strategy() {
+        use adaptive::{AdaptiveStrategy, Method};
+        assert_eq!(AdaptiveStrategy::<5>::method_for(5000), Method::Frequency);
+        assert_eq!(AdaptiveStrategy::<5>::method_for(1000), Method::Entropy);
+        assert_eq!(AdaptiveStrategy::<5>::method_for(50), Method::Entropy);
+        assert_eq!(AdaptiveStrategy::<5>::method_for(49), Method::Exact);
+        assert_eq!(AdaptiveStrategy::<5>::method_for(1), Method::Forced);
+
+        let word_list: WordList<5> = word_list::WORD_LIST
+            .iter()
+            .map(|s| Word::<5>::try_from(*s).unwrap())
+            .collect();
+        let mut strategy = AdaptiveStrategy::new(word_list.clone());
+        let engine = engine::StandardEngine::new(Word::try_from("favor").unwrap(), word_list, 6);
+        assert!(run_game(engine, &mut strategy));
+        assert!(matches!(
+            strategy.last_method(),
+            Some(Method::Exact | Method::Forced)
+        ));
+    }
+
+    #[test]
+    fn test_think_budget() {
+        let word_list: WordList<5> = word_list::WORD_LIST
+            .iter()
+            .map(|s| Word::<5>::try_from(*s).unwrap())
+            .collect();
+        // With no time to think, the solver still plays words that could be
+        // the answer rather than hanging or guessing blindly.
+        let mut strategy = strategy::SimpleStrategy::new(word_list.clone());
+        strategy.set_think_budget(std::time::Duration::ZERO);
+        let start = std::time::Instant::now();
+        let word = Word::try_from("favor").unwrap();
+        let engine = engine::StandardEngine::new(word, word_list, 10);
+        assert!(run_game(engine, strategy));
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_incremental_suggest() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let strategy = strategy::SimpleStrategy::new(word_list.clone());
+        let mut search = strategy.start_suggest();
+        let mut polls = 1;
+        let suggestion = loop {
+            if let Some(guess) = search.poll_suggest(std::time::Duration::ZERO).unwrap() {
+                break guess;
+            }
+            assert!(search.best_so_far().is_some());
+            polls += 1;
+        };
+        assert_eq!(polls, word_list.0.len());
+        assert_eq!(search.progress(), (polls, polls));
+
+        let mut budgeted = strategy::SimpleStrategy::new(word_list);
+        budgeted.set_think_budget(std::time::Duration::from_secs(60));
+        assert_eq!(budgeted.make_guess().unwrap(), suggestion);
+
+        let mut empty = strategy::SimpleStrategy::<3>::new(WordList(Vec::new())).start_suggest();
+        assert_eq!(
+            empty.poll_suggest(std::time::Duration::ZERO),
+            Err(StrategyError::OutOfCandidates)
+        );
+    }
+
+    #[test]
+    fn test_multi_length_session() {
+        let mut session =
+            session::MultiLengthSession::new(vec!["bat", "cats", "dogs", "cogs", "hogs"]);
+        assert_eq!(session.suggest(), Err(session::SessionError::NoActiveGame));
+        assert_eq!(
+            session.start_game(3),
+            Err(session::SessionError::UnsupportedLength(3))
+        );
+        assert_eq!(
+            session.start_game(6),
+            Err(session::SessionError::NoWordsOfLength(6))
+        );
+
+        session.start_game(4).unwrap();
+        assert_eq!(session.current_length(), Some(4));
+        assert_eq!(
+            session.record_score("gggg"),
+            Err(session::SessionError::NoPendingGuess)
+        );
+        let guess = session.suggest().unwrap();
+        assert_eq!(guess.len(), 4);
+        assert!(session.record_score("bbbbbb").is_err());
+        session.record_score("bggg").unwrap();
+        assert_eq!(
+            session.record_score("bggg"),
+            Err(session::SessionError::NoPendingGuess)
+        );
+    }
+
+    #[test]
+    fn test_antiwordle() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let engine =
+            engine::AntiWordleEngine::new(Word::try_from("cat").unwrap(), word_list.clone());
+        let strategy = strategy::AntiWordleStrategy::new(word_list.clone());
+        assert!(!run_game(&engine, strategy));
+        assert!(engine.turns_taken() > 1);
+
+        // Refused guesses don't take a turn.
+        let word = |s: &str| Word::try_from(s).unwrap();
+        let engine = engine::AntiWordleEngine::new(word("cat"), word_list.clone());
+        assert!(matches!(
+            engine.score_guess(&word("zzz")),
+            GuessResult::Rejected(engine::GuessError::NotInWordList(_))
+        ));
+        assert!(matches!(
+            engine.score_guess(&word("dog")),
+            GuessResult::Continue(_)
+        ));
+        assert!(matches!(
+            engine.score_guess(&word("cog")),
+            GuessResult::Rejected(engine::GuessError::Inconsistent { earlier })
+                if earlier == word("dog")
+        ));
+        assert_eq!(engine.turns_taken(), 1);
+
+        // A strategy that can retry picks something else.
+        let strategies: Vec<Box<dyn strategy::Strategy<3>>> = vec![
+            Box::new(strategy::SimpleStrategy::new(word_list.clone())),
+            Box::new(strategy::EntropyStrategy::new(word_list.clone())),
+            Box::new(strategy::AntiWordleStrategy::new(word_list.clone())),
+            Box::new(strategy::HumanLikeStrategy::new(word_list, 0.0, 0)),
+        ];
+        for mut strategy in strategies {
+            let first = strategy.make_guess().unwrap();
+            strategy
+                .guess_rejected(&first, &engine::GuessError::NotInWordList(first))
+                .unwrap();
+            assert_ne!(strategy.make_guess().unwrap(), first);
+        }
+    }
+
+    #[test]
+    fn test_absurdle() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let engine = engine::AbsurdleEngine::new(word_list.clone());
+        let cat = Word::try_from("cat").unwrap();
+        // Dog, bog and hog share nothing with cat, the biggest bucket.
+        assert!(matches!(
+            engine.score_guess(&cat),
+            GuessResult::Continue([Wrong, Wrong, Wrong])
+        ));
+        assert_eq!(engine.candidates().0.len(), 3);
+
+        let engine = engine::AbsurdleEngine::new(word_list.clone());
+        assert!(run_game(&engine, strategy::EntropyStrategy::new(word_list)));
+        assert_eq!(engine.candidates().0.len(), 1);
+        // No single guess can be conceded straight away.
+        assert!(engine.turns_taken() > 1);
+    }
+
+    #[test]
+    fn test_restricted_guesses() {
+        let words = |list: &[&str]| -> WordList<3> {
+            list.iter().map(|s| Word::try_from(*s).unwrap()).collect()
+        };
+        let answers = words(&["bat", "cot", "dig"]);
+        let full = words(&["bat", "cot", "dig", "xyz"]);
+        let report = eval::evaluate_restricted_guesses(&answers, &full, &answers, 6);
+        assert_eq!(report.full.games, 3);
+        assert_eq!(report.full.wins, 3);
+        assert_eq!(report.restricted.wins, 3);
+        assert!(report.extra_guesses().unwrap() >= 0.0);
+
+        // Losing every game leaves no guesses per win to compare, rather
+        // than a NaN.
+        let lost = eval::EvaluationSummary {
+            games: 3,
+            wins: 0,
+            guesses_in_wins: 0,
+        };
+        let report = eval::RestrictedGuessReport {
+            full: report.full,
+            restricted: lost,
+        };
+        assert_eq!(lost.average_guesses(), None);
+        assert_eq!(report.extra_guesses(), None);
+        assert_eq!(report.win_rate_loss(), Some(1.0));
+        assert_eq!(eval::EvaluationSummary::default().win_rate(), None);
+    }
+
+    #[test]
+    fn test_cross_validate() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let (train, test) = eval::split_answers(&word_list, 7);
+        assert_eq!(train.0.len(), 4);
+        assert_eq!(test.0.len(), 4);
+        assert!(train.0.iter().all(|word| !test.0.contains(word)));
+
+        let openers = ["bat", "dog", "cog"].map(|s| Word::try_from(s).unwrap());
+        let run = || {
+            eval::cross_validate(
+                &openers,
+                |opener| {
+                    let mut strategy = strategy::SimpleStrategy::new(word_list.clone());
+                    strategy.set_guess_rules(strategy::GuessRules {
+                        always_open: Some(*opener),
+                        ..Default::default()
+                    });
+                    strategy
+                },
+                &word_list,
+                &word_list,
+                6,
+                7,
+            )
+        };
+        let result = run().unwrap();
+        assert!(openers.contains(&result.chosen));
+        assert_eq!(result.train.games + result.test.games, 8);
+        assert_eq!(
+            result.generalization_gap(),
+            Some(result.test.average_guesses().unwrap() - result.train.average_guesses().unwrap())
+        );
+        assert_eq!(run(), Some(result));
+        assert!(eval::cross_validate(
+            &[] as &[Word<3>],
+            |_| strategy::SimpleStrategy::new(word_list.clone()),
+            &word_list,
+            &word_list,
+            6,
+            7,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_bootstrap_mean() {
+        use stats::bootstrap_mean;
+        assert_eq!(bootstrap_mean(&[], 0.95, 100, 0), None);
+        let constant = bootstrap_mean(&[4.0; 10], 0.95, 100, 0).unwrap();
+        assert_eq!((constant.low, constant.high), (4.0, 4.0));
+
+        let samples = [3.0, 4.0, 4.0, 5.0, 3.0, 4.0, 6.0, 2.0, 4.0, 5.0];
+        let interval = bootstrap_mean(&samples, 0.95, 1000, 0).unwrap();
+        assert!(interval.contains(4.0));
+        assert!(interval.low > 2.0 && interval.high < 6.0);
+        assert_eq!(bootstrap_mean(&samples, 0.95, 1000, 0), Some(interval));
+        // A wider interval holds the narrower one.
+        let narrow = bootstrap_mean(&samples, 0.5, 1000, 0).unwrap();
+        assert!(interval.contains(narrow.low) && interval.contains(narrow.high));
+        let shifted = samples.map(|sample| sample + 3.0);
+        let shifted = bootstrap_mean(&shifted, 0.95, 1000, 0).unwrap();
+        assert!(!interval.overlaps(&shifted));
+    }
+
+    #[test]
+    fn test_human_like_strategy() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let secret = Word::try_from("cog").unwrap();
+        let baseline = eval::human_baseline(&secret, &word_list, 6, 0.2, 10);
+        assert!(baseline >= 1.0);
+        assert_eq!(
+            baseline,
+            eval::human_baseline(&secret, &word_list, 6, 0.2, 10)
+        );
+
+        // Rates outside 0 to 1 are clamped rather than panicking.
+        for mistake_rate in [-1.0, 2.0, f64::NAN] {
+            let strategy = strategy::HumanLikeStrategy::new(word_list.clone(), mistake_rate, 0);
+            assert!(run_game(
+                engine::StandardEngine::new(secret, word_list.clone(), 6),
+                strategy
+            ));
+        }
+    }
+
+    #[test]
+    fn test_share_grid() {
+        let mut board = board::Board::new();
+        board.push(
+            Word::try_from("xax").unwrap(),
+            [Wrong, RightLetter, Wrong],
+            None,
+        );
+        board.push(
+            Word::try_from("axd").unwrap(),
+            [RightPlace, Wrong, RightPlace],
+            None,
+        );
+        assert_eq!(
+            play::share_grid(&board, true, 6, 2, tiles::TileStyle::Color),
+            "Wordle 3/6 (2 hints)\n\n⬛🟨⬛\n🟩⬛🟩\n🟩🟩🟩"
+        );
+        assert_eq!(
+            play::share_grid::<3>(&board::Board::new(), false, 6, 0, tiles::TileStyle::Color),
+            "Wordle X/6\n"
+        );
+        assert_eq!(
+            play::share_grid(&board, false, 2, 0, tiles::TileStyle::HighContrast),
+            "Wordle X/2\n\n⬛🟦⬛\n🟧⬛🟧"
+        );
+        let symbols = play::share_grid(&board, true, 6, 0, tiles::TileStyle::Symbols);
+        assert_eq!(
+            symbols,
+            "Wordle 3/6\n\n.#. (#) .#.\n[#] .#. [#]\n[#] [#] [#]"
+        );
+        assert_eq!(league::parse_share_grid(&symbols).unwrap().guesses, Some(3));
+        assert_eq!(
+            tiles::TileStyle::Symbols.row(
+                &Word::try_from("axd").unwrap(),
+                &[RightPlace, RightLetter, Wrong]
+            ),
+            "[A] (X) .D."
+        );
+        assert_eq!("high-contrast".parse(), Ok(tiles::TileStyle::HighContrast));
+
+        let record = record::GameRecord {
+            date: None,
+            puzzle: None,
+            max_guesses: 2,
+            board: board.clone(),
+            think_times: Vec::new(),
+        };
+        let text = record.share_grid(tiles::TileStyle::Text);
+        assert_eq!(text, "Wordle X/2\n\n-+-\n=-=");
+        assert_eq!(league::parse_share_grid(&text).unwrap().guesses, None);
+
+        let transcript = engine::GameTranscript {
+            turns: vec![
+                engine::TranscriptTurn {
+                    guess: Word::try_from("xax").unwrap(),
+                    result: engine::GuessResult::Continue([Wrong, RightLetter, Wrong]),
+                },
+                engine::TranscriptTurn {
+                    guess: Word::try_from("add").unwrap(),
+                    result: engine::GuessResult::Done {
+                        won: true,
+                        score: Some([RightPlace; 3]),
+                    },
+                },
+            ],
+            won: true,
+            gave_up: None,
+        };
+        let shared = transcript.share_grid(Some(1210), 6, tiles::TileStyle::Color);
+        assert_eq!(shared, "Wordle 1,210 2/6\n\n⬛🟨⬛\n🟩🟩🟩");
+        assert_eq!(
+            league::parse_share_grid(&shared).unwrap().puzzle,
+            Some(1210)
+        );
+        assert_eq!(
+            tiles::TileStyle::parse_tiles("⬛\u{fe0f}🟨⬜\u{fe0f}"),
+            Some(vec![Wrong, RightLetter, Wrong])
+        );
+        assert_eq!(
+            tiles::TileStyle::Text.row(
+                &Word::try_from("axd").unwrap(),
+                &[RightPlace, RightLetter, Wrong]
+            ),
+            "=+- axd"
+        );
+
+        let keyboard = board.keyboard();
+        assert_eq!(keyboard[&'x'], Wrong);
+        assert_eq!(keyboard[&'a'], RightPlace);
+        assert_eq!(keyboard[&'d'], RightPlace);
+        assert!(board.is_consistent(&Word::try_from("abd").unwrap()));
+        assert!(!board.is_consistent(&Word::try_from("bad").unwrap()));
+    }
+
+    #[test]
+    fn test_adversarial_score() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let guess = Word::try_from("cat").unwrap();
+        assert_eq!(word_list.partition_sizes(&guess).values().sum::<usize>(), 5);
+        let (score, remaining) = word_list.adversarial_score(&guess).unwrap();
+        assert_eq!(score, [Wrong, RightPlace, RightPlace]);
+        assert_eq!(remaining.0.len(), 2);
+        assert!(WordList::<3>(Vec::new())
+            .adversarial_score(&guess)
+            .is_none());
+    }
+
+    #[test]
+    fn test_simple_strategy_approaches_oracle() {
+        let alphabet = ['a', 'b', 'c', 'd', 'e'];
+
+        let words = oracle::all_words::<2>(&alphabet);
+        assert_eq!(words.0.len(), 25);
+        assert!(oracle::all_words::<2>(&[]).0.is_empty());
+        let optimal = oracle::Oracle::new(words.clone()).optimal_average_guesses(&words);
+        let simple = eval::evaluate(
+            || strategy::SimpleStrategy::new(words.clone()),
+            &words,
+            &words,
+            25,
+        );
+        assert_eq!(simple.wins, 25);
+        assert!(simple.average_guesses().unwrap() >= optimal);
+        assert!(simple.average_guesses().unwrap() <= optimal + 1.0);
+
+        let words = oracle::all_words::<3>(&alphabet);
+        let answers: WordList<3> = words.0.iter().step_by(4).copied().collect();
+        let optimal = oracle::Oracle::new(words.clone()).optimal_average_guesses(&answers);
+        let simple = eval::evaluate(
+            || strategy::SimpleStrategy::with_guess_list(words.clone(), answers.clone()),
+            &answers,
+            &words,
+            125,
+        );
+        assert_eq!(simple.wins, answers.0.len());
+        assert!(simple.average_guesses().unwrap() >= optimal);
+        assert!(simple.average_guesses().unwrap() <= optimal + 1.0);
+    }
+
+    #[test]
+    fn test_optimal_strategy() {
+        use optimal::OptimalStrategy;
+        let alphabet = ['a', 'b', 'c', 'd', 'e'];
+        let words = oracle::all_words::<2>(&alphabet);
+        let optimal = oracle::Oracle::new(words.clone()).optimal_average_guesses(&words);
+        // Every word fits in the search, so it plays the true optimum.
+        let searched = eval::evaluate(
+            || OptimalStrategy::new(words.clone()).with_shortlist(25),
+            &words,
+            &words,
+            25,
+        );
+        assert_eq!(searched.wins, 25);
+        assert_eq!(searched.average_guesses(), Some(optimal));
+
+        // With a small cutoff, the first guesses come from the fallback.
+        let mut strategy = OptimalStrategy::new(words.clone()).with_max_exact(5);
+        strategy.make_guess().unwrap();
+        assert!(!strategy.last_guess_exact());
+        let mut strategy = OptimalStrategy::new(words).with_max_exact(5);
+        strategy.prime(&[Clue::Scored(
+            Word::try_from("ca").unwrap(),
+            [RightPlace, Wrong],
+        )]);
+        strategy.make_guess().unwrap();
+        assert!(strategy.last_guess_exact());
+    }
+
+    #[test]
+    fn test_word_policies() {
+        use policy::*;
+        let word_list: WordList<5> = ["cards", "class", "focus", "baked", "greed", "crane"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let policy = AllOf(vec![
+            Box::new(ExcludePlurals),
+            Box::new(ExcludePastTense),
+            Box::new(ExcludeTagged(
+                std::iter::once(Word::try_from("crane").unwrap()).collect(),
+            )),
+        ]);
+        let allowed = word_list
+            .filtered(&policy)
+            .0
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        assert_eq!(allowed, ["class", "focus", "greed"]);
+        assert_eq!("past-tense".parse(), Ok(Exclusion::PastTense));
+        assert!("nouns".parse::<Exclusion>().is_err());
+
+        // Once the solver takes over, it never proposes an excluded word as
+        // the answer.
+        let word_list: WordList<4> = ["bats", "bath", "bate", "mist"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let word = |s: &str| Word::<4>::try_from(s).unwrap();
+        let solve = |exclude: Option<Exclusion>| {
+            let mut guesser = strategy::StdinThenSolver::new(word_list.clone());
+            if let Some(exclusion) = exclude {
+                guesser.set_answer_policy(&exclusion);
+            }
+            guesser.prime(&[Clue::Scored(
+                word("bate"),
+                word("bats").evaluate_guess(&word("bate")),
+            )]);
+            guesser.start_solver().unwrap();
+            guesser.make_guess()
+        };
+        // Without the policy, it has to probe to tell "bats" from "bath".
+        assert_eq!(solve(None), Ok(word("mist")));
+        assert_eq!(solve(Some(Exclusion::Plurals)), Ok(word("bath")));
+    }
+
+    #[test]
+    fn test_parse_guess_line() {
+        use strategy::{parse_guess_line, ParsedGuess};
+        let crane = Word::try_from("crane").unwrap();
+        assert_eq!(parse_guess_line::<5>("crane\n"), ParsedGuess::Clean(crane));
+        assert_eq!(
+            parse_guess_line::<5>("  CRANE 🟩⬛⬛🟨⬛\n"),
+            ParsedGuess::Clean(crane)
+        );
+        assert_eq!(
+            parse_guess_line::<4>("can't"),
+            ParsedGuess::Stripped(Word::try_from("cant").unwrap())
+        );
+        assert_eq!(parse_guess_line::<5>("crane slate"), ParsedGuess::Invalid);
+        assert_eq!(parse_guess_line::<5>("cranes"), ParsedGuess::Invalid);
+        assert_eq!(parse_guess_line::<5>("🟩🟩🟩🟩🟩"), ParsedGuess::Invalid);
+    }
+
+    #[test]
+    fn test_archive_summary() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let won = record::GameRecord::<3>::parse(
+            "date: 2022-01-15\npuzzle: 210\nguesses: 6\nbat bgg\ncat ggg\n",
+        )
+        .unwrap();
+        assert_eq!(record::GameRecord::parse(&won.to_string()), Ok(won.clone()));
+        let lost =
+            record::GameRecord::<3>::parse("date: 2022-02-01\nguesses: 1\nbat bbb\n").unwrap();
+        assert_eq!(
+            record::GameRecord::<3>::parse("bat bgg\ncat gg\n")
+                .unwrap_err()
+                .line,
+            2
+        );
+
+        let summary = analysis::ArchiveSummary::from_records(&[won, lost], &word_list);
+        assert_eq!(summary.overall.games, 2);
+        assert_eq!(summary.overall.wins, 1);
+        assert_eq!(summary.overall.average_guesses(), Some(2.0));
+        assert_eq!(summary.months.len(), 2);
+        assert_eq!(summary.openers, [("bat".to_owned(), 2)]);
+
+        // An empty archive has nothing to average rather than NaNs.
+        let empty = analysis::ArchiveSummary::from_records(&[], &word_list);
+        assert_eq!(empty.overall.win_rate(), None);
+        assert_eq!(empty.overall.average_guesses(), None);
+        assert_eq!(empty.overall.average_bits_per_guess(), None);
+        let text = empty.to_string();
+        assert!(
+            text.starts_with("overall      0 games     n/a won   n/a guesses   n/a bits/guess\n"),
+            "{}",
+            text
+        );
+        assert!(!text.contains("NaN"));
+    }
+
+    #[test]
+    fn test_renderers() {
+        use display::{AnsiRenderer, CsvRenderer, PlainRenderer, Renderer};
+        let mut board = board::Board::<5>::new();
+        board.push(
+            Word::try_from("crane").unwrap(),
+            parse_score("bybbg").unwrap(),
+            None,
+        );
+        board.push(
+            Word::try_from("spilt").unwrap(),
+            parse_score("ggggg").unwrap(),
+            None,
+        );
+        assert_eq!(
+            PlainRenderer(tiles::TileStyle::Text).render(&board),
+            "-+--= crane\n===== spilt\n"
+        );
+        assert_eq!(
+            CsvRenderer.render(&board),
+            "guess,score\ncrane,bybbg\nspilt,ggggg\n"
+        );
+        let ansi = AnsiRenderer::default().render(&board);
+        assert_eq!(ansi.lines().count(), 2);
+        assert!(ansi.starts_with("\x1b[1;97;100m C \x1b[0m \x1b[1;97;43m R \x1b[0m"));
+        let high_contrast = AnsiRenderer {
+            high_contrast: true,
+        };
+        assert!(high_contrast
+            .render(&board)
+            .contains("\x1b[1;97;48;5;208m E "));
+    }
+
+    #[test]
+    fn test_doctor() {
+        use std::path::Path;
+        let (check, words) = doctor::bundled_list("list", &["crane", "spilt"]);
+        assert_eq!(check.result, Ok("2 words".to_owned()));
+        assert_eq!(words.unwrap().0.len(), 2);
+        let (check, words) = doctor::bundled_list("list", &["crane", "spilt", "crane"]);
+        assert_eq!(check.result, Err("crane is listed twice".to_owned()));
+        assert!(words.is_none());
+        let (check, _) = doctor::bundled_list("list", &["crane", "cranes"]);
+        assert_eq!(check.result, Err("bad word \"cranes\"".to_owned()));
+        assert!(doctor::scoring().result.is_ok());
+
+        // Files that were never written are fine; ones named outright aren't.
+        let missing = Path::new("no/such/file");
+        let report = doctor::run(Some(missing), Some(missing), &[missing]);
+        assert_eq!(report.0.len(), 7);
+        assert!(report.0[..6].iter().all(|check| check.result.is_ok()));
+        assert!(!report.passed());
+        assert!(report.to_string().ends_with("1 of 7 checks failed."));
+    }
+
+    #[test]
+    fn test_import_csv() {
+        use import::{detect_format, import_csv, TrackerFormat};
+        let games = import_csv::<5>(
+            "Date,Wordle #,Answer,Guesses,Result\n\
+             2022-01-15,#210,spilt,CRANE|spilt,won\n\
+             2022-01-16,211,\"hasty\",\"crane slate tapas\",lost\n",
+        )
+        .unwrap();
+        assert_eq!(games.len(), 2);
+        assert_eq!(
+            games[0].to_string(),
+            record::GameRecord::<5>::parse(
+                "date: 2022-01-15\npuzzle: 210\ncrane bbbbb\nspilt ggggg\n"
+            )
+            .unwrap()
+            .to_string()
+        );
+        assert!(!games[1].won());
+        assert_eq!(games[1].board.len(), 3);
+
+        let guesses = import_csv::<5>(
+            "date,puzzle,guess,score\n\
+             2022-01-15,210,crane,bbbbb\n\
+             2022-01-15,210,spilt,🟩🟩🟩🟩🟩\n\
+             2022-01-16,211,crane,bybbg\n\
+             2022-01-17,211,crane,ggggg\n",
+        )
+        .unwrap();
+        assert_eq!(guesses.len(), 3);
+        assert_eq!(guesses[0], games[0]);
+        assert_eq!(guesses[1].board.len(), 1);
+        // A solved game ends the game, even with the same date and puzzle.
+        let replayed = import_csv::<5>(
+            "date,puzzle,guess,score\n2022-01-15,210,spilt,ggggg\n2022-01-15,210,spilt,ggggg\n",
+        )
+        .unwrap();
+        assert_eq!(replayed.len(), 2);
+
+        assert_eq!(
+            detect_format("Day,Solution,Attempts"),
+            Some(TrackerFormat::Games)
+        );
+        assert_eq!(detect_format("guess,pattern"), Some(TrackerFormat::Guesses));
+        assert_eq!(detect_format("date,puzzle"), None);
+        let error = import_csv::<5>("date,guess,score\n2022-01-15,crane,bbbbx\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: bad score \"bbbbx\"");
+        assert_eq!(import_csv::<5>("date,puzzle\n").unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let words = |words: &[&str]| -> WordList<3> {
+            words.iter().map(|s| Word::try_from(*s).unwrap()).collect()
+        };
+        // Pinned, since saved artifacts rely on the hash never changing.
+        assert_eq!(words(&[]).content_hash(), 0xcbf29ce484222325);
+        assert_eq!(
+            words(&["bat", "cat"]).content_hash(),
+            words(&["bat", "cat"]).content_hash()
+        );
+        assert_ne!(
+            words(&["bat", "cat"]).content_hash(),
+            words(&["cat", "bat"]).content_hash()
+        );
+    }
+
+    #[test]
+    fn test_hard_mode_strategy() {
+        use hard_mode::HardMode;
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let mut replaced = 0;
+        for secret in &word_list.0 {
+            // A refused guess would panic, since neither strategy retries.
+            let engine = engine::StandardEngine::new(*secret, word_list.clone(), 6).hard_mode();
+            let mut simple = HardMode::new(
+                strategy::SimpleStrategy::new(word_list.clone()),
+                word_list.clone(),
+            );
+            assert!(run_game(engine, &mut simple), "lost on {}", secret);
+            replaced += simple.replaced();
+
+            let engine = engine::StandardEngine::new(*secret, word_list.clone(), 6).hard_mode();
+            let entropy = HardMode::new(
+                strategy::EntropyStrategy::new(word_list.clone()),
+                word_list.clone(),
+            );
+            assert!(run_game(engine, entropy), "lost on {}", secret);
+        }
+        // The simple strategy likes to probe, so some guesses were swapped.
+        assert!(replaced > 0);
+    }
+
+    #[test]
+    fn test_strategy_info() {
+        use hard_mode::HardMode;
+        use strategy::Solver;
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        // Every solver that claims hard mode follows HardMode's swaps.
+        for solver in [
+            Solver::Simple,
+            Solver::Adaptive,
+            Solver::Entropy,
+            Solver::Optimal,
+        ] {
+            assert!(solver.info().supports_hard_mode, "{}", solver);
+            for secret in &word_list.0 {
+                let engine = engine::StandardEngine::new(*secret, word_list.clone(), 6).hard_mode();
+                let strategy = HardMode::new(
+                    solver.build(word_list.clone(), Default::default()),
+                    word_list.clone(),
+                );
+                assert!(run_game(engine, strategy), "{} lost on {}", solver, secret);
+            }
+        }
+        assert!(Solver::Simple.info().approximate);
+        assert!(!Solver::Optimal.info().approximate);
+        let human = strategy::HumanLikeStrategy::<3>::INFO;
+        assert!(!human.supports_hard_mode && !human.deterministic);
+    }
+
+    #[test]
+    fn test_merge_word_lists() {
+        use word::{MergePolicy, Precedence};
+        let words = |words: &[&str]| -> WordList<3> {
+            words.iter().map(|s| Word::try_from(*s).unwrap()).collect()
+        };
+        let official = words(&["bat", "cat", "hat"]);
+        let personal = words(&["Zap", "hat", "cAt"]);
+        let merged = |policy| {
+            WordList::merge(&[official.clone(), personal.clone()], policy)
+                .0
+                .iter()
+                .map(|word| word.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(merged(MergePolicy::default()), ["bat", "cat", "hat", "zap"]);
+        assert_eq!(
+            merged(MergePolicy {
+                fold_case: true,
+                precedence: Precedence::Later,
+            }),
+            ["bat", "zap", "hat", "cat"]
+        );
+        assert_eq!(
+            merged(MergePolicy {
+                fold_case: false,
+                precedence: Precedence::Earlier,
+            }),
+            ["bat", "cat", "hat", "Zap", "cAt"]
+        );
+    }
+
+    #[test]
+    fn test_decision_tree() {
+        use decision_tree::{DecisionTree, DecisionTreeStrategy};
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let tree = DecisionTree::build(&word_list, &word_list, &advice::Advisor::Entropy);
+        let tree = std::sync::Arc::new(tree);
+        let summary = eval::evaluate(
+            || DecisionTreeStrategy::new(tree.clone(), word_list.clone()),
+            &word_list,
+            &word_list,
+            tree.depth(),
+        );
+        assert_eq!(summary.wins, 8);
+        assert_eq!(
+            Some(summary.guesses_in_wins),
+            tree.total_guesses(&word_list)
+        );
+        assert_eq!(tree.guess_after(&[]), Some(tree.guess));
+
+        let mut book = Vec::new();
+        tree.write(&mut book, &word_list).unwrap();
+        assert!(book.starts_with(b"WSLV 1 opening-book 3 "));
+        assert_eq!(
+            DecisionTree::read(book.as_slice(), &word_list).unwrap(),
+            *tree
+        );
+        let text = String::from_utf8(book).unwrap();
+        let (header, _) = text.split_once('\n').unwrap();
+        assert!(matches!(
+            DecisionTree::<3>::read(format!("{}\ncat\n    bgg hat\n", header).as_bytes(), &word_list),
+            Err(artifact::ArtifactError::Malformed(message)) if message == "line 3: bad indentation"
+        ));
+
+        // Secrets the tree wasn't built for are played off book.
+        let answers = WordList(word_list.0[..4].to_vec());
+        let tree = std::sync::Arc::new(DecisionTree::build(
+            &word_list,
+            &answers,
+            &advice::Advisor::Entropy,
+        ));
+        assert_eq!(tree.guesses_for(&Word::try_from("hog").unwrap()), None);
+        assert!(tree.total_guesses(&answers).is_some());
+        let mut strategy = DecisionTreeStrategy::new(tree, word_list.clone());
+        let engine = engine::StandardEngine::new(Word::try_from("hog").unwrap(), word_list, 8);
+        assert!(run_game(&engine, &mut strategy));
+        assert!(strategy.off_book());
+    }
+
+    #[test]
+    fn test_transcript_round_trip() {
+        use artifact::ArtifactError;
+        let word_list: WordList<3> = ["bat", "cat", "hat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let records = [
+            record::GameRecord::<3>::parse("puzzle: 1\nbat bgg\ncat ggg\n").unwrap(),
+            record::GameRecord::<3>::parse("guesses: 1\nhat bgg\n").unwrap(),
+        ];
+        let mut transcript = Vec::new();
+        record::write_transcript(&mut transcript, &records, &word_list).unwrap();
+        assert!(transcript.starts_with(b"WSLV 1 transcript 3 "));
+        assert_eq!(
+            record::read_transcript(transcript.as_slice(), &word_list).unwrap(),
+            records
+        );
+
+        let other_list: WordList<3> = ["bat", "cat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        assert!(matches!(
+            record::read_transcript(transcript.as_slice(), &other_list),
+            Err(ArtifactError::WordListMismatch { .. })
+        ));
+        assert!(matches!(
+            record::read_transcript::<5>(transcript.as_slice(), &WordList(Vec::new())),
+            Err(ArtifactError::WrongWordLength {
+                expected: 5,
+                found: 3
+            })
+        ));
+        assert!(matches!(
+            record::read_transcript(&b"bat bgg\n"[..], &word_list),
+            Err(ArtifactError::NotAnArtifact)
+        ));
+        let future = String::from_utf8(transcript)
+            .unwrap()
+            .replacen(" 1 ", " 2 ", 1);
+        assert!(matches!(
+            record::read_transcript(future.as_bytes(), &word_list),
+            Err(ArtifactError::UnsupportedVersion(2))
+        ));
+    }
+
+    #[test]
+    fn test_played_puzzles() {
+        let mut played = history::PlayedPuzzles::parse("210 4\n211 X\n").unwrap();
+        assert_eq!(played.result(210), Some(Some(4)));
+        assert_eq!(played.result(211), Some(None));
+        assert_eq!(played.result(212), None);
+        played.record(212, Some(3));
+        // A replay doesn't overwrite the first result.
+        played.record(210, Some(2));
+        assert_eq!(played.result(210), Some(Some(4)));
+        assert_eq!(
+            history::PlayedPuzzles::parse(&played.to_string()).unwrap(),
+            played
+        );
+        assert!(played.stats().starts_with("Played 3, won 2 (67%)\n"));
+        assert!(history::PlayedPuzzles::parse("210 four\n").is_err());
+    }
+
+    #[test]
+    fn test_schedule_engine() {
+        use schedule::{Schedule, ScheduleEngine};
+        let word_list: WordList<3> = ["bat", "cat", "hat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let schedule =
+            Schedule::parse("# league\n2022-01-15 cat\n\n2022-01-16 HAT\n", &word_list).unwrap();
+        let date = |s: &str| s.parse::<nyt::Date>().unwrap();
+        assert_eq!(
+            schedule.answer(date("2022-01-16")),
+            Word::try_from("hat").ok()
+        );
+        assert_eq!(schedule.last_date(), Some(date("2022-01-16")));
+        assert!(ScheduleEngine::new(&schedule, date("2022-01-17"), word_list.clone(), 6).is_none());
+
+        let engine =
+            ScheduleEngine::new(&schedule, date("2022-01-15"), word_list.clone(), 6).unwrap();
+        assert!(matches!(
+            engine.score_guess(&Word::try_from("cat").unwrap()),
+            GuessResult::Done { won: true, .. }
+        ));
+
+        let error = Schedule::parse("2022-01-15 cat\n2022-01-16 dog\n", &word_list).unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            Schedule::parse("2022-01-15 cat\n2022-01-15 bat\n", &word_list)
+                .unwrap_err()
+                .line,
+            2
+        );
+        assert!(Schedule::parse("2022-02-30 cat\n", &word_list).is_err());
+    }
+
+    #[test]
+    fn test_league() {
+        use league::*;
+        let nyt =
+            parse_share_grid("Wordle 1,210 3/6*\n\n⬛🟨⬛⬛⬛\n🟩⬛🟩⬛🟨\n🟩🟩🟩🟩🟩\n").unwrap();
+        assert_eq!(
+            nyt,
+            SharedResult {
+                puzzle: Some(1210),
+                guesses: Some(3),
+                max_guesses: 6
+            }
+        );
+        let ours = parse_share_grid("Wordle X/2 (1 hint)\n\n⬛⬛⬛⬛⬛\n🟩⬛🟩⬛🟨\n").unwrap();
+        assert_eq!(ours.puzzle, None);
+        assert_eq!(ours.guesses, None);
+        assert!(parse_share_grid("Wordle 210 2/6\n\n🟩🟩🟩🟩🟩\n").is_err());
+        assert!(parse_share_grid("Wordle 210 1/6\n\n🟩⬛🟩🟩🟩\n").is_err());
+        assert_eq!(parse_time("1:35"), Some(95));
+        assert_eq!(parse_time("1:75"), None);
+
+        let mut league = League::new();
+        league.record(
+            210,
+            "alice",
+            Entry {
+                guesses: Some(3),
+                seconds: Some(95),
+            },
+        );
+        league.record(
+            210,
+            "bob",
+            Entry {
+                guesses: None,
+                seconds: None,
+            },
+        );
+        league.record(
+            211,
+            "bob",
+            Entry {
+                guesses: Some(2),
+                seconds: None,
+            },
+        );
+        league.record(
+            211,
+            "carol smith",
+            Entry {
+                guesses: Some(3),
+                seconds: Some(60),
+            },
+        );
+        let standings = league.standings();
+        let order = standings
+            .iter()
+            .map(|s| s.player.as_str())
+            .collect::<Vec<_>>();
+        // Alice and Carol tie on points; Carol was quicker.
+        assert_eq!(order, ["bob", "carol smith", "alice"]);
+        assert_eq!(standings[0].points, 5);
+        assert_eq!(standings[0].wins, 1);
+        assert_eq!(League::parse(&league.to_string()).unwrap(), league);
+        assert!(league.standings_table().contains("alice"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_core_types() {
+        use record::GameRecord;
+        let word = Word::<5>::try_from("crane").unwrap();
+        assert_eq!(serde_json::to_string(&word).unwrap(), r#""crane""#);
+        assert_eq!(serde_json::from_str::<Word<5>>(r#""crane""#).unwrap(), word);
+        assert!(serde_json::from_str::<Word<5>>(r#""cranes""#).is_err());
+
+        let word_list: WordList<3> = ["bat", "cat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let json = serde_json::to_string(&word_list).unwrap();
+        assert_eq!(json, r#"["bat","cat"]"#);
+        assert_eq!(
+            serde_json::from_str::<WordList<3>>(&json).unwrap().0,
+            word_list.0
+        );
+
+        let record = GameRecord::<5>::parse("puzzle: 210\ncrane bybbg\nspilt ggggg\n").unwrap();
+        let json = serde_json::to_string(&vec![record.clone()]).unwrap();
+        assert_eq!(
+            json,
+            r#"[{"puzzle":210,"max_guesses":6,"board":[{"guess":"crane","score":"bybbg"},{"guess":"spilt","score":"ggggg"}]}]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<GameRecord<5>>>(&json).unwrap(),
+            [record]
+        );
+        assert!(
+            serde_json::from_str::<Vec<GameRecord<5>>>(&json.replace("bybbg", "bybbx")).is_err()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_schemas() {
+        use schema::Schema;
+        let validator = |schema: Schema| {
+            let document = serde_json::from_str(schema.text()).unwrap();
+            jsonschema::validator_for(&document).unwrap()
+        };
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+
+        let suggestion = trace::CandidateStats::new(Word::try_from("cat").unwrap(), &word_list);
+        let json = serde_json::to_value(suggestion).unwrap();
+        assert!(validator(Schema::Suggestion).is_valid(&json));
+
+        let record = record::GameRecord::<5>::parse(
+            "date: 2022-01-15\npuzzle: 210\ncrane bybbg 4.2s\nspilt ggggg 3.0s\n",
+        )
+        .unwrap();
+        let json = serde_json::to_value(&record).unwrap();
+        assert!(validator(Schema::GameRecord).is_valid(&json));
+        let mut broken = json.clone();
+        broken["board"][0]["score"] = "bybbx".into();
+        assert!(!validator(Schema::GameRecord).is_valid(&broken));
+
+        let report = bench::run_benchmark(
+            || strategy::SimpleStrategy::new(word_list.clone()),
+            &word_list,
+        );
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(validator(Schema::Benchmark).is_valid(&json));
+        assert_eq!(
+            serde_json::from_value::<bench::BenchmarkReport>(json).unwrap(),
+            report
+        );
+
+        let tree =
+            decision_tree::DecisionTree::build(&word_list, &word_list, &advice::Advisor::Entropy);
+        let json = serde_json::to_value(&tree).unwrap();
+        assert!(validator(Schema::DecisionTree).is_valid(&json));
+        assert_eq!(
+            serde_json::from_value::<decision_tree::DecisionTree<3>>(json).unwrap(),
+            tree
+        );
+        let unknown = serde_json::json!({ "guess": "cat", "replies": {}, "depth": 1 });
+        assert!(!validator(Schema::DecisionTree).is_valid(&unknown));
+
+        for schema in Schema::ALL {
+            assert_eq!(schema.to_string().parse(), Ok(schema));
+            assert!(schema.text().contains(&format!(":v{}:", schema::VERSION)));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_run_manifest() {
+        use manifest::{ManifestError, RunManifest};
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog", "bog", "hog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let parameters = vec![("mistake_rate".to_owned(), "0.5".to_owned())];
+        let mut manifest = RunManifest::new(
+            "human",
+            parameters.into_iter().collect(),
+            &word_list,
+            5,
+            6,
+            7,
+        );
+        let results = manifest.run(&word_list).unwrap();
+        assert_eq!(results.games, 5);
+        manifest.results = Some(results);
+
+        let reloaded = RunManifest::from_json(&manifest.to_json()).unwrap();
+        assert_eq!(reloaded, manifest);
+        assert_eq!(reloaded.run(&word_list).unwrap(), results);
+
+        let mut noisy = manifest.clone();
+        noisy.human_errors.score_error = 0.3;
+        let reloaded = RunManifest::from_json(&noisy.to_json()).unwrap();
+        assert_eq!(reloaded.human_errors, noisy.human_errors);
+        assert_eq!(
+            reloaded.run(&word_list).unwrap(),
+            noisy.run(&word_list).unwrap()
+        );
+
+        let other_list = WordList(word_list.0[1..].to_vec());
+        assert!(matches!(
+            manifest.run(&other_list),
+            Err(ManifestError::WordListMismatch { .. })
+        ));
+        manifest
+            .parameters
+            .insert("speed".to_owned(), "11".to_owned());
+        assert!(matches!(
+            manifest.run(&word_list),
+            Err(ManifestError::UnknownParameter(name)) if name == "speed"
+        ));
+        manifest.strategy = "psychic".to_owned();
+        assert!(matches!(
+            manifest.run(&word_list),
+            Err(ManifestError::UnknownStrategy(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_dictionary_sweep() {
+        use dictionary::{parse_words, Dictionary, SweepReport};
+        let words = parse_words::<3>("# made up\nSOL\nmar\n\nárbol\nmar\npez\nluz\n").unwrap();
+        assert_eq!(words.0.len(), 4);
+        assert!(words.0.contains(&Word::try_from("sol").unwrap()));
+        assert!(parse_words::<5>("sol\nmar\n").is_err());
+        assert!(parse_words::<3>("sol mar\n").is_err());
+
+        let spanish = Dictionary::<3>::new(
+            "es",
+            parse_words("sol\nmar\npez\n").unwrap(),
+            parse_words("luz\nsol\n").unwrap(),
+        );
+        assert_eq!(spanish.allowed.0.len(), 4);
+        let english = Dictionary::new(
+            "en",
+            ["bat", "cat", "cot"]
+                .iter()
+                .map(|s| Word::try_from(*s).unwrap())
+                .collect(),
+            WordList(Vec::new()),
+        );
+        let strategies = ["simple".to_owned(), "entropy".to_owned()];
+        let dictionaries = [spanish, english];
+        let report = dictionary::sweep(&dictionaries, &strategies, 3, 6, 0).unwrap();
+        assert_eq!(report.0.len(), 4);
+        assert!(report.0.iter().all(|result| result.summary.wins == 3));
+        let table = report.to_string();
+        assert_eq!(table.lines().count(), 5);
+        assert!(table.starts_with(
+            "dictionary  strategy  games     won  guesses/win  95% interval\nes          simple        3  100.0%"
+        ));
+        assert!(dictionary::sweep(&dictionaries, &["psychic".to_owned()], 1, 6, 0).is_err());
+
+        let matrix =
+            dictionary::LengthMatrix(vec![(3, report.clone()), (4, SweepReport(Vec::new()))]);
+        assert_eq!(
+            matrix.to_string().lines().collect::<Vec<_>>(),
+            [
+                "strategy       3 letters       4 letters",
+                "simple      2.000 (100%)               -",
+                "entropy     2.000 (100%)               -",
+            ]
+        );
+
+        let extra = dictionary::parse_extra_words::<5>("# clones\nQAJAQ\n\nzizit\n").unwrap();
+        assert_eq!(
+            extra.0,
+            [
+                Word::try_from("qajaq").unwrap(),
+                Word::try_from("zizit").unwrap()
+            ]
+        );
+        let error = dictionary::parse_extra_words::<5>("qajaq\nzizi\ncr4ne\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: \"zizi\" isn't a 5-letter word");
+        assert!(dictionary::parse_extra_words::<5>("cr4ne\n").is_err());
+
+        assert!(Dictionary::bundled("en-answers").is_some());
+        assert!(Dictionary::bundled("klingon").is_none());
+    }
+
+    #[test]
+    fn test_with_word_length() {
+        fn parses<const N: usize>(text: &str) -> bool {
+            Word::<N>::try_from(text).is_ok()
+        }
+        assert_eq!(with_word_length!(4, |N| N), Some(4));
+        assert_eq!(with_word_length!(11, |N| N), Some(11));
+        assert_eq!(with_word_length!(3, |N| N), None);
+        assert_eq!(with_word_length!(12, |N| N), None);
+        assert_eq!(
+            with_word_length!("planet".len(), |N| parses::<N>("planet")),
+            Some(true)
+        );
+        assert!(dispatch::WORD_LENGTHS
+            .clone()
+            .all(|length| with_word_length!(length, |N| N == length) == Some(true)));
+    }
+
+    #[test]
+    fn test_dynamic_words() {
+        use dynamic::*;
+        let word = |text: &str| DynWord::try_from(text).unwrap();
+        assert_eq!(
+            DynWord::from(Word::<5>::try_from("crane").unwrap()),
+            word("crane")
+        );
+        assert_eq!(
+            Word::<5>::try_from(&word("crane")),
+            Ok(Word::try_from("crane").unwrap())
+        );
+        assert_eq!(
+            Word::<5>::try_from(&word("planet")),
+            Err(WordError::Length {
+                expected: 5,
+                found: 6
+            })
+        );
+        assert_eq!(
+            word("planet").evaluate_guess(&word("pellet")),
+            evaluate::<6>("pellet", "planet").to_vec()
+        );
+        assert!(DynWordList::new(6, vec![word("planet"), word("crane")]).is_err());
+
+        let word_list = DynWordList::parse_words(6, "planet\nplanes\nstreet\ncrane\nrocket\n")
+            .unwrap()
+            .unwrap();
+        assert_eq!(word_list.words().len(), 4);
+        assert_eq!(
+            DynWordList::from(word_list.to_list::<6>().unwrap()),
+            word_list
+        );
+        assert!(word_list.to_list::<5>().is_err());
+        assert!(DynWordList::parse_words(12, "").is_none());
+
+        let mut solver = strategy::Solver::Simple.build_dyn(&word_list).unwrap();
+        assert_eq!(solver.word_length(), 6);
+        let transcript = dynamic::play(&word("rocket"), solver.as_mut(), 6).unwrap();
+        assert!(transcript.won);
+        assert_eq!(transcript.turns.last().unwrap().0, word("rocket"));
+    }
+
+    #[test]
+    fn test_word_errors() {
+        assert_eq!(
+            Word::<5>::try_from("cat"),
+            Err(WordError::Length {
+                expected: 5,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Word::<5>::try_from("cat").unwrap_err().to_string(),
+            "expected 5 letters, got 3"
+        );
+        assert_eq!(
+            Word::<4>::try_from("ni\u{f1}o").unwrap().to_string(),
+            "ni\u{f1}o"
+        );
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn test_unicode_words() {
+        // A letter typed with a combining accent is the same single letter
+        // as the precomposed one.
+        let composed = Word::<4>::try_from("ni\u{f1}o").unwrap();
+        assert_eq!(Word::<4>::try_from("nin\u{303}o"), Ok(composed));
+        assert_eq!(
+            Word::<4>::try_from("u\u{308}ber").unwrap().to_string(),
+            "\u{fc}ber"
+        );
+        assert_eq!(
+            Word::<3>::try_from("ab\u{1f1ea}\u{1f1f8}"),
+            Err(WordError::NotALetter("\u{1f1ea}\u{1f1f8}".to_owned()))
+        );
+
+        let words =
+            WordList::<4>::from_reader("NIN\u{303}O\nnin\u{303}a\nni\u{f1}o\n".as_bytes()).unwrap();
+        assert_eq!(words.0.len(), 2);
+        assert_eq!(words.0[0], composed);
+        let guess = words.0[1];
+        assert_eq!(format_score(&composed.evaluate_guess(&guess)), "gggb");
+    }
+
+    #[test]
+    fn test_word_list_loading() {
+        let words = WordList::<3>::from_reader("# pets\nCAT\n\ndog\ncat\n".as_bytes()).unwrap();
+        assert_eq!(
+            words.0,
+            [
+                Word::try_from("cat").unwrap(),
+                Word::try_from("dog").unwrap()
+            ]
+        );
+        let error = WordList::<3>::from_reader("cat\ndog\nmouse\n".as_bytes()).unwrap_err();
+        assert_eq!(error.to_string(), "line 3: \"mouse\" isn't a 3-letter word");
+        assert!(WordList::<3>::from_reader("c4t\n".as_bytes()).is_err());
+        assert!(WordList::<3>::from_reader("# nothing\n".as_bytes()).is_err());
+        assert!(matches!(
+            WordList::<3>::from_path("no/such/words.txt"),
+            Err(dictionary::LoadError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_practice_plan() {
+        use practice::*;
+        let word_list: WordList<3> = [
+            "bat", "cat", "hat", "mat", "rat", "zoo", "dog", "cog", "egg",
+        ]
+        .iter()
+        .map(|s| Word::try_from(*s).unwrap())
+        .collect();
+        let estimator = DifficultyEstimator::new(word_list.clone());
+        assert_eq!(estimator.family_size(&Word::try_from("bat").unwrap()), 4);
+        assert!(estimator.has_weakness(&Word::try_from("egg").unwrap(), Weakness::DoubleLetters));
+
+        let records = [
+            "dog bbb\ncat bgg\nbat bgg\nhat bgg\nmat ggg",
+            "zoo ggg",
+            "dog ggg",
+        ]
+        .iter()
+        .map(|text| record::GameRecord::<3>::parse(text).unwrap())
+        .collect::<Vec<_>>();
+        let weaknesses = find_weaknesses(&records, &estimator);
+        assert_eq!(weaknesses, [Weakness::LargeFamilies]);
+
+        let plan = weekly_plan(&weaknesses, &word_list, &estimator, 7);
+        assert_eq!(plan.len(), 5);
+        assert!(plan
+            .iter()
+            .all(|word| estimator.has_weakness(word, Weakness::LargeFamilies)));
+        assert_eq!(plan, weekly_plan(&weaknesses, &word_list, &estimator, 7));
+    }
+
+    #[test]
+    fn test_candidate_trace() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let stats = trace::CandidateStats::new(Word::try_from("cat").unwrap(), &word_list);
+        assert_eq!(stats.worst_case, 2);
+        assert!(stats.viable);
+        assert!(stats.entropy > 1.5 && stats.entropy < 2.6);
+
+        let mut out = Vec::new();
+        trace::write_candidate_table(&mut out, 1, 6, &[(stats, 4)]).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("turn 1: 6 viable words, top 1 candidates\n"));
+        assert!(out.contains("cat"));
+
+        let word = |text| Word::<5>::try_from(text).unwrap();
+        assert_eq!(word("crane").shared_letters(&word("nacre")), 5);
+        assert_eq!(word("eerie").shared_letters(&word("there")), 3);
+        assert_eq!(word("crane").shared_letters(&word("split")), 0);
+        let ranked = ["crane", "caner", "slate", "react", "least", "doily"]
+            .iter()
+            .enumerate()
+            .map(|(rank, text)| (word(text), rank))
+            .collect::<Vec<_>>();
+        let picks = |count, max_shared| {
+            trace::diverse(ranked.clone(), count, max_shared)
+                .into_iter()
+                .map(|(word, _)| word.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            picks(3, trace::DIVERSE_MAX_SHARED),
+            ["crane", "slate", "doily"]
+        );
+        assert_eq!(picks(3, 5), ["crane", "caner", "slate"]);
+    }
+
+    #[test]
+    fn test_advisor_comparison() {
+        use advice::{Advisor, Comparison};
+        let stats = |word: &str, entropy, worst_case, viable| trace::CandidateStats {
+            word: Word::<3>::try_from(word).unwrap(),
+            entropy,
+            worst_case,
+            viable,
+        };
+        let candidates = [
+            stats("bat", 2.0, 3, false),
+            stats("cat", 1.8, 2, false),
+            stats("hat", 1.8, 2, true),
+        ];
+        let entropy = *Advisor::Entropy.pick(&candidates).unwrap();
+        let minimax = *Advisor::Minimax.pick(&candidates).unwrap();
+        assert_eq!(entropy.word, Word::try_from("bat").unwrap());
+        assert_eq!(minimax.word, Word::try_from("hat").unwrap());
+
+        let comparison = Comparison {
+            picks: [(Advisor::Entropy, entropy), (Advisor::Minimax, minimax)],
+        };
+        assert!(!comparison.agree());
+        let text = comparison.to_string();
+        assert!(text.contains("bat expects 0.20 more bits"));
+        assert!(text.contains("hat leaves 1 fewer words at worst"));
+        assert!(text.contains("only hat could win this turn"));
+
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "dog", "cog"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let comparison =
+            Comparison::new([Advisor::Entropy, Advisor::Minimax], &word_list, &word_list).unwrap();
+        assert!(word_list.0.contains(&comparison.picks[0].1.word));
+        assert!(Comparison::new(
+            [Advisor::Entropy, Advisor::Minimax],
+            &WordList(Vec::new()),
+            &word_list
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_disambiguator() {
+        use advice::Disambiguator;
+        let words = |words: &[&str]| -> WordList<5> {
+            words.iter().map(|s| Word::try_from(*s).unwrap()).collect()
+        };
+        let family = words(&["light", "might", "night", "right"]);
+        let guess_list = words(&[
+            "light", "might", "night", "right", "climb", "lemon", "hotel",
+        ]);
+        let disambiguator = Disambiguator::find(&guess_list, &family).unwrap();
+        assert_eq!(disambiguator.guess, Word::try_from("lemon").unwrap());
+        assert_eq!(disambiguator.letters_covered, 3);
+        assert_eq!(
+            disambiguator.to_string(),
+            "play 'lemon' to split light/might/night/right"
+        );
+
+        // Not a family: the words differ in more than one position.
+        let mixed = words(&["light", "might", "fight", "lemon"]);
+        assert_eq!(Disambiguator::find(&guess_list, &mixed), None);
+        // Two words are quicker to just guess.
+        assert_eq!(
+            Disambiguator::find(&guess_list, &words(&["light", "might"])),
+            None
+        );
+        // Nothing covers more than one telling letter.
+        assert_eq!(
+            Disambiguator::find(&words(&["light", "hotel"]), &family),
+            None
+        );
+    }
+
+    #[test]
+    fn test_families() {
+        use family::{dangerous_family, find_families, positional_variety};
+        let words = |words: &[&str]| -> WordList<5> {
+            words.iter().map(|s| Word::try_from(*s).unwrap()).collect()
+        };
+        let viable = words(&[
+            "light", "might", "night", "right", "sight", "tight", "lemon", "melon", "fight",
+            "fishy", "lever", "lover",
+        ]);
+        assert_eq!(positional_variety(&viable), [7, 3, 5, 3, 4]);
+
+        let families = find_families(&viable, 2);
+        assert_eq!(families.len(), 2);
+        assert_eq!(families[0].position, 0);
+        assert_eq!(families[0].pattern(), "_ight");
+        assert_eq!(families[0].letters(), ['l', 'm', 'n', 'r', 's', 't', 'f']);
+        assert_eq!(families[1].to_string(), "l_ver (lever/lover)");
+
+        let family = dangerous_family(&viable).unwrap();
+        assert_eq!(family.members.len(), 7);
+        // Too small a share of the viable words to worry about yet.
+        let mut crowded = viable.clone();
+        crowded.0.extend(
+            words(&[
+                "crane", "slate", "trace", "adieu", "pious", "hyena", "brown", "jumpy", "quack",
+                "zebra", "vivid", "wheat", "oxide", "plumb", "gecko", "dwarf", "knelt", "chord",
+            ])
+            .0,
+        );
+        assert_eq!(dangerous_family(&crowded), None);
+        assert_eq!(dangerous_family(&words(&["light", "might", "night"])), None);
+    }
+
+    #[test]
+    fn test_consistent_secrets() {
+        use inverse::{consistent_secrets, InverseError};
+        let words = |words: &[&str]| -> Vec<Word<5>> {
+            words.iter().map(|s| Word::try_from(*s).unwrap()).collect()
+        };
+        let word_list = WordList(words(&["crane", "drone", "prone", "krone", "shone"]));
+        let grid = |rows: &[&str]| {
+            rows.iter()
+                .map(|row| tiles::TileStyle::parse_tiles(row).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let played = grid(&["⬛🟩⬛🟩🟩", "⬛🟩🟩🟩🟩", "🟩🟩🟩🟩🟩"]);
+
+        // Without the winning guess, every word fitting the rest will do.
+        assert_eq!(
+            consistent_secrets(&words(&["crane", "drone"]), &played, &word_list)
+                .unwrap()
+                .0,
+            words(&["prone", "krone"])
+        );
+        assert_eq!(
+            consistent_secrets(&words(&["crane", "drone", "prone"]), &played, &word_list)
+                .unwrap()
+                .0,
+            words(&["prone"])
+        );
+        // A row typed in wrong leaves no secret at all.
+        assert!(consistent_secrets(
+            &words(&["crane", "drone", "prone"]),
+            &grid(&["⬛🟩⬛🟩🟨", "⬛🟩🟩🟩🟩", "🟩🟩🟩🟩🟩"]),
+            &word_list
+        )
+        .unwrap()
+        .0
+        .is_empty());
+
+        assert_eq!(
+            consistent_secrets(&words(&["crane"]), &played, &word_list).err(),
+            Some(InverseError::RowCount {
+                guesses: 1,
+                rows: 3
+            })
+        );
+        assert_eq!(
+            consistent_secrets(&words(&["crane"]), &grid(&["⬛🟩⬛🟩"]), &word_list).err(),
+            Some(InverseError::RowLength { row: 1, tiles: 4 })
+        );
+    }
+
+    #[test]
+    fn test_two_guess_witnesses() {
+        let words = |words: &[&str]| -> WordList<5> {
+            words.iter().map(|s| Word::try_from(*s).unwrap()).collect()
+        };
+        let word_list = words(&["crane", "drone", "prone", "krone", "shone", "phone"]);
+        let answer = Word::try_from("prone").unwrap();
+        let witnesses = inverse::two_guess_witnesses(&answer, &word_list, &word_list);
+        assert_eq!(witnesses.len(), 5);
+        assert!(witnesses
+            .windows(2)
+            .all(|pair| pair[0].remaining <= pair[1].remaining));
+        let crane = witnesses
+            .iter()
+            .find(|witness| witness.opener == Word::try_from("crane").unwrap())
+            .unwrap();
+        // Drone, krone and prone all score bgbgg.
+        assert_eq!(crane.remaining, 3);
+        assert_eq!(crane.to_string(), "crane bgbgg: 1 in 3 (33.3%)");
+        assert_eq!(witnesses[0].to_string(), "phone gbggg: 1 in 1 (100.0%)");
+    }
+
+    #[test]
+    fn test_hard_mode_traps() {
+        let words = |words: &[&str]| -> WordList<5> {
+            words.iter().map(|s| Word::try_from(*s).unwrap()).collect()
+        };
+        let word_list = words(&[
+            "fight", "light", "might", "night", "sight", "tight", "wight", "about", "lemon",
+            "flint",
+        ]);
+        let opener = Word::try_from("about").unwrap();
+
+        let traps = hard_mode::traps(&opener, &word_list, &word_list, 6);
+        assert_eq!(traps.len(), 1);
+        assert_eq!(traps[0].score, parse_score("bbbbg").unwrap());
+        assert_eq!(traps[0].entry, Word::try_from("fight").unwrap());
+        assert_eq!(
+            traps[0].to_string(),
+            "bbbbg _ight (fight/light/might/night/sight/tight/wight): fight leaves 6"
+        );
+        // Enough guesses to try every member in turn.
+        assert!(hard_mode::traps(&opener, &word_list, &word_list, 8).is_empty());
+    }
+
+    /// Plays `strategy` against each secret and lists the guesses it made,
+    /// headed by a hash of the word list so a dictionary change shows up as
+    /// its own snapshot diff.
+    fn guess_sequences<S, F>(mut make_strategy: F, word_list: &WordList<5>) -> String
+    where
+        S: strategy::Strategy<5>,
+        F: FnMut() -> S,
+    {
+        struct Recorded<S> {
+            strategy: S,
+            guesses: Vec<String>,
+        }
+
+        impl<S: strategy::Strategy<5>> strategy::Strategy<5> for Recorded<S> {
+            fn make_guess(&mut self) -> Result<Word<5>, StrategyError> {
+                let guess = self.strategy.make_guess()?;
+                self.guesses.push(guess.into());
+                Ok(guess)
+            }
+
+            fn receive_score(&mut self, score: &Score<5>) {
+                self.strategy.receive_score(score)
+            }
+        }
+
+        let hash = word_list.content_hash();
+        let mut out = format!(
+            "word list: {} words, hash {:016x}\n",
+            word_list.0.len(),
+            hash
+        );
+        for secret in ["aband", "brood", "bongs", "bevel", "zoned"] {
+            let secret = Word::try_from(secret).unwrap();
+            let engine = engine::StandardEngine::new(secret, word_list.clone(), 6);
+            let mut recorded = Recorded {
+                strategy: make_strategy(),
+                guesses: Vec::new(),
+            };
+            let won = run_game(engine, &mut recorded);
+            out.push_str(&format!(
+                "{}: {} ({})\n",
+                secret,
+                recorded.guesses.join(" "),
+                if won { "won" } else { "lost" }
+            ));
+        }
+        out
+    }
+
+    fn snapshot_word_list() -> WordList<5> {
+        word_list::WORD_LIST
+            .iter()
+            .step_by(50)
+            .chain(["aband", "brood", "bongs", "bevel", "zoned"].iter())
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn snapshot_simple_strategy() {
+        let word_list = snapshot_word_list();
+        insta::assert_snapshot!(guess_sequences(
+            || strategy::SimpleStrategy::new(word_list.clone()),
+            &word_list
+        ));
+    }
+
+    #[test]
+    fn snapshot_human_like_strategy() {
+        let word_list = snapshot_word_list();
+        insta::assert_snapshot!(guess_sequences(
+            || strategy::HumanLikeStrategy::new(word_list.clone(), 0.2, 0),
+            &word_list
+        ));
+    }
+
+    #[test]
+    fn snapshot_antiwordle_strategy() {
+        let word_list = snapshot_word_list();
+        insta::assert_snapshot!(guess_sequences(
+            || strategy::AntiWordleStrategy::new(word_list.clone()),
+            &word_list
+        ));
+    }
+
+    #[test]
+    fn test_bundled_replies() {
+        let words = |list: &[&str]| -> WordList<5> {
+            list.iter().map(|s| Word::try_from(*s).unwrap()).collect()
+        };
+        let (all, answers) = (words(word_list::WORD_LIST), words(nyt::ANSWERS));
+        let as_text = |replies: Vec<(Score<5>, Word<5>)>| {
+            replies
+                .into_iter()
+                .map(|(score, reply)| (format_score(&score), reply.to_string()))
+                .collect::<Vec<_>>()
+        };
+        let bundled = |replies: &[(&str, &str)]| {
+            replies
+                .iter()
+                .map(|(score, reply)| (score.to_string(), reply.to_string()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            as_text(replies::best_replies(&all, &answers).unwrap()),
+            bundled(replies::NYT_ANSWER_REPLIES)
+        );
+
+        // Past the opener, the solver plays the bundled reply without
+        // scoring anything, and would have worked out the same.
+        let secret = Word::try_from("arise").unwrap();
+        let mut solver = strategy::SimpleStrategy::new(all.clone());
+        let opener = solver.make_guess().unwrap();
+        solver.receive_score(&secret.evaluate_guess(&opener));
+        let reply = solver.make_guess().unwrap();
+        assert_eq!(solver.last_metrics().unwrap().candidates_evaluated, 0);
+        let score = format_score(&secret.evaluate_guess(&opener));
+        assert!(replies::WORD_LIST_REPLIES.contains(&(score.as_str(), reply.to_string().as_str())));
+        let mut thinker = strategy::SimpleStrategy::new(all.clone());
+        thinker.ignore_bundled_replies();
+        thinker.make_guess().unwrap();
+        thinker.receive_score(&secret.evaluate_guess(&opener));
+        assert_eq!(thinker.make_guess().unwrap(), reply);
+
+        // Any other list is worked out as usual.
+        let mut other = strategy::SimpleStrategy::new(WordList(all.0[1..].to_vec()));
+        other.make_guess().unwrap();
+        other.receive_score(&secret.evaluate_guess(&opener));
+        other.make_guess().unwrap();
+        assert!(other.last_metrics().unwrap().candidates_evaluated > 0);
+    }
+
+    #[test]
+    fn test_nyt_answers() {
+        use policy::WordPolicy;
+        assert_eq!(nyt::answer::<5>(0), Word::try_from("cigar").ok());
+        assert_eq!(nyt::answer::<5>(210), Word::try_from("panic").ok());
+        assert_eq!(nyt::answer::<5>(100_000), None);
+        assert!(nyt::ANSWERS
+            .iter()
+            .all(|answer| word_list::WORD_LIST.contains(answer)));
+
+        assert_eq!(nyt::puzzle_date(0).to_string(), "2021-06-19");
+        assert_eq!(nyt::puzzle_date(210).to_string(), "2022-01-15");
+        let date = "2022-01-15".parse().unwrap();
+        assert_eq!(nyt::puzzle_for_date(date), Some(210));
+        assert_eq!(nyt::puzzle_for_date("2021-01-01".parse().unwrap()), None);
+        assert!("2022-02-30".parse::<nyt::Date>().is_err());
+
+        let policy = nyt::ExcludePastAnswers::before(210);
+        assert!(!policy.allows(&Word::<5>::try_from("cigar").unwrap()));
+        assert!(policy.allows(&Word::<5>::try_from("panic").unwrap()));
+    }
+
+    #[test]
+    fn test_reject_repeated_guesses() {
+        let word_list: WordList<3> = ["bat", "cat", "hat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let engine = engine::StandardEngine::new(Word::try_from("hat").unwrap(), word_list, 6)
+            .reject_repeated_guesses();
+        let bat = Word::try_from("bat").unwrap();
+        assert!(matches!(engine.score_guess(&bat), GuessResult::Continue(_)));
+        assert!(matches!(
+            engine.score_guess(&bat),
+            GuessResult::Rejected(engine::GuessError::Repeated(word)) if word == bat
+        ));
+    }
+
+    #[test]
+    fn test_invalid_guess_policy() {
+        use engine::InvalidGuessPolicy;
+        let word_list: WordList<3> = ["bat", "cat", "hat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let word = |s: &str| Word::try_from(s).unwrap();
+        let engine = |policy| {
+            engine::StandardEngine::new(word("hat"), word_list.clone(), 2).on_invalid_guess(policy)
+        };
+
+        let reject = engine(InvalidGuessPolicy::Reject);
+        assert!(matches!(
+            reject.score_guess(&word("zzz")),
+            GuessResult::Rejected(engine::GuessError::NotInWordList(w)) if w == word("zzz")
+        ));
+        assert!(matches!(
+            reject.score_guess(&word("bat")),
+            GuessResult::Continue(_)
+        ));
+        assert!(matches!(
+            reject.score_guess(&word("hat")),
+            GuessResult::Done { won: true, .. }
+        ));
+
+        let consume = engine(InvalidGuessPolicy::ConsumeTurn);
+        assert!(matches!(
+            consume.score_guess(&word("zzz")),
+            GuessResult::Wasted(engine::GuessError::NotInWordList(_))
+        ));
+        assert!(matches!(
+            consume.score_guess(&word("bat")),
+            GuessResult::Done {
+                won: false,
+                score: Some(_)
+            }
+        ));
+
+        let end = engine(InvalidGuessPolicy::EndGame);
+        assert!(matches!(
+            end.score_guess(&word("zzz")),
+            GuessResult::Done {
+                won: false,
+                score: None
+            }
+        ));
+
+        // A strategy that keeps its turn count honest sees the wasted turn.
+        struct Fumbler {
+            guesses: Vec<Word<3>>,
+            wasted: usize,
+        }
+        impl Strategy<3> for Fumbler {
+            fn make_guess(&mut self) -> Result<Word<3>, StrategyError> {
+                Ok(self.guesses.remove(0))
+            }
+            fn receive_score(&mut self, _: &Score<3>) {}
+            fn guess_wasted(
+                &mut self,
+                _: &Word<3>,
+                _: &engine::GuessError<3>,
+            ) -> Result<(), StrategyError> {
+                self.wasted += 1;
+                Ok(())
+            }
+        }
+        let mut fumbler = Fumbler {
+            guesses: vec![word("zzz"), word("hat")],
+            wasted: 0,
+        };
+        assert!(run_game(
+            engine(InvalidGuessPolicy::ConsumeTurn),
+            &mut fumbler
+        ));
+        assert_eq!(fumbler.wasted, 1);
+
+        // The solver keeps the turn a wasted guess used up, unlike a
+        // rejected one.
+        let trace = SharedSink::default();
+        let mut solver = strategy::SimpleStrategy::new(word_list.clone());
+        solver.set_trace(Box::new(trace.clone()));
+        let first = solver.make_guess().unwrap();
+        solver
+            .guess_wasted(&first, &engine::GuessError::NotInWordList(first))
+            .unwrap();
+        assert_ne!(solver.make_guess().unwrap(), first);
+        let turns = trace
+            .contents()
+            .lines()
+            .filter(|line| line.starts_with("turn "))
+            .map(|line| line.split(':').next().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(turns, ["turn 1", "turn 2"]);
+    }
+
+    #[test]
+    fn test_run_game_recorded() {
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let word = |s: &str| Word::<3>::try_from(s).unwrap();
+        struct Scripted(Vec<Word<3>>);
+        impl Strategy<3> for Scripted {
+            fn make_guess(&mut self) -> Result<Word<3>, StrategyError> {
+                Ok(self.0.remove(0))
+            }
+            fn receive_score(&mut self, _: &Score<3>) {}
+            fn guess_rejected(
+                &mut self,
+                _: &Word<3>,
+                _: &engine::GuessError<3>,
+            ) -> Result<(), StrategyError> {
+                Ok(())
+            }
+        }
+        let engine = || engine::StandardEngine::new(word("hat"), word_list.clone(), 3);
+
+        let transcript = run_game_recorded(
+            engine(),
+            Scripted(vec![word("cot"), word("zzz"), word("bat"), word("hat")]),
+        );
+        assert!(transcript.won);
+        assert_eq!(transcript.turns.len(), 4);
+        assert_eq!(
+            transcript.turns[1].result,
+            GuessResult::Rejected(engine::GuessError::NotInWordList(word("zzz")))
+        );
+        assert_eq!(transcript.guesses_used(), 3);
+        let record = transcript.to_record(3);
+        assert_eq!(
+            record.to_string(),
+            "guesses: 3\ncot bbg\nbat bgg\nhat ggg\n"
+        );
+        assert!(record.won());
+
+        // The last guess of a lost game is scored too, so its share grid
+        // has a row for every guess.
+        let transcript = run_game_recorded(
+            engine(),
+            Scripted(vec![word("cot"), word("bat"), word("cat")]),
+        );
+        assert!(!transcript.won);
+        assert_eq!(transcript.guesses_used(), 3);
+        assert_eq!(transcript.scored_guesses().len(), 3);
+        let grid = transcript.share_grid(None, 3, tiles::TileStyle::Color);
+        assert!(grid.starts_with("Wordle X/3"), "{}", grid);
+        assert_eq!(grid.lines().skip(1).filter(|l| !l.is_empty()).count(), 3);
+
+        // One that can't retry a refused guess gives up instead of
+        // panicking, with the refused guess still in the transcript.
+        struct Stubborn(Vec<Word<3>>);
+        impl Strategy<3> for Stubborn {
+            fn make_guess(&mut self) -> Result<Word<3>, StrategyError> {
+                Ok(self.0.remove(0))
+            }
+            fn receive_score(&mut self, _: &Score<3>) {}
+        }
+        let transcript = run_game_recorded(engine(), Stubborn(vec![word("cot"), word("zzz")]));
+        assert_eq!(transcript.turns.len(), 2);
+        assert_eq!(
+            transcript.outcome(),
+            engine::GameOutcome::GaveUp(StrategyError::CantRetry)
+        );
+    }
+
+    #[test]
+    fn test_async_game() {
+        use asynchronous::AsyncEngine;
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::task::{Context, Poll, Waker};
+
+        fn block_on<F: Future>(future: F) -> F::Output {
+            let mut future = std::pin::pin!(future);
+            let mut context = Context::from_waker(Waker::noop());
+            loop {
+                if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                    return output;
+                }
+            }
+        }
+
+        // Not ready the first time it's polled, like a reply still on its way.
+        struct Pending(bool);
+        impl Future for Pending {
+            type Output = ();
+            fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+                if std::mem::replace(&mut self.0, true) {
+                    Poll::Ready(())
+                } else {
+                    context.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            }
+        }
+
+        struct Remote(engine::StandardEngine<3>);
+        impl AsyncEngine<3> for Remote {
+            async fn score(
+                &self,
+                guess: &Word<3>,
+            ) -> Result<engine::GuessResult<3>, error::WordleError> {
+                Pending(false).await;
+                self.0.try_score_guess(guess)
+            }
+        }
+
+        let word_list: WordList<3> = ["bat", "cat", "hat", "mat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let secret = Word::try_from("mat").unwrap();
+        let standard = || engine::StandardEngine::new(secret, word_list.clone(), 6);
+        let expected =
+            try_run_game(standard(), strategy::SimpleStrategy::new(word_list.clone())).unwrap();
+        let remote = block_on(asynchronous::run_game(
+            Remote(standard()),
+            strategy::SimpleStrategy::new(word_list.clone()),
+        ))
+        .unwrap();
+        assert_eq!(remote, expected);
+        let sync = block_on(asynchronous::run_game(
+            standard(),
+            strategy::SimpleStrategy::new(word_list.clone()),
+        ))
+        .unwrap();
+        assert_eq!(sync, expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_protocol() {
+        use error::WordleError;
+        use protocol::{JsonEngine, JsonStrategy};
+        let word_list: WordList<3> = ["bat", "cat", "hat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let word = |s: &str| Word::<3>::try_from(s).unwrap();
+
+        // Another program guessing, with a blank line thrown in.
+        let guesses = "{\"version\":1,\"type\":\"guess\",\"guess\":\"bat\"}\n\n{\"type\":\"guess\",\"guess\":\"hat\",\"version\":1}\n";
+        let mut sent = Vec::new();
+        let engine = engine::StandardEngine::new(word("hat"), word_list.clone(), 6);
+        let transcript =
+            try_run_game(engine, JsonStrategy::new(guesses.as_bytes(), &mut sent)).unwrap();
+        assert!(transcript.won);
+        assert_eq!(transcript.guesses_used(), 2);
+        assert_eq!(
+            String::from_utf8(sent).unwrap().lines().collect::<Vec<_>>(),
+            [
+                r#"{"version":1,"type":"guess"}"#,
+                r#"{"version":1,"type":"score","score":"bgg"}"#,
+                r#"{"version":1,"type":"guess"}"#,
+            ]
+        );
+
+        // Another program scoring.
+        let scores = "{\"version\":1,\"type\":\"rejected\"}\n{\"version\":1,\"type\":\"won\"}\n";
+        let mut sent = Vec::new();
+        let engine = JsonEngine::new(scores.as_bytes(), &mut sent);
+        let transcript =
+            try_run_game(engine, strategy::SimpleStrategy::new(word_list.clone())).unwrap();
+        assert!(transcript.won);
+        assert!(matches!(
+            transcript.turns[0].result,
+            engine::GuessResult::Rejected(_)
+        ));
+        let sent = String::from_utf8(sent).unwrap();
+        assert_eq!(sent.lines().count(), 2);
+        assert!(sent
+            .lines()
+            .all(|line| line.starts_with(r#"{"version":1,"type":"score","guess":""#)));
+
+        let play = |replies: &str| {
+            let engine = JsonEngine::new(replies.as_bytes(), std::io::sink());
+            try_run_game(engine, strategy::SimpleStrategy::new(word_list.clone())).unwrap_err()
+        };
+        let error = play("{\"version\":2,\"type\":\"won\"}\n");
+        assert_eq!(
+            error.to_string(),
+            "bad message: version 2 isn't supported (this build speaks version 1)"
+        );
+        assert!(matches!(
+            play("{\"version\":1,\"type\":\"scored\",\"score\":\"gx\"}\n"),
+            WordleError::Protocol(_)
+        ));
+        assert!(matches!(play("not json\n"), WordleError::Protocol(_)));
+        assert!(matches!(play(""), WordleError::InputClosed));
+    }
+
+    #[test]
+    fn test_wordle_error() {
+        use error::WordleError;
+        let word_list: WordList<3> = ["bat", "cat", "hat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let word = |s: &str| Word::<3>::try_from(s).unwrap();
+
+        let mut guesser = strategy::StdinThenSolver::new(word_list.clone());
+        assert!(guesser.start_solver().is_ok());
+        assert!(matches!(
+            guesser.start_solver(),
+            Err(WordleError::SolverAlreadyStarted)
+        ));
+
+        // Scores worked out in the background are there for the solver once
+        // it takes over.
+        let solve = |warm_up: bool| {
+            let mut guesser = strategy::StdinThenSolver::new(word_list.clone());
+            if warm_up {
+                guesser.warm_up();
+                while !guesser.warmed_up() {
+                    std::thread::sleep(std::time::Duration::from_millis(1));
+                }
+            }
+            assert!(guesser.start_solver().is_ok());
+            let guess = guesser.make_guess().unwrap();
+            (guess, guesser.last_metrics().unwrap().cache_hits)
+        };
+        let (cold, cold_hits) = solve(false);
+        let (warm, warm_hits) = solve(true);
+        assert_eq!(cold, warm);
+        assert_eq!(cold_hits, 0);
+        assert!(warm_hits > 0);
+
+        // The solver picks up after the player's turns instead of opening
+        // again.
+        let mut guesser = strategy::StdinThenSolver::new(word_list.clone());
+        guesser.set_guess_rules(strategy::GuessRules {
+            never_guess: Default::default(),
+            always_open: Some(word("bat")),
+        });
+        guesser.prime(&[Clue::Scored(
+            word("bat"),
+            word("hat").evaluate_guess(&word("bat")),
+        )]);
+        guesser.start_solver().unwrap();
+        assert_ne!(guesser.make_guess().unwrap(), word("bat"));
+
+        // Each of the player's turns counts once, so the solver's first
+        // guess after taking over two turns is the third.
+        let trace = SharedSink::default();
+        let mut guesser = strategy::StdinThenSolver::new(word_list.clone());
+        guesser.set_trace(Box::new(trace.clone()));
+        guesser.prime(&[
+            Clue::Scored(word("cat"), word("hat").evaluate_guess(&word("cat"))),
+            Clue::Scored(word("bat"), word("hat").evaluate_guess(&word("bat"))),
+        ]);
+        guesser.start_solver().unwrap();
+        guesser.make_guess().unwrap();
+        let trace = trace.contents();
+        assert!(trace.starts_with("turn 3: 1 viable words"), "{}", trace);
+
+        // A strategy that runs out of input partway through ends the game
+        // with the error rather than a panic.
+        struct Closing(Vec<Word<3>>);
+        impl Strategy<3> for Closing {
+            fn make_guess(&mut self) -> Result<Word<3>, StrategyError> {
+                if self.0.is_empty() {
+                    Err(WordleError::InputClosed.into())
+                } else {
+                    Ok(self.0.remove(0))
+                }
+            }
+            fn receive_score(&mut self, _: &Score<3>) {}
+        }
+        let engine = engine::StandardEngine::new(word("hat"), word_list.clone(), 6);
+        let error = try_run_game(engine, Closing(vec![word("bat")])).unwrap_err();
+        assert!(matches!(error, WordleError::InputClosed));
+        assert_eq!(error.to_string(), "input ended before the game did");
+
+        // Scores no word fits, as from a typo, leave the solvers nothing to
+        // guess, which they say rather than panicking, and the game is lost.
+        for solver in [
+            strategy::Solver::Simple,
+            strategy::Solver::Adaptive,
+            strategy::Solver::Entropy,
+            strategy::Solver::Optimal,
+        ] {
+            let mut strategy = solver.build(word_list.clone(), Default::default());
+            strategy.prime(&[Clue::Scored(word("bat"), [LetterScore::Wrong; 3])]);
+            assert!(
+                matches!(strategy.make_guess(), Err(StrategyError::NeedsReset)),
+                "{}",
+                solver
+            );
+            let engine = engine::StandardEngine::new(word("hat"), word_list.clone(), 6);
+            let transcript = try_run_game(engine, strategy).unwrap();
+            assert_eq!(
+                transcript.outcome(),
+                engine::GameOutcome::GaveUp(StrategyError::NeedsReset)
+            );
+        }
+
+        // Every word that could be the answer is off limits.
+        let mut strategy = strategy::EntropyStrategy::new(word_list.clone());
+        strategy.set_guess_rules(strategy::GuessRules {
+            never_guess: word_list.0.iter().copied().collect(),
+            always_open: None,
+        });
+        let engine = engine::StandardEngine::new(word("hat"), word_list.clone(), 6);
+        assert!(!run_game(engine, &mut strategy));
+        assert!(matches!(
+            strategy.make_guess(),
+            Err(StrategyError::OutOfCandidates)
+        ));
+
+        // No solver can guess in no time at all.
+        let limit = std::time::Duration::ZERO;
+        let strategy =
+            strategy::TimeLimit::new(strategy::SimpleStrategy::new(word_list.clone()), limit);
+        let engine = engine::StandardEngine::new(word("hat"), word_list, 6);
+        let transcript = try_run_game(engine, strategy).unwrap();
+        assert_eq!(
+            transcript.gave_up,
+            Some(StrategyError::BudgetExceeded(limit))
+        );
+        assert!(transcript.turns.is_empty());
+    }
+
+    #[test]
+    fn test_untrusted_input() {
+        // Malformed input of every kind the library parses comes back as an
+        // error; none of it may panic.
+        let nasty = [
+            "",
+            "\n\n",
+            "é",
+            "ééééé",
+            "CRANE",
+            "cran",
+            "cranes",
+            "\u{0}\u{0}\u{0}\u{0}\u{0}",
+            "🟩🟩🟩🟩🟩🟩",
+            "gggggg",
+            "99999999999999999999",
+            "-1",
+            "crane:gy",
+            "a,b,c\n,,,,,,",
+            "WSLV",
+            "WSLV 99999999999 transcript 5 0",
+            "WSLV 1 opening-book 5 zz",
+            "Wordle 1,234 3/6\n🟩",
+            "Wordle 0 7/6\n\n⬛⬛",
+            "guesses: 99999999999999999\ncrane ggggg",
+            "date,puzzle,guess,score\n,#,é,🟩",
+            "answer,guesses\nspilt,crane|é",
+            "9999-99-99 crane",
+            "2022-02-30 crane",
+            "crane\n   ggggg spilt\n ybbbb",
+            "[#] (#) .#.",
+        ];
+        let word_list: WordList<5> = ["crane", "spilt"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let header = {
+            let mut header = Vec::new();
+            artifact::Header::new(artifact::ArtifactKind::OpeningBook, &word_list)
+                .write(&mut header)
+                .unwrap();
+            String::from_utf8(header).unwrap()
+        };
+        for a in nasty {
+            for b in nasty {
+                for text in [format!("{}{}", a, b), format!("{}\n{}", a, b)] {
+                    let _ = Word::<5>::try_from(text.as_str());
+                    let _ = parse_score::<5>(&text);
+                    let _ = score(&text, "crane");
+                    let _ = score(&text, &text);
+                    let _ = tiles::TileStyle::parse_tiles(&text);
+                    let _ = strategy::parse_guess_line::<5>(&text);
+                    let _ = history::PlayedPuzzles::parse(&text);
+                    let _ = league::parse_share_grid(&text);
+                    let _ = league::League::parse(&text);
+                    let _ = record::GameRecord::<5>::parse(&text);
+                    let _ = schedule::Schedule::parse(&text, &word_list);
+                    let _ = dictionary::parse_words::<5>(&text);
+                    let _ = dictionary::parse_extra_words::<5>(&text);
+                    let _ = import::import_csv::<5>(&text);
+                    let _ = frequency::Frequencies::<5>::parse_csv(&text);
+                    let _ = text.parse::<nyt::Date>();
+                    let _ = artifact::Header::read(text.as_bytes());
+                    let _ = decision_tree::DecisionTree::read(text.as_bytes(), &word_list);
+                    let book = format!("{}{}", header, text);
+                    let _ = decision_tree::DecisionTree::read(book.as_bytes(), &word_list);
+                    let _ = record::read_transcript::<5>(text.as_bytes(), &word_list);
+                    #[cfg(feature = "serde")]
+                    let _ = manifest::RunManifest::from_json(&text);
+                }
+            }
+        }
+
+        // A game allowed no guesses still gets one.
+        let engine = engine::StandardEngine::new(word_list.0[0], word_list.clone(), 0);
+        let transcript =
+            run_game_recorded(&engine, strategy::SimpleStrategy::new(word_list.clone()));
+        assert_eq!(transcript.turns.len(), 1);
+        let engine = engine::StandardEngine::new(word_list.0[1], word_list.clone(), 0)
+            .on_invalid_guess(engine::InvalidGuessPolicy::ConsumeTurn);
+        assert_eq!(
+            engine.score_guess(&Word::try_from("zzzzz").unwrap()),
+            GuessResult::Done {
+                won: false,
+                score: None
+            }
+        );
+    }
+
+    #[test]
+    fn test_multi_board() {
+        use multi_board::MultiBoardStrategy;
+        let word_list: WordList<3> = ["bat", "cat", "hat", "can", "tan", "ant", "nab"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let word = |s: &str| Word::try_from(s).unwrap();
+
+        let engine =
+            engine::MultiBoardEngine::new([word("cat"), word("tan")], word_list.clone(), 6);
+        match engine.score_guess(&word("cat")) {
+            GuessResult::Boards(scores) => assert_eq!(
+                scores,
+                vec![
+                    Some([RightPlace; 3]),
+                    Some([Wrong, RightPlace, RightLetter])
+                ]
+            ),
+            _ => panic!("expected a score per board"),
+        }
+        assert_eq!(engine.solved(), [true, false]);
+        match engine.score_guess(&word("bat")) {
+            GuessResult::Boards(scores) => assert_eq!(scores[0], None),
+            _ => panic!("expected a score per board"),
+        }
+        assert!(matches!(
+            engine.score_guess(&word("tan")),
+            GuessResult::Done { won: true, .. }
+        ));
+
+        for secrets in [["cat", "tan"], ["nab", "hat"], ["ant", "ant"]] {
+            let engine = engine::MultiBoardEngine::new(secrets.map(word), word_list.clone(), 7);
+            let mut strategy = MultiBoardStrategy::<3, 2>::new(word_list.clone());
+            assert!(run_game(&engine, &mut strategy), "lost on {:?}", secrets);
+        }
+    }
+
+    #[test]
+    fn test_solvers() {
+        use strategy::Solver;
+        let word_list: WordList<3> = ["bat", "cat", "hat", "cot", "cut"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        for solver in [
+            Solver::Simple,
+            Solver::Adaptive,
+            Solver::Entropy,
+            Solver::Optimal,
+        ] {
+            assert_eq!(solver.to_string().parse(), Ok(solver));
+            let engine =
+                engine::StandardEngine::new(Word::try_from("cut").unwrap(), word_list.clone(), 6);
+            assert!(run_game(
+                engine,
+                solver.build(word_list.clone(), Default::default())
+            ));
+
+            // With only two possible answers, a guess from either settles it.
+            let answers: WordList<3> = ["cat", "hat"]
+                .iter()
+                .map(|s| Word::try_from(*s).unwrap())
+                .collect();
+            let engine =
+                engine::StandardEngine::new(Word::try_from("hat").unwrap(), word_list.clone(), 6);
+            let transcript = run_game_recorded(
+                engine,
+                solver.build_with_answers(word_list.clone(), answers, Default::default()),
+            );
+            assert!(transcript.won, "{}", solver);
+            if matches!(solver, Solver::Entropy | Solver::Optimal) {
+                assert!(transcript.guesses_used() <= 2, "{}", solver);
+            }
+        }
+        assert!("greedy".parse::<Solver>().is_err());
+    }
+
+    #[test]
+    fn test_profiles() {
+        use profile::Profile;
+        let word_list: WordList<3> = ["bat", "cat", "hat", "hot"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let word = |s: &str| Word::try_from(s).unwrap();
+        for profile in profile::PROFILES {
+            assert_eq!(profile.name.parse::<Profile>().unwrap(), *profile);
+        }
+        assert!("quordle".parse::<Profile>().is_err());
+
+        let lewdle = "lewdle".parse::<Profile>().unwrap();
+        let engine = lewdle.engine(word("hat"), word_list.clone());
+        assert!(matches!(
+            engine.score_guess(&word("zzz")),
+            GuessResult::Wasted(_)
+        ));
+        assert!(matches!(
+            engine.score_guess(&word("bat")),
+            GuessResult::Continue(_)
+        ));
+        assert!(matches!(
+            engine.score_guess(&word("bat")),
+            GuessResult::Wasted(engine::GuessError::Repeated(_))
+        ));
+
+        let hard = "hard-mode".parse::<Profile>().unwrap();
+        let engine = hard.engine(word("hat"), word_list);
+        assert!(matches!(
+            engine.score_guess(&word("bat")),
+            GuessResult::Continue(_)
+        ));
+        assert!(matches!(
+            engine.score_guess(&word("hot")),
+            GuessResult::Rejected(engine::GuessError::MissingGreen { .. })
+        ));
+    }
+
+    #[test]
+    fn test_hard_mode() {
+        use engine::GuessError::*;
+        let word_list: WordList<3> = ["bat", "tab", "tan", "ant", "oat", "zoo"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let word = |s: &str| Word::try_from(s).unwrap();
+        let engine = engine::StandardEngine::new(word("ant"), word_list.clone(), 6).hard_mode();
+        // bat against ant: the t is green, the a yellow.
+        assert!(matches!(
+            engine.score_guess(&word("bat")),
+            GuessResult::Continue(_)
+        ));
+        assert!(matches!(
+            engine.score_guess(&word("tan")),
+            GuessResult::Rejected(MissingGreen {
+                letter: 't',
+                position: 2
+            })
+        ));
+        assert!(matches!(
+            engine.score_guess(&word("zoo")),
+            GuessResult::Rejected(MissingGreen { .. })
+        ));
+        // A refused guess doesn't count as played.
+        assert!(matches!(
+            engine.score_guess(&word("oat")),
+            GuessResult::Continue(_)
+        ));
+        assert!(matches!(
+            engine.score_guess(&word("ant")),
+            GuessResult::Done { won: true, .. }
+        ));
+
+        let history = [(word("tan"), [RightLetter, RightLetter, Wrong])];
+        assert_eq!(engine::check_hard_mode(&word("bat"), &history), Ok(()));
+        assert_eq!(
+            engine::check_hard_mode(&word("zoo"), &history),
+            Err(MissingYellow { letter: 't' })
+        );
+        assert_eq!(
+            engine::GuessError::<3>::MissingGreen {
+                letter: 't',
+                position: 2
+            }
+            .to_string(),
+            "letter 3 must be t"
+        );
+
+        // A strategy that plays along with the rules wins as usual.
+        let engine = engine::StandardEngine::new(word("ant"), word_list.clone(), 6).hard_mode();
+        assert!(run_game(
+            engine,
+            strategy::AntiWordleStrategy::new(word_list)
+        ));
+    }
+
+    #[test]
+    fn test_letter_stats() {
+        let word_list: WordList<3> = ["bat", "tat", "cab"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let stats = letter_stats::LetterStats::new(&word_list);
+        assert_eq!(stats.words(), 3);
+        assert_eq!(stats.occurrences('t'), 3);
+        assert_eq!(stats.containing('t'), 2);
+        assert_eq!(stats.at(0, 't'), 1);
+        assert_eq!(stats.at(2, 't'), 2);
+        assert_eq!(stats.occurrences('z'), 0);
+        assert_eq!(stats.bigram('a', 't'), 2);
+        assert_eq!(stats.bigram('t', 'a'), 1);
+        assert_eq!(stats.letters(), vec!['a', 't', 'b', 'c']);
+        assert_eq!(stats.rarest(2), vec!['c', 'b']);
+        assert_eq!(stats.top_bigrams(1), vec![(('a', 't'), 2)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "strategy repeated guess bat")]
+    fn test_no_repeat_guard() {
+        struct Stubborn;
+        impl Strategy<3> for Stubborn {
+            fn make_guess(&mut self) -> Result<Word<3>, StrategyError> {
+                Ok(Word::try_from("bat").unwrap())
+            }
+
+            fn receive_score(&mut self, _score: &Score<3>) {}
+        }
+
+        let word_list: WordList<3> = ["bat", "cat", "hat"]
+            .iter()
+            .map(|s| Word::try_from(*s).unwrap())
+            .collect();
+        let engine = engine::StandardEngine::new(Word::try_from("hat").unwrap(), word_list, 6);
+        run_game(engine, strategy::NoRepeatGuard::new(Stubborn));
     }
 }
@@ -1,11 +1,1621 @@
+use clap::{Args, Parser, Subcommand};
 use std::convert::TryFrom;
+use std::path::{Path, PathBuf};
 use wordle_solve::*;
+
+#[derive(Parser)]
+#[command(about = "Solve Wordle puzzles, or play them")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Word file to play with instead of the bundled word list, one
+    /// five-letter word per line; `solve --length` and `sweep --lengths`
+    /// take the words of their lengths from it instead
+    #[arg(long, global = true, value_name = "FILE")]
+    words: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Score your guesses from a real game and let the solver take over
+    Assist {
+        /// Print the solver's top candidates each turn
+        #[arg(long)]
+        trace: bool,
+        /// Write the trace to this file instead of stdout
+        #[arg(long)]
+        trace_file: Option<PathBuf>,
+        /// Leave out of the trace candidates sharing four or more letters
+        /// with a better one
+        #[arg(long)]
+        diverse: bool,
+        /// Show two advisors' picks (entropy, minimax) before each of your
+        /// guesses after the first
+        #[arg(long, num_args = 2, value_names = ["ADVISOR", "ADVISOR"])]
+        compare: Option<Vec<advice::Advisor>>,
+        /// Longest the solver may think about each guess; it plays the best
+        /// guess found so far when time runs out
+        #[arg(long, value_name = "MS")]
+        max_think_ms: Option<u64>,
+        /// After each turn, show how many words fell under each score
+        #[arg(long)]
+        histogram: bool,
+        /// After each turn, list up to N of the words the score ruled out
+        #[arg(long, value_name = "N")]
+        eliminated: Option<usize>,
+        /// Don't score every word against every other in the background
+        /// while you type, which makes the solver faster but takes memory
+        #[arg(long)]
+        no_warm_up: bool,
+        /// After the game, write a timestamped log of every guess and score
+        /// to this file, for finding where a game went wrong
+        #[arg(long, value_name = "FILE")]
+        event_log: Option<PathBuf>,
+        /// After the game, save the guesses and scores as a record, to check
+        /// against the real game's grid with `verify`
+        #[arg(long, value_name = "FILE")]
+        save_record: Option<PathBuf>,
+        /// A guess already played and its score, e.g. crane:gybbb, to pick
+        /// up a game part way through; may be repeated
+        #[arg(long = "clue", value_name = "GUESS:SCORE", value_parser = parse_clue)]
+        clues: Vec<(word::Word<5>, word::Score<5>)>,
+        /// Words the solver shouldn't consider as the answer, as the NYT
+        /// never picks them: plurals or past-tense; may be repeated
+        #[arg(long, value_name = "KIND")]
+        exclude: Vec<policy::Exclusion>,
+        #[command(flatten)]
+        rules: GuessRuleArgs,
+        /// How to draw scores: color, high-contrast, symbols or text
+        #[arg(long, default_value_t)]
+        tiles: tiles::TileStyle,
+        /// Play from an opening book written by `opening-book` instead of
+        /// thinking
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["trace", "trace_file", "diverse", "compare", "max_think_ms", "histogram", "eliminated", "no_warm_up", "event_log", "save_record", "clues", "exclude", "never_guess", "always_open"])]
+        book: Option<PathBuf>,
+    },
+    /// Play against a random secret word
+    Play {
+        /// Number of hints you can ask the solver for
+        #[arg(long, default_value_t = 0)]
+        hints: usize,
+        /// Rules of the site to simulate: nyt, wordle-unlimited, lewdle or
+        /// hard-mode. The flags below add to them
+        #[arg(long)]
+        profile: Option<profile::Profile>,
+        /// Number of guesses allowed [default: 6, or the profile's]
+        #[arg(long)]
+        guesses: Option<usize>,
+        /// Refuse repeated guesses instead of letting them waste a turn
+        #[arg(long)]
+        reject_repeats: bool,
+        /// Every guess has to keep the greens in place and use the yellows
+        #[arg(long)]
+        hard_mode: bool,
+        /// Give away this many letters of the secret before the first guess
+        #[arg(long, default_value_t = 0)]
+        free_letters: usize,
+        /// Seed for picking the secret and the free letters, to play the
+        /// same game again [default: random]
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Time each guess, and keep speed stats alongside the results
+        #[arg(long)]
+        time_attack: bool,
+        /// How to draw scores and the share grid: color, high-contrast,
+        /// symbols or text
+        #[arg(long, default_value_t)]
+        tiles: tiles::TileStyle,
+        /// Save the finished game as a record, e.g. into an archive folder
+        #[arg(long, value_name = "FILE")]
+        save_record: Option<PathBuf>,
+        /// Replay a past NYT puzzle by number
+        #[arg(long, conflicts_with_all = ["practice", "schedule"])]
+        puzzle: Option<u32>,
+        /// League schedule file of `YYYY-MM-DD word` lines to take the secret
+        /// from
+        #[arg(long, conflicts_with = "practice")]
+        schedule: Option<PathBuf>,
+        /// Day of the schedule to play [default: today]
+        #[arg(long, requires = "schedule")]
+        date: Option<nyt::Date>,
+        /// File recording which puzzles you've played [default:
+        /// ~/.wordle-solve/played]
+        #[arg(long)]
+        history: Option<PathBuf>,
+        /// Show your stats instead of replaying a puzzle you've already played
+        #[arg(long, requires = "puzzle")]
+        stats: bool,
+        /// Practice plan written by `practice-plan` to take the secret from
+        #[arg(long, requires = "day")]
+        practice: Option<PathBuf>,
+        /// Day of the practice plan to play (1-7)
+        #[arg(long)]
+        day: Option<usize>,
+    },
+    /// Watch a solver find a secret you give it
+    Solve {
+        /// The word to find
+        #[cfg_attr(feature = "fetch", arg(long, required_unless_present = "today"))]
+        #[cfg_attr(not(feature = "fetch"), arg(long, required = true))]
+        secret: Option<String>,
+        /// Find today's NYT answer, fetched from the NYT the first time
+        #[cfg(feature = "fetch")]
+        #[arg(long, conflicts_with = "secret")]
+        today: bool,
+        /// Solver to play: simple, adaptive, entropy or optimal
+        #[arg(long, default_value_t)]
+        strategy: strategy::Solver,
+        /// Number of guesses allowed
+        #[arg(long, default_value_t = 6)]
+        guesses: usize,
+        /// Only consider NYT answers as the secret, as the NYT does, while
+        /// still guessing from the whole word list
+        #[arg(long)]
+        nyt_answers: bool,
+        /// Words the solver shouldn't consider as the answer: plurals or
+        /// past-tense; may be repeated
+        #[arg(long, value_name = "KIND")]
+        exclude: Vec<policy::Exclusion>,
+        /// CSV of word,count from a corpus, so the entropy solver treats
+        /// common words as likelier answers
+        #[arg(long, value_name = "FILE")]
+        frequencies: Option<PathBuf>,
+        /// Every guess has to keep the greens in place and use the yellows;
+        /// the solver's probes are swapped for words that do
+        #[arg(long)]
+        hard_mode: bool,
+        /// How to draw scores: color, high-contrast, symbols or text
+        #[arg(long, default_value_t)]
+        tiles: tiles::TileStyle,
+        #[command(flatten)]
+        rules: GuessRuleArgs,
+        /// Word length to play, taking the words of that length from
+        /// --words instead of five-letter ones
+        #[arg(
+            long,
+            value_parser = parse_length,
+            conflicts_with_all = ["nyt_answers", "exclude", "frequencies", "hard_mode", "never_guess", "always_open"]
+        )]
+        #[cfg_attr(feature = "fetch", arg(conflicts_with = "today"))]
+        length: Option<usize>,
+    },
+    /// Keep standings for a group of players sharing their grids
+    League {
+        #[command(subcommand)]
+        command: LeagueCommand,
+    },
+    /// Benchmark a strategy on a seeded sample of secrets, writing a run
+    /// manifest that can reproduce it
+    #[cfg(feature = "serde")]
+    Bench {
+        /// Strategy to benchmark: simple, adaptive, entropy, human, or optimal
+        #[arg(long, default_value = "simple")]
+        strategy: String,
+        /// Strategy parameter, as name=value; may be repeated
+        #[arg(long = "param", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+        /// Number of secrets to play
+        #[arg(long, default_value_t = 100)]
+        games: usize,
+        /// Number of guesses allowed
+        #[arg(long, default_value_t = 6)]
+        guesses: usize,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Chance each turn that the simulated player plays some other
+        /// candidate instead of the strategy's guess
+        #[arg(long, default_value_t = 0.0, value_parser = parse_probability)]
+        guess_error: f64,
+        /// Chance each turn that the simulated player enters a tile of the
+        /// score wrong
+        #[arg(long, default_value_t = 0.0, value_parser = parse_probability)]
+        score_error: f64,
+        /// Folder to write run-manifest.json to
+        #[arg(long, default_value = ".")]
+        out: PathBuf,
+        /// Rerun the benchmark described by a run manifest instead
+        #[arg(long, conflicts_with_all = ["strategy", "params", "games", "guesses", "seed", "guess_error", "score_error", "never_guess", "always_open"])]
+        from_manifest: Option<PathBuf>,
+        #[command(flatten)]
+        rules: GuessRuleArgs,
+    },
+    /// Benchmark strategies on several dictionaries side by side, e.g. to
+    /// check they don't lean on English letter frequencies
+    #[cfg(feature = "serde")]
+    Sweep {
+        /// Dictionary to play: en-answers, en-allowed, or NAME=ANSWERS for a
+        /// word file, optionally with a file of extra allowed guesses as
+        /// NAME=ANSWERS,ALLOWED; may be repeated [default: en-answers]
+        #[arg(long = "dictionary", value_name = "DICTIONARY")]
+        dictionaries: Vec<String>,
+        /// Strategy to compare: simple, adaptive, entropy, human, or optimal;
+        /// may be repeated [default: simple]
+        #[arg(long = "strategy")]
+        strategies: Vec<String>,
+        /// Number of secrets to play from each dictionary
+        #[arg(long, default_value_t = 100)]
+        games: usize,
+        /// Number of guesses allowed
+        #[arg(long, default_value_t = 6)]
+        guesses: usize,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Word lengths to sweep instead, e.g. 4-8, each played from the
+        /// words of that length in --words
+        #[arg(long, value_name = "MIN-MAX", value_parser = parse_lengths, conflicts_with = "dictionaries")]
+        lengths: Option<std::ops::RangeInclusive<usize>>,
+    },
+    /// Pick the best of several openers on a random half of the NYT answers
+    /// and play it on the other half, to check the pick isn't overfit
+    #[cfg(feature = "serde")]
+    CrossValidate {
+        /// Strategy to tune: simple, adaptive, entropy, human, or optimal
+        #[arg(long, default_value = "simple")]
+        strategy: String,
+        /// Strategy parameter, as name=value; may be repeated
+        #[arg(long = "param", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+        /// Opener to try; may be repeated
+        #[arg(long = "opener", value_name = "WORD", required = true)]
+        openers: Vec<String>,
+        /// Number of guesses allowed
+        #[arg(long, default_value_t = 6)]
+        guesses: usize,
+        /// Seed for splitting the answers
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// List the answer families hard mode can trap you in after an opener,
+    /// with more members than guesses left and no allowed guess to split them
+    Traps {
+        #[arg(default_value = strategy::DEFAULT_OPENER)]
+        opener: String,
+        /// Number of guesses allowed
+        #[arg(long, default_value_t = 6)]
+        guesses: usize,
+        /// Only look for traps among NYT answers, instead of the whole word
+        /// list
+        #[arg(long)]
+        nyt_answers: bool,
+    },
+    /// Print the JSON Schema for one of the solver's JSON outputs, or list
+    /// them
+    #[cfg(feature = "serde")]
+    Schema {
+        /// suggestion, game-record, benchmark or decision-tree
+        name: Option<schema::Schema>,
+    },
+    /// Work out the guess for every turn of every NYT answer and save it as
+    /// an opening book
+    OpeningBook {
+        /// File to write the book to
+        out: PathBuf,
+        /// How to pick each guess: entropy or minimax
+        #[arg(long, default_value = "entropy")]
+        advisor: advice::Advisor,
+    },
+    /// Say whether a word is still possible given the clues so far
+    Check {
+        word: String,
+        /// Each guess and its score, e.g. crane:gybbb
+        #[arg(value_parser = parse_clue)]
+        clues: Vec<(word::Word<5>, word::Score<5>)>,
+    },
+    /// List the secrets that fit a finished game, with its grid on stdin;
+    /// leave out the winning guess to recover a forgotten answer
+    Secrets {
+        /// The guesses, in order
+        guesses: Vec<String>,
+        /// Only consider NYT answers, instead of the whole word list
+        #[arg(long)]
+        nyt_answers: bool,
+    },
+    /// Say how lucky a solve in two was: the openers that could have led to
+    /// it, and the chance each left of guessing the answer second
+    Witness {
+        answer: String,
+        /// The opener actually played; lists the surest openers if not given
+        #[arg(long)]
+        opener: Option<String>,
+        /// Number of openers to list
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+        /// Only count NYT answers as left over, instead of the whole word
+        /// list
+        #[arg(long)]
+        nyt_answers: bool,
+    },
+    /// Count how often each letter turns up in each position of the word
+    /// list, and its most common bigrams
+    Heatmap {
+        /// Number of bigrams to list
+        #[arg(long, default_value_t = 10)]
+        bigrams: usize,
+        /// Count the NYT answers instead of the whole word list
+        #[arg(long)]
+        nyt_answers: bool,
+    },
+    /// Append a score column to a CSV of secret,guess rows
+    ScoreFile {
+        /// CSV to read; stdin if not given
+        input: Option<PathBuf>,
+        /// File to write; stdout if not given
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Check the real game's grid, pasted on stdin, against a saved game
+    /// record, pointing out the first row where they differ
+    Verify {
+        /// Game record, as saved by `assist --save-record`
+        record: PathBuf,
+    },
+    /// Print the share grid of a saved game record
+    Share {
+        /// Game record, as saved by `play --save-record`
+        record: PathBuf,
+        /// How to draw the grid: color, high-contrast, symbols or text (for
+        /// places that strip emoji)
+        #[arg(long, default_value_t)]
+        tiles: tiles::TileStyle,
+    },
+    /// Summarize a folder of exported game records
+    AnalyzeArchive {
+        /// Folder with one game record per file
+        dir: PathBuf,
+    },
+    /// Turn another tracker's CSV export into game records, one file each
+    ImportRecords {
+        /// The tracker's CSV export
+        export: PathBuf,
+        /// Folder to write the records to, to summarize with analyze-archive
+        dir: PathBuf,
+    },
+    /// Write a week of practice secrets targeting the weaknesses in an archive
+    PracticePlan {
+        /// Folder with one game record per file
+        dir: PathBuf,
+        /// File to write the plan to, one secret per line
+        out: PathBuf,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Check the bundled data, saved files and scoring work, for bug reports
+    Doctor {
+        /// Saved file, such as an opening book, to check this build can
+        /// still load; may be repeated
+        #[arg(long = "artifact", value_name = "FILE")]
+        artifacts: Vec<PathBuf>,
+    },
+}
+
+#[derive(Args, Default)]
+struct GuessRuleArgs {
+    /// Words the solver must never guess, comma separated
+    #[arg(long, value_delimiter = ',', value_name = "WORDS")]
+    never_guess: Vec<String>,
+    /// Word the solver must open with
+    #[arg(long, value_name = "WORD")]
+    always_open: Option<String>,
+}
+
+/// Allows only the answers none of `exclusions` rule out.
+fn answer_policy(exclusions: &[policy::Exclusion]) -> policy::AllOf<5> {
+    policy::AllOf(
+        exclusions
+            .iter()
+            .map(|exclusion| Box::new(*exclusion) as Box<dyn policy::WordPolicy<5>>)
+            .collect(),
+    )
+}
+
+impl GuessRuleArgs {
+    fn to_rules(&self, word_list: &word::WordList<5>) -> strategy::GuessRules<5> {
+        let word = |text: &String| {
+            word::Word::try_from(text.to_ascii_lowercase().as_str())
+                .ok()
+                .filter(|word| word_list.0.contains(word))
+                .unwrap_or_else(|| exit_with(format!("{:?} isn't in the dictionary", text)))
+        };
+        let rules = strategy::GuessRules {
+            never_guess: self.never_guess.iter().map(word).collect(),
+            always_open: self.always_open.as_ref().map(word),
+        };
+        if let Some(opener) = rules.always_open.filter(|opener| !rules.allows(opener)) {
+            exit_with(format!("{} can't be both banned and the opener", opener));
+        }
+        rules
+    }
+}
+
+#[derive(Subcommand)]
+enum LeagueCommand {
+    /// Add a player's share grid, read from stdin, to the league
+    Import {
+        /// League file, created if it doesn't exist
+        league: PathBuf,
+        player: String,
+        /// Puzzle number, for grids that don't say
+        #[arg(long)]
+        puzzle: Option<u32>,
+        /// Time taken, as m:ss or seconds
+        #[arg(long, value_parser = parse_time)]
+        time: Option<u32>,
+    },
+    /// Print the league's standings
+    Standings { league: PathBuf },
+}
+
+#[cfg(feature = "serde")]
+fn parse_param(text: &str) -> Result<(String, String), String> {
+    let (name, value) = text
+        .split_once('=')
+        .ok_or_else(|| format!("expected name=value, got {:?}", text))?;
+    Ok((name.to_owned(), value.to_owned()))
+}
+
+#[cfg(feature = "serde")]
+fn parse_probability(text: &str) -> Result<f64, String> {
+    text.parse()
+        .ok()
+        .filter(|p| (0.0..=1.0).contains(p))
+        .ok_or_else(|| format!("expected a probability from 0 to 1, got {:?}", text))
+}
+
+fn parse_time(text: &str) -> Result<u32, String> {
+    league::parse_time(text).ok_or_else(|| format!("expected m:ss or seconds, got {:?}", text))
+}
+
+fn parse_clue(text: &str) -> Result<(word::Word<5>, word::Score<5>), String> {
+    let bad_clue = || {
+        format!(
+            "expected a guess and its score like crane:gybbb, got {:?}",
+            text
+        )
+    };
+    let (guess, score) = text.split_once(':').ok_or_else(bad_clue)?;
+    let guess =
+        word::Word::try_from(guess.to_ascii_lowercase().as_str()).map_err(|_| bad_clue())?;
+    let score = word::parse_score(score).ok_or_else(bad_clue)?;
+    Ok((guess, score))
+}
+
+#[cfg(feature = "serde")]
+fn parse_lengths(text: &str) -> Result<std::ops::RangeInclusive<usize>, String> {
+    let bad_lengths = || {
+        format!(
+            "expected word lengths like 4-8, between {} and {}, got {:?}",
+            dispatch::WORD_LENGTHS.start(),
+            dispatch::WORD_LENGTHS.end(),
+            text
+        )
+    };
+    let (min, max) = text.split_once('-').unwrap_or((text, text));
+    let min = min.trim().parse().map_err(|_| bad_lengths())?;
+    let max = max.trim().parse().map_err(|_| bad_lengths())?;
+    if min > max || !dispatch::WORD_LENGTHS.contains(&min) || !dispatch::WORD_LENGTHS.contains(&max)
+    {
+        return Err(bad_lengths());
+    }
+    Ok(min..=max)
+}
+
+fn parse_length(text: &str) -> Result<usize, String> {
+    text.trim()
+        .parse()
+        .ok()
+        .filter(|length| dispatch::WORD_LENGTHS.contains(length))
+        .ok_or_else(|| {
+            format!(
+                "expected a word length between {} and {}, got {:?}",
+                dispatch::WORD_LENGTHS.start(),
+                dispatch::WORD_LENGTHS.end(),
+                text
+            )
+        })
+}
+
+/// Solves for `secret` among the `length`-letter words of a word file.
+fn solve_length(
+    length: usize,
+    path: &Path,
+    secret: &str,
+    strategy: strategy::Solver,
+    guesses: usize,
+    tiles: tiles::TileStyle,
+) {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|error| exit_with(error));
+    let word_list = dynamic::DynWordList::parse_words(length, &text)
+        .expect("clap checks the length")
+        .unwrap_or_else(|error| exit_with(format!("{}: {}", path.display(), error)));
+    let secret = dynamic::DynWord::try_from(secret.to_lowercase().as_str())
+        .ok()
+        .filter(|secret| word_list.contains(secret))
+        .unwrap_or_else(|| {
+            exit_with(format!(
+                "{:?} isn't one of the {}-letter words in {}",
+                secret,
+                length,
+                path.display()
+            ))
+        });
+    let mut solver = strategy
+        .build_dyn(&word_list)
+        .expect("clap checks the length");
+    let transcript =
+        dynamic::play(&secret, solver.as_mut(), guesses).unwrap_or_else(|error| exit_with(error));
+    for (guess, score) in &transcript.turns {
+        println!("{}", tiles.dyn_row(guess, score));
+    }
+    if transcript.won {
+        println!("Solved in {}/{}", transcript.turns.len(), guesses);
+    } else {
+        println!("Out of guesses; the word was {}", secret);
+        std::process::exit(1);
+    }
+}
+
+/// `", 4.123 guesses per win"`, or nothing if no games were won.
+#[cfg(feature = "serde")]
+fn per_win(average_guesses: Option<f64>) -> String {
+    average_guesses.map_or_else(String::new, |average| {
+        format!(", {:.3} guesses per win", average)
+    })
+}
+
+/// Sweeps each of `lengths` over the words of that length in the word file
+/// at `path`, and prints the lengths side by side.
+#[cfg(feature = "serde")]
+fn sweep_lengths(
+    lengths: std::ops::RangeInclusive<usize>,
+    path: &Path,
+    strategies: &[String],
+    games: usize,
+    guesses: usize,
+    seed: u64,
+) {
+    let mut strategies = strategies.to_vec();
+    if strategies.is_empty() {
+        strategies.push("simple".to_owned());
+    }
+    let text = std::fs::read_to_string(path)
+        .unwrap_or_else(|error| exit_with(format!("{}: {}", path.display(), error)));
+    let mut matrix = dictionary::LengthMatrix::default();
+    for length in lengths {
+        let report = with_word_length!(length, |N| sweep_length::<N>(
+            &text,
+            &strategies,
+            games,
+            guesses,
+            seed
+        ))
+        .expect("parse_lengths checks the range");
+        matrix.0.push((length, report));
+    }
+    print!("{}", matrix);
+}
+
+/// Sweeps the `WORD_LENGTH`-letter words of a general word list.
+#[cfg(feature = "serde")]
+fn sweep_length<const WORD_LENGTH: usize>(
+    text: &str,
+    strategies: &[String],
+    games: usize,
+    guesses: usize,
+    seed: u64,
+) -> dictionary::SweepReport {
+    let words =
+        dictionary::parse_words::<WORD_LENGTH>(text).unwrap_or_else(|error| exit_with(error));
+    let dictionary =
+        dictionary::Dictionary::new(&WORD_LENGTH.to_string(), words, word::WordList(Vec::new()));
+    dictionary::sweep(&[dictionary], strategies, games, guesses, seed)
+        .unwrap_or_else(|error| exit_with(error))
+}
+
+/// Says when the solver played the best guess it had found because its
+/// thinking time ran out.
+struct ThinkTimeNotice;
+
+impl observer::Observer<5> for ThinkTimeNotice {
+    fn on_guess(&mut self, _guess: &word::Word<5>, metrics: Option<metrics::Metrics>) {
+        if let Some(metrics) = metrics.filter(|metrics| metrics.candidates_skipped > 0) {
+            println!(
+                "Out of thinking time after {} of {} candidates",
+                metrics.candidates_evaluated,
+                metrics.candidates_evaluated + metrics.candidates_skipped
+            );
+        }
+    }
+}
+
+fn exit_with(error: impl std::fmt::Display) -> ! {
+    eprintln!("{}", error);
+    std::process::exit(1);
+}
+
+fn confirm(prompt: &str) -> bool {
+    println!("{}", prompt);
+    loop {
+        let mut buffer = String::new();
+        if std::io::stdin().read_line(&mut buffer).unwrap_or(0) == 0 {
+            return false;
+        }
+        match buffer.trim().to_ascii_lowercase().as_str() {
+            "y" => return true,
+            "n" => return false,
+            _ => println!("Please answer y or n:"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn load_dictionary(spec: &str) -> dictionary::Dictionary<5> {
+    if let Some(dictionary) = dictionary::Dictionary::bundled(spec) {
+        return dictionary;
+    }
+    let (name, files) = spec.split_once('=').unwrap_or_else(|| {
+        exit_with(format!(
+            "unknown dictionary {:?} (expected {} or NAME=FILE)",
+            spec,
+            dictionary::BUNDLED.join(", ")
+        ))
+    });
+    let read = |path: &str| {
+        let text = std::fs::read_to_string(path).unwrap_or_else(|error| exit_with(error));
+        dictionary::parse_words(&text)
+            .unwrap_or_else(|error| exit_with(format!("{}: {}", path, error)))
+    };
+    let (answers, allowed) = match files.split_once(',') {
+        Some((answers, allowed)) => (read(answers), read(allowed)),
+        None => (read(files), word::WordList(Vec::new())),
+    };
+    dictionary::Dictionary::new(name, answers, allowed)
+}
+
+/// `extra-words.txt` in the config folder, whose words are added to the
+/// dictionary when it exists.
+fn extra_words_path() -> Option<PathBuf> {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| Some(Path::new(&std::env::var_os("HOME")?).join(".config")))?;
+    Some(config.join("wordle-solve").join("extra-words.txt"))
+}
+
+/// Where fetched daily puzzles are kept.
+#[cfg(feature = "fetch")]
+fn daily_cache_dir() -> Option<PathBuf> {
+    let cache = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| Some(Path::new(&std::env::var_os("HOME")?).join(".cache")))?;
+    Some(cache.join("wordle-solve").join("daily"))
+}
+
+fn default_history_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")?;
+    Some(Path::new(&home).join(".wordle-solve").join("played"))
+}
+
+fn scheduled_secret(path: &Path, date: nyt::Date, word_list: &word::WordList<5>) -> word::Word<5> {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|error| exit_with(error));
+    let schedule = schedule::Schedule::parse(&text, word_list)
+        .unwrap_or_else(|error| exit_with(format!("{}: {}", path.display(), error)));
+    schedule.answer(date).unwrap_or_else(|| {
+        exit_with(match schedule.last_date() {
+            Some(last) => format!(
+                "nothing is scheduled on {} (the schedule ends {})",
+                date, last
+            ),
+            None => "the schedule is empty".to_owned(),
+        })
+    })
+}
+
+fn practice_secret(path: &Path, day: usize, word_list: &word::WordList<5>) -> word::Word<5> {
+    let plan = std::fs::read_to_string(path).unwrap_or_else(|error| exit_with(error));
+    let line = plan
+        .lines()
+        .nth(day.wrapping_sub(1))
+        .unwrap_or_else(|| exit_with(format!("practice plan has no day {}", day)));
+    let secret = word::Word::try_from(line.trim())
+        .unwrap_or_else(|_| exit_with(format!("bad practice secret {:?}", line)));
+    if !word_list.0.contains(&secret) {
+        exit_with(format!(
+            "practice secret for day {} ({}) isn't in the word list",
+            day, secret
+        ));
+    }
+    secret
+}
+
 fn main() {
-    let word_list: word::WordList<5> = word_list::WORD_LIST
-        .iter()
-        .map(|s| word::Word::<5>::try_from(*s).unwrap())
-        .collect();
-    let strategy = strategy::StdinThenSolver::new(word_list.clone());
-    let engine = engine::StdinEvaluator;
-    run_game(engine, strategy);
+    // Only warnings unless RUST_LOG asks for more, e.g. RUST_LOG=debug to
+    // follow each guess.
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn")),
+        )
+        .with_writer(std::io::stderr)
+        .without_time()
+        .init();
+    let cli = Cli::parse();
+    // Before loading the extra words, so a broken file is reported rather
+    // than stopping the check.
+    if let Some(Command::Doctor { artifacts }) = &cli.command {
+        let report = doctor::run(
+            extra_words_path().as_deref(),
+            default_history_path().as_deref(),
+            artifacts,
+        );
+        println!("{}", report);
+        std::process::exit(if report.passed() { 0 } else { 1 });
+    }
+    // Before loading the five-letter list, which a file of other lengths
+    // would fail.
+    if let Some(Command::Solve {
+        secret,
+        strategy,
+        guesses,
+        tiles,
+        length: Some(length),
+        ..
+    }) = &cli.command
+    {
+        let path = cli
+            .words
+            .as_ref()
+            .unwrap_or_else(|| exit_with("--length needs a word file from --words"));
+        let secret = secret.as_ref().expect("clap requires a secret");
+        solve_length(*length, path, secret, *strategy, *guesses, *tiles);
+        return;
+    }
+    #[cfg(feature = "serde")]
+    if let Some(Command::Sweep {
+        strategies,
+        games,
+        guesses,
+        seed,
+        lengths: Some(lengths),
+        ..
+    }) = &cli.command
+    {
+        let path = cli
+            .words
+            .as_ref()
+            .unwrap_or_else(|| exit_with("--lengths needs a word file from --words"));
+        sweep_lengths(lengths.clone(), path, strategies, *games, *guesses, *seed);
+        return;
+    }
+    let mut word_list: word::WordList<5> = match &cli.words {
+        Some(path) => word::WordList::from_path(path)
+            .unwrap_or_else(|error| exit_with(format!("{}: {}", path.display(), error))),
+        None => word_list::WORD_LIST
+            .iter()
+            .map(|s| word::Word::<5>::try_from(*s).unwrap())
+            .collect(),
+    };
+    if let Some(path) = extra_words_path().filter(|path| path.is_file()) {
+        let text = std::fs::read_to_string(&path).unwrap_or_else(|error| exit_with(error));
+        let extra = dictionary::parse_extra_words(&text)
+            .unwrap_or_else(|error| exit_with(format!("{}: {}", path.display(), error)));
+        word_list = word::WordList::merge(&[word_list, extra], Default::default());
+    }
+
+    let words_path = cli.words;
+    let command = cli.command.unwrap_or(Command::Assist {
+        trace: false,
+        trace_file: None,
+        diverse: false,
+        compare: None,
+        max_think_ms: None,
+        histogram: false,
+        eliminated: None,
+        no_warm_up: false,
+        event_log: None,
+        save_record: None,
+        clues: Vec::new(),
+        exclude: Vec::new(),
+        rules: GuessRuleArgs::default(),
+        tiles: tiles::TileStyle::default(),
+        book: None,
+    });
+    match command {
+        Command::Assist {
+            book: Some(path), ..
+        } => {
+            let file = std::fs::File::open(&path).unwrap_or_else(|error| exit_with(error));
+            let tree = decision_tree::DecisionTree::read(std::io::BufReader::new(file), &word_list)
+                .unwrap_or_else(|error| exit_with(format!("{}: {}", path.display(), error)));
+            let strategy =
+                decision_tree::DecisionTreeStrategy::new(std::sync::Arc::new(tree), word_list);
+            let transcript = try_run_game(engine::StdinEvaluator, strategy)
+                .unwrap_or_else(|error| exit_with(error));
+            if let Some(error) = transcript.gave_up {
+                exit_with(error);
+            }
+        }
+        Command::Assist {
+            trace,
+            trace_file,
+            diverse,
+            compare,
+            max_think_ms,
+            histogram,
+            eliminated,
+            no_warm_up,
+            event_log,
+            save_record,
+            clues,
+            exclude,
+            rules,
+            tiles,
+            book: None,
+        } => {
+            let mut strategy = strategy::StdinThenSolver::new(word_list.clone());
+            strategy.set_answer_policy(&answer_policy(&exclude));
+            if !no_warm_up {
+                strategy.warm_up();
+            }
+            if let Some(ms) = max_think_ms {
+                strategy.set_think_budget(std::time::Duration::from_millis(ms));
+            }
+            if histogram {
+                strategy.show_histogram();
+            }
+            if let Some(max_words) = eliminated {
+                strategy.show_eliminated(max_words);
+            }
+            strategy.set_guess_rules(rules.to_rules(&word_list));
+            strategy.set_tile_style(tiles);
+            let mut board = board::Board::new();
+            for (guess, score) in &clues {
+                board.push(*guess, *score, None);
+            }
+            if !clues.is_empty() {
+                let clues = clues
+                    .into_iter()
+                    .map(|(guess, score)| word::Clue::Scored(guess, score))
+                    .collect::<Vec<_>>();
+                strategy::Strategy::prime(&mut strategy, &clues);
+            }
+            if let Some(advisors) = compare {
+                strategy.set_advisors([advisors[0], advisors[1]]);
+            }
+            if let Some(path) = trace_file {
+                let file = std::fs::File::create(path).unwrap_or_else(|error| exit_with(error));
+                strategy.set_trace(Box::new(file));
+            } else if trace {
+                strategy.set_trace(Box::new(std::io::stdout()));
+            }
+            if diverse {
+                strategy.set_diverse_trace();
+            }
+            let engine = event_log::LoggedEngine::new(engine::StdinEvaluator);
+            let result = run_game_with_observer(&engine, strategy, ThinkTimeNotice);
+            // The log matters most when the game went wrong, so write it
+            // before reporting any error.
+            if let Some(path) = event_log {
+                let mut file = std::fs::File::create(path).unwrap_or_else(|error| exit_with(error));
+                engine
+                    .write_log(&mut file)
+                    .unwrap_or_else(|error| exit_with(error));
+            }
+            if let Some(path) = save_record {
+                for row in engine.board().rows() {
+                    board.push(row.guess, row.score, None);
+                }
+                let record = record::GameRecord {
+                    date: Some(nyt::Date::today().to_string()),
+                    puzzle: None,
+                    max_guesses: 6,
+                    board,
+                    think_times: Vec::new(),
+                };
+                std::fs::write(&path, record.to_string()).unwrap_or_else(|error| exit_with(error));
+            }
+            let transcript = result.unwrap_or_else(|error| exit_with(error));
+            if let Some(error) = transcript.gave_up {
+                exit_with(error);
+            }
+        }
+        Command::Play {
+            hints,
+            profile,
+            guesses,
+            reject_repeats,
+            hard_mode,
+            free_letters,
+            seed,
+            time_attack,
+            tiles,
+            save_record,
+            puzzle,
+            schedule,
+            date,
+            history,
+            stats,
+            practice,
+            day,
+        } => {
+            let mut profile = profile.unwrap_or_default();
+            if let Some(guesses) = guesses {
+                profile.num_guesses = guesses;
+            }
+            profile.reject_repeats |= reject_repeats;
+            profile.hard_mode |= hard_mode;
+            let guesses = profile.num_guesses;
+            let (answers, allowed) = match profile.words {
+                profile::ProfileWords::Nyt => (
+                    nyt::ANSWERS
+                        .iter()
+                        .map(|s| word::Word::try_from(*s).unwrap())
+                        .collect(),
+                    word_list.clone(),
+                ),
+                profile::ProfileWords::Dictionary => (word_list.clone(), word_list.clone()),
+                profile::ProfileWords::Custom => {
+                    let path = words_path.unwrap_or_else(|| {
+                        exit_with(format!("the {} profile needs --words", profile))
+                    });
+                    // The site's own list, without the extra words.
+                    let words = word::WordList::from_path(&path).unwrap_or_else(|error| {
+                        exit_with(format!("{}: {}", path.display(), error))
+                    });
+                    (words.clone(), words)
+                }
+            };
+            // Let the player type any word they know, so the profile decides
+            // what happens to ones the site doesn't take.
+            let word_list =
+                word::WordList::merge(&[allowed.clone(), word_list], Default::default());
+            let seed = seed.unwrap_or_else(rand::random);
+            let history_path = history.or_else(default_history_path);
+            let mut played = match &history_path {
+                Some(path) => {
+                    history::PlayedPuzzles::load(path).unwrap_or_else(|error| exit_with(error))
+                }
+                None => history::PlayedPuzzles::new(),
+            };
+            if let Some(result) = puzzle.and_then(|puzzle| played.result(puzzle)) {
+                match result {
+                    Some(guesses) => println!("You already solved this puzzle in {}.", guesses),
+                    None => println!("You already played this puzzle and missed it."),
+                }
+                if stats || !confirm("Play it again anyway? [y/n] (n shows your stats)") {
+                    print!("{}", played.stats());
+                    return;
+                }
+            }
+            let secret = match (puzzle, schedule, practice, day) {
+                (_, Some(path), _, _) => {
+                    scheduled_secret(&path, date.unwrap_or_else(nyt::Date::today), &word_list)
+                }
+                (Some(puzzle), _, _, _) => nyt::answer(puzzle).unwrap_or_else(|| {
+                    exit_with(format!(
+                        "puzzle {} is past the bundled answers (last is {})",
+                        puzzle,
+                        nyt::ANSWERS.len() - 1
+                    ))
+                }),
+                (None, None, Some(path), Some(day)) => practice_secret(&path, day, &word_list),
+                _ => engine::random_secret(&answers, seed, None)
+                    .unwrap_or_else(|| exit_with("there are no answers to pick a secret from")),
+            };
+            let mut engine = profile.engine(secret, allowed);
+            for letter in 0..free_letters {
+                engine = engine.reveal_free_letter(seed.wrapping_add(1 + letter as u64));
+            }
+            let mut guesser = play::AssistedGuesser::new(word_list, hints);
+            if time_attack {
+                guesser.time_guesses();
+            }
+            guesser.set_tile_style(tiles);
+            let transcript =
+                try_run_game(engine, &mut guesser).unwrap_or_else(|error| exit_with(error));
+            let won = transcript.won;
+            if !won {
+                println!("The word was {}", secret);
+            }
+            let mut record = guesser.to_record(&transcript, guesses);
+            println!(
+                "{}",
+                play::share_grid(&record.board, won, guesses, guesser.hints_used(), tiles)
+            );
+            record.puzzle = puzzle;
+            record.date = Some(nyt::Date::today().to_string());
+            if let Some(total) = record.total_think_time() {
+                for (turn, time) in record.think_times.iter().enumerate() {
+                    println!("  guess {}: {:.1}s", turn + 1, time.as_secs_f64());
+                }
+                println!("Total time {}", history::format_time(total));
+            }
+            if let (Some(puzzle), Some(path)) = (puzzle, history_path) {
+                let guesses = won.then(|| record.board.len());
+                match record.total_think_time() {
+                    Some(total) => played.record_timed(puzzle, guesses, total),
+                    None => played.record(puzzle, guesses),
+                }
+                played.save(&path).unwrap_or_else(|error| exit_with(error));
+            }
+            if let Some(path) = save_record {
+                std::fs::write(&path, record.to_string()).unwrap_or_else(|error| exit_with(error));
+            }
+        }
+        Command::Solve {
+            secret,
+            strategy,
+            guesses,
+            nyt_answers,
+            exclude,
+            frequencies,
+            hard_mode,
+            tiles,
+            rules,
+            ..
+        } => {
+            if hard_mode && !strategy.info().supports_hard_mode {
+                exit_with(format!(
+                    "the {} strategy can't play hard mode; try another --strategy",
+                    strategy
+                ));
+            }
+            let answers = if nyt_answers {
+                nyt::ANSWERS
+                    .iter()
+                    .filter_map(|answer| word::Word::try_from(*answer).ok())
+                    .filter(|answer| word_list.0.contains(answer))
+                    .collect()
+            } else {
+                word_list.clone()
+            };
+            #[cfg(feature = "fetch")]
+            let secret = secret.or_else(|| {
+                let cache = daily::DailyCache::new(daily_cache_dir().unwrap_or_else(|| {
+                    exit_with("no cache folder for daily puzzles (HOME isn't set)")
+                }));
+                let puzzle = cache
+                    .get(nyt::Date::today())
+                    .unwrap_or_else(|error| exit_with(error));
+                Some(puzzle.solution)
+            });
+            let secret = secret.expect("clap requires a secret");
+            let secret = word::Word::try_from(secret.to_ascii_lowercase().as_str())
+                .ok()
+                .filter(|secret| answers.0.contains(secret))
+                .unwrap_or_else(|| {
+                    exit_with(format!(
+                        "{:?} isn't in the {}",
+                        secret,
+                        if nyt_answers {
+                            "NYT answers"
+                        } else {
+                            "word list"
+                        }
+                    ))
+                });
+            // The secret is what it is; only the solver goes by the policy.
+            let answers = answers.filtered(&answer_policy(&exclude));
+            let rules = rules.to_rules(&word_list);
+            let solver = match frequencies {
+                Some(path) => {
+                    if strategy != strategy::Solver::Entropy {
+                        exit_with("--frequencies only works with the entropy strategy");
+                    }
+                    let text =
+                        std::fs::read_to_string(&path).unwrap_or_else(|error| exit_with(error));
+                    let frequencies =
+                        frequency::Frequencies::parse_csv(&text).unwrap_or_else(|error| {
+                            exit_with(format!("{}: {}", path.display(), error))
+                        });
+                    let mut solver = strategy::EntropyStrategy::with_guess_list(
+                        word_list.clone(),
+                        answers.clone(),
+                    );
+                    solver.set_guess_rules(rules);
+                    solver.set_frequencies(std::sync::Arc::new(frequencies));
+                    Box::new(solver)
+                }
+                None => strategy.build_with_answers(word_list.clone(), answers.clone(), rules),
+            };
+            let mut engine = engine::StandardEngine::new(secret, word_list, guesses);
+            let solver = if hard_mode {
+                engine = engine.hard_mode();
+                Box::new(hard_mode::HardMode::new(solver, answers))
+            } else {
+                solver
+            };
+            let transcript = try_run_game(engine, solver).unwrap_or_else(|error| exit_with(error));
+            for (guess, score) in transcript.scored_guesses() {
+                println!("{}", tiles.row(&guess, &score));
+            }
+            match transcript.outcome() {
+                engine::GameOutcome::Won => {
+                    println!("Solved in {}/{}", transcript.guesses_used(), guesses)
+                }
+                engine::GameOutcome::Lost => {
+                    println!("Out of guesses; the word was {}", secret);
+                    std::process::exit(1);
+                }
+                engine::GameOutcome::GaveUp(error) => exit_with(error),
+            }
+        }
+        Command::League { command } => match command {
+            LeagueCommand::Import {
+                league: path,
+                player,
+                puzzle,
+                time,
+            } => {
+                let player = player.trim();
+                if player.is_empty() {
+                    exit_with("player name can't be blank");
+                }
+                let mut grid = String::new();
+                std::io::Read::read_to_string(&mut std::io::stdin(), &mut grid)
+                    .unwrap_or_else(|error| exit_with(error));
+                let shared =
+                    league::parse_share_grid(&grid).unwrap_or_else(|error| exit_with(error));
+                let puzzle = shared.puzzle.or(puzzle).unwrap_or_else(|| {
+                    exit_with("the grid has no puzzle number; pass one with --puzzle")
+                });
+                let mut league =
+                    league::League::load(&path).unwrap_or_else(|error| exit_with(error));
+                league.record(
+                    puzzle,
+                    player,
+                    league::Entry {
+                        guesses: shared.guesses,
+                        seconds: time,
+                    },
+                );
+                league.save(&path).unwrap_or_else(|error| exit_with(error));
+                print!("{}", league.standings_table());
+            }
+            LeagueCommand::Standings { league: path } => {
+                let league = league::League::load(&path).unwrap_or_else(|error| exit_with(error));
+                print!("{}", league.standings_table());
+            }
+        },
+        #[cfg(feature = "serde")]
+        Command::Bench {
+            strategy,
+            params,
+            games,
+            guesses,
+            seed,
+            guess_error,
+            score_error,
+            out,
+            from_manifest,
+            rules,
+        } => {
+            let print_results = |results: &eval::EvaluationSummary| {
+                println!(
+                    "{} games, won {:.1}%{}",
+                    results.games,
+                    100.0 * results.win_rate().unwrap_or(0.0),
+                    per_win(results.average_guesses())
+                )
+            };
+            if let Some(path) = from_manifest {
+                let json = std::fs::read_to_string(&path).unwrap_or_else(|error| exit_with(error));
+                let manifest = manifest::RunManifest::from_json(&json)
+                    .unwrap_or_else(|error| exit_with(error));
+                if !manifest.same_version() {
+                    eprintln!(
+                        "warning: manifest was written by version {}; results may differ",
+                        manifest.crate_version
+                    );
+                }
+                let results = manifest
+                    .run(&word_list)
+                    .unwrap_or_else(|error| exit_with(error));
+                print_results(&results);
+                match manifest.results {
+                    Some(recorded) if recorded == results => {
+                        println!("Reproduced the recorded results.")
+                    }
+                    Some(recorded) => {
+                        print!("Recorded results differ: ");
+                        print_results(&recorded);
+                    }
+                    None => {}
+                }
+                return;
+            }
+
+            let mut manifest = manifest::RunManifest::new(
+                &strategy,
+                params.into_iter().collect(),
+                &word_list,
+                games,
+                guesses,
+                seed,
+            );
+            // Check the words up front, and save them in a canonical order.
+            let checked = rules.to_rules(&word_list);
+            manifest.never_guess = checked
+                .never_guess
+                .iter()
+                .map(|word| word.to_string())
+                .collect();
+            manifest.never_guess.sort();
+            manifest.always_open = checked.always_open.map(|word| word.to_string());
+            manifest.human_errors = noise::HumanErrors {
+                guess_error,
+                score_error,
+            };
+            let results = manifest
+                .run(&word_list)
+                .unwrap_or_else(|error| exit_with(error));
+            print_results(&results);
+            manifest.results = Some(results);
+            let path = out.join(manifest::FILE_NAME);
+            std::fs::write(&path, manifest.to_json() + "\n")
+                .unwrap_or_else(|error| exit_with(error));
+            println!("Wrote {}", path.display());
+        }
+        #[cfg(feature = "serde")]
+        Command::Sweep {
+            mut dictionaries,
+            mut strategies,
+            games,
+            guesses,
+            seed,
+            ..
+        } => {
+            if strategies.is_empty() {
+                strategies.push("simple".to_owned());
+            }
+            if dictionaries.is_empty() {
+                dictionaries.push("en-answers".to_owned());
+            }
+            let dictionaries = dictionaries
+                .iter()
+                .map(|spec| load_dictionary(spec))
+                .collect::<Vec<_>>();
+            let report = dictionary::sweep(&dictionaries, &strategies, games, guesses, seed)
+                .unwrap_or_else(|error| exit_with(error));
+            print!("{}", report);
+        }
+        Command::Traps {
+            opener,
+            guesses,
+            nyt_answers,
+        } => {
+            let opener = word::Word::try_from(opener.to_ascii_lowercase().as_str())
+                .unwrap_or_else(|error| exit_with(format!("{:?}: {}", opener, error)));
+            if !word_list.0.contains(&opener) {
+                exit_with(format!("{:?} isn't in the dictionary", opener.to_string()));
+            }
+            let answers = if nyt_answers {
+                nyt::ANSWERS
+                    .iter()
+                    .filter_map(|answer| word::Word::try_from(*answer).ok())
+                    .filter(|answer| word_list.0.contains(answer))
+                    .collect()
+            } else {
+                word_list.clone()
+            };
+            let traps = hard_mode::traps(&opener, &word_list, &answers, guesses);
+            println!(
+                "{} hard-mode trap{} after {}",
+                traps.len(),
+                if traps.len() == 1 { "" } else { "s" },
+                opener
+            );
+            for trap in traps {
+                println!("  {}", trap);
+            }
+        }
+        #[cfg(feature = "serde")]
+        Command::CrossValidate {
+            strategy,
+            params,
+            openers,
+            guesses,
+            seed,
+        } => {
+            let answers = nyt::ANSWERS
+                .iter()
+                .map(|s| word::Word::try_from(*s).unwrap())
+                .collect::<word::WordList<5>>();
+            let candidates = openers
+                .iter()
+                .map(|opener| {
+                    let mut manifest = manifest::RunManifest::new(
+                        &strategy,
+                        params.iter().cloned().collect(),
+                        &word_list,
+                        answers.0.len(),
+                        guesses,
+                        seed,
+                    );
+                    manifest.always_open = Some(opener.to_ascii_lowercase());
+                    // Check each one up front, so a bad one is an error
+                    // rather than a panic inside the evaluation.
+                    manifest
+                        .strategy(&word_list)
+                        .unwrap_or_else(|error| exit_with(error));
+                    manifest
+                })
+                .collect::<Vec<_>>();
+            let result = eval::cross_validate(
+                &candidates,
+                |manifest| manifest.strategy(&word_list).expect("already checked"),
+                &answers,
+                &word_list,
+                guesses,
+                seed,
+            )
+            .unwrap_or_else(|| exit_with("no openers to choose from"));
+            let print_results = |label: &str, results: &eval::EvaluationSummary| {
+                println!(
+                    "{}: {} games, won {:.1}%{}",
+                    label,
+                    results.games,
+                    100.0 * results.win_rate().unwrap_or(0.0),
+                    per_win(results.average_guesses())
+                )
+            };
+            println!(
+                "Chose {}",
+                result.chosen.always_open.as_deref().unwrap_or_default()
+            );
+            print_results("Training", &result.train);
+            print_results("Held out", &result.test);
+            match result.generalization_gap() {
+                Some(gap) => println!("Generalization gap: {:+.3} guesses per win", gap),
+                None => println!("Generalization gap: n/a, a half won no games"),
+            }
+        }
+        #[cfg(feature = "serde")]
+        Command::Schema { name } => match name {
+            Some(schema) => print!("{}", schema.text()),
+            None => {
+                for schema in schema::Schema::ALL {
+                    println!("{} (v{})", schema, schema::VERSION);
+                }
+            }
+        },
+        Command::OpeningBook { out, advisor } => {
+            let answers = nyt::ANSWERS
+                .iter()
+                .map(|s| word::Word::try_from(*s).unwrap())
+                .collect::<word::WordList<5>>();
+            let tree = decision_tree::DecisionTree::build(&word_list, &answers, &advisor);
+            let file = std::fs::File::create(&out).unwrap_or_else(|error| exit_with(error));
+            tree.write(std::io::BufWriter::new(file), &word_list)
+                .unwrap_or_else(|error| exit_with(error));
+            let total = tree
+                .total_guesses(&answers)
+                .expect("the tree finds every answer");
+            println!(
+                "Wrote the book to {}: at most {} guesses, {:.3} on average",
+                out.display(),
+                tree.depth(),
+                total as f64 / answers.0.len() as f64
+            );
+        }
+        Command::Check { word: text, clues } => {
+            let candidate = word::Word::try_from(text.to_ascii_lowercase().as_str())
+                .unwrap_or_else(|_| exit_with(format!("{:?} isn't a five-letter word", text)));
+            if !word_list.0.contains(&candidate) {
+                println!("Note: {} isn't in the dictionary.", candidate);
+            }
+            match word::first_conflict(&candidate, &clues) {
+                None => println!("{} is still possible.", candidate),
+                Some(index) => {
+                    let (guess, score) = &clues[index];
+                    println!(
+                        "{} is ruled out by {} ({}): it would have scored {}.",
+                        candidate,
+                        guess,
+                        word::format_score(score),
+                        word::format_score(&candidate.evaluate_guess(guess))
+                    );
+                    for reason in word::conflict_reasons(&candidate, guess, score) {
+                        println!("  {} {}.", candidate, reason);
+                    }
+                }
+            }
+        }
+        Command::Secrets {
+            guesses,
+            nyt_answers,
+        } => {
+            let guesses = guesses
+                .iter()
+                .map(|text| {
+                    word::Word::try_from(text.to_ascii_lowercase().as_str())
+                        .unwrap_or_else(|error| exit_with(format!("{:?}: {}", text, error)))
+                })
+                .collect::<Vec<_>>();
+            let mut grid = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut grid)
+                .unwrap_or_else(|error| exit_with(error));
+            let rows = grid
+                .lines()
+                .filter_map(tiles::TileStyle::parse_tiles)
+                .collect::<Vec<_>>();
+            let answers = if nyt_answers {
+                nyt::ANSWERS
+                    .iter()
+                    .filter_map(|answer| word::Word::try_from(*answer).ok())
+                    .collect()
+            } else {
+                word_list
+            };
+            let secrets = inverse::consistent_secrets(&guesses, &rows, &answers)
+                .unwrap_or_else(|error| exit_with(error));
+            if secrets.0.is_empty() {
+                exit_with("no secret fits that game; a guess or a row is probably wrong");
+            }
+            for secret in &secrets.0 {
+                println!("{}", secret);
+            }
+        }
+        Command::Witness {
+            answer,
+            opener,
+            top,
+            nyt_answers,
+        } => {
+            let word = |text: &String| {
+                word::Word::try_from(text.to_ascii_lowercase().as_str())
+                    .ok()
+                    .filter(|word| word_list.0.contains(word))
+                    .unwrap_or_else(|| exit_with(format!("{:?} isn't in the dictionary", text)))
+            };
+            let answer = word(&answer);
+            let answers = if nyt_answers {
+                nyt::ANSWERS
+                    .iter()
+                    .filter_map(|answer| word::Word::try_from(*answer).ok())
+                    .collect()
+            } else {
+                word_list.clone()
+            };
+            let guess_list = match &opener {
+                Some(opener) if word(opener) == answer => {
+                    exit_with(format!("opening with {} solves it in one", answer))
+                }
+                Some(opener) => word::WordList(vec![word(opener)]),
+                None => word_list.clone(),
+            };
+            let witnesses = inverse::two_guess_witnesses(&answer, &guess_list, &answers);
+            if opener.is_none() {
+                let certain = witnesses
+                    .iter()
+                    .filter(|witness| witness.remaining == 1)
+                    .count();
+                println!(
+                    "{} of {} openers leave {} as the only answer",
+                    certain,
+                    witnesses.len(),
+                    answer
+                );
+            }
+            for witness in witnesses.iter().take(top) {
+                println!("{}", witness);
+            }
+        }
+        Command::Heatmap {
+            bigrams,
+            nyt_answers,
+        } => {
+            let words = if nyt_answers {
+                nyt::ANSWERS
+                    .iter()
+                    .filter_map(|answer| word::Word::try_from(*answer).ok())
+                    .collect()
+            } else {
+                word_list
+            };
+            let stats = letter_stats::LetterStats::new(&words);
+            print!("letter");
+            for position in 1..=5 {
+                print!(" {:>5}", position);
+            }
+            println!("  words");
+            for letter in stats.letters() {
+                print!("{:<6}", letter);
+                for position in 0..5 {
+                    print!(" {:>5}", stats.at(position, letter));
+                }
+                println!("  {:>5}", stats.containing(letter));
+            }
+            if bigrams > 0 {
+                println!();
+                for ((first, second), count) in stats.top_bigrams(bigrams) {
+                    println!("{}{} {}", first, second, count);
+                }
+            }
+        }
+        Command::ScoreFile { input, out } => {
+            let input: Box<dyn std::io::BufRead> = match input {
+                Some(path) => Box::new(std::io::BufReader::new(
+                    std::fs::File::open(path).unwrap_or_else(|error| exit_with(error)),
+                )),
+                None => Box::new(std::io::stdin().lock()),
+            };
+            let out: Box<dyn std::io::Write> = match out {
+                Some(path) => Box::new(std::io::BufWriter::new(
+                    std::fs::File::create(path).unwrap_or_else(|error| exit_with(error)),
+                )),
+                None => Box::new(std::io::stdout().lock()),
+            };
+            dataset::append_scores(input, out).unwrap_or_else(|error| exit_with(error));
+        }
+        Command::Verify { record } => {
+            let text = std::fs::read_to_string(&record).unwrap_or_else(|error| exit_with(error));
+            let record = record::GameRecord::<5>::parse(&text)
+                .unwrap_or_else(|error| exit_with(format!("{}: {}", record.display(), error)));
+            let mut grid = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut grid)
+                .unwrap_or_else(|error| exit_with(error));
+            // Skips the share grid's header and anything else that isn't a
+            // row of tiles.
+            let rows = grid
+                .lines()
+                .filter_map(tiles::TileStyle::parse_tiles)
+                .collect::<Vec<_>>();
+            match record.first_divergence(&rows) {
+                Some(divergence) => exit_with(divergence),
+                None => println!("All {} rows match", rows.len()),
+            }
+        }
+        Command::Share { record, tiles } => {
+            let text = std::fs::read_to_string(&record).unwrap_or_else(|error| exit_with(error));
+            let record = record::GameRecord::<5>::parse(&text)
+                .unwrap_or_else(|error| exit_with(format!("{}: {}", record.display(), error)));
+            println!("{}", record.share_grid(tiles));
+        }
+        Command::AnalyzeArchive { dir } => {
+            let records = analysis::load_archive(&dir).unwrap_or_else(|error| exit_with(error));
+            print!(
+                "{}",
+                analysis::ArchiveSummary::from_records(&records, &word_list)
+            );
+        }
+        Command::ImportRecords { export, dir } => {
+            let text = std::fs::read_to_string(&export).unwrap_or_else(|error| exit_with(error));
+            let records = import::import_csv::<5>(&text)
+                .unwrap_or_else(|error| exit_with(format!("{}: {}", export.display(), error)));
+            std::fs::create_dir_all(&dir).unwrap_or_else(|error| exit_with(error));
+            for (index, record) in records.iter().enumerate() {
+                let path = dir.join(format!("imported-{:04}.txt", index + 1));
+                // Never overwrite records already in the folder.
+                let mut file = std::fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&path)
+                    .unwrap_or_else(|error| exit_with(format!("{}: {}", path.display(), error)));
+                std::io::Write::write_all(&mut file, record.to_string().as_bytes())
+                    .unwrap_or_else(|error| exit_with(error));
+            }
+            println!("Imported {} games into {}", records.len(), dir.display());
+        }
+        Command::PracticePlan { dir, out, seed } => {
+            let records = analysis::load_archive(&dir).unwrap_or_else(|error| exit_with(error));
+            let estimator = practice::DifficultyEstimator::new(word_list.clone());
+            let weaknesses = practice::find_weaknesses(&records, &estimator);
+            if weaknesses.is_empty() {
+                println!("No particular weaknesses found; practicing hard words.");
+            }
+            for weakness in &weaknesses {
+                println!("Practicing {}", weakness);
+            }
+            let plan = practice::weekly_plan(&weaknesses, &word_list, &estimator, seed)
+                .into_iter()
+                .map(|word| format!("{}\n", word))
+                .collect::<String>();
+            std::fs::write(&out, plan).unwrap_or_else(|error| exit_with(error));
+            println!(
+                "Wrote the plan to {}; play it with `play --practice {} --day N`",
+                out.display(),
+                out.display()
+            );
+        }
+        Command::Doctor { .. } => unreachable!("handled before loading the word list"),
+    }
 }
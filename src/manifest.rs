@@ -0,0 +1,283 @@
+//! Run manifests: everything needed to rerun a benchmark exactly, saved as
+//! `run-manifest.json` next to its results.
+
+use crate::adaptive::AdaptiveStrategy;
+use crate::eval::{evaluate_games, EvaluationSummary};
+use crate::noise::{HumanErrors, Noisy};
+use crate::optimal::{OptimalStrategy, DEFAULT_MAX_EXACT, DEFAULT_SHORTLIST};
+use crate::strategy::{EntropyStrategy, GuessRules, HumanLikeStrategy, SimpleStrategy, Strategy};
+use crate::word::*;
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+pub const FILE_NAME: &str = "run-manifest.json";
+
+/// Strategies a manifest can name, and the parameters each accepts.
+pub const STRATEGIES: &[(&str, &[&str])] = &[
+    ("simple", &[]),
+    ("adaptive", &[]),
+    ("entropy", &[]),
+    ("human", &["mistake_rate", "seed"]),
+    ("optimal", &["max_exact", "shortlist"]),
+];
+
+#[derive(Debug)]
+pub enum ManifestError {
+    Json(serde_json::Error),
+    UnknownStrategy(String),
+    UnknownParameter(String),
+    BadParameter {
+        name: String,
+        value: String,
+    },
+    /// A word in the guess rules that isn't in the word list.
+    UnknownWord(String),
+    /// The manifest was made with a different word list than the one it's
+    /// being rerun with.
+    WordListMismatch {
+        expected: String,
+        found: String,
+    },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Json(error) => write!(f, "bad manifest: {}", error),
+            Self::UnknownStrategy(name) => write!(
+                f,
+                "unknown strategy {:?} (expected one of {})",
+                name,
+                STRATEGIES
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::UnknownParameter(name) => write!(f, "unknown parameter {:?}", name),
+            Self::BadParameter { name, value } => {
+                write!(f, "bad value {:?} for parameter {:?}", value, name)
+            }
+            Self::UnknownWord(word) => write!(f, "{:?} isn't in the word list", word),
+            Self::WordListMismatch { expected, found } => write!(
+                f,
+                "the manifest was made with word list {}, but the current list is {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {}
+
+impl From<serde_json::Error> for ManifestError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunManifest {
+    pub strategy: String,
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+    /// [`WordList::content_hash`] of the word list, in hex.
+    pub word_list_hash: String,
+    /// Number of secrets sampled from the word list.
+    pub games: usize,
+    pub max_guesses: usize,
+    /// Seed for sampling the secrets.
+    pub seed: u64,
+    pub crate_version: String,
+    /// Words the strategy may never guess.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub never_guess: Vec<String>,
+    /// The word the strategy must open every game with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub always_open: Option<String>,
+    /// Simulated mistakes by the player following the strategy, if any.
+    #[serde(default, skip_serializing_if = "HumanErrors::is_none")]
+    pub human_errors: HumanErrors,
+    /// What the run scored, once it's been run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub results: Option<EvaluationSummary>,
+}
+
+impl RunManifest {
+    pub fn new<const WORD_LENGTH: usize>(
+        strategy: &str,
+        parameters: BTreeMap<String, String>,
+        word_list: &WordList<WORD_LENGTH>,
+        games: usize,
+        max_guesses: usize,
+        seed: u64,
+    ) -> Self {
+        Self {
+            strategy: strategy.to_owned(),
+            parameters,
+            word_list_hash: format!("{:016x}", word_list.content_hash()),
+            games,
+            max_guesses,
+            seed,
+            crate_version: env!("CARGO_PKG_VERSION").to_owned(),
+            never_guess: Vec::new(),
+            always_open: None,
+            human_errors: HumanErrors::default(),
+            results: None,
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ManifestError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("manifests always serialize")
+    }
+
+    /// Whether the manifest was written by this version of the crate. Other
+    /// versions can still rerun it, but may not reproduce its results.
+    pub fn same_version(&self) -> bool {
+        self.crate_version == env!("CARGO_PKG_VERSION")
+    }
+
+    /// The secrets the run plays, drawn from `word_list` with the seed.
+    pub fn secrets<const WORD_LENGTH: usize>(
+        &self,
+        word_list: &WordList<WORD_LENGTH>,
+    ) -> WordList<WORD_LENGTH> {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        word_list
+            .0
+            .choose_multiple(&mut rng, self.games)
+            .copied()
+            .collect()
+    }
+
+    fn parameter<T: std::str::FromStr>(&self, name: &str, default: T) -> Result<T, ManifestError> {
+        match self.parameters.get(name) {
+            None => Ok(default),
+            Some(value) => value.parse().map_err(|_| ManifestError::BadParameter {
+                name: name.to_owned(),
+                value: value.clone(),
+            }),
+        }
+    }
+
+    /// The manifest's guess rules, checked against `word_list`.
+    pub fn guess_rules<const WORD_LENGTH: usize>(
+        &self,
+        word_list: &WordList<WORD_LENGTH>,
+    ) -> Result<GuessRules<WORD_LENGTH>, ManifestError> {
+        let word = |text: &String| {
+            Word::try_from(text.as_str())
+                .ok()
+                .filter(|word| word_list.0.contains(word))
+                .ok_or_else(|| ManifestError::UnknownWord(text.clone()))
+        };
+        Ok(GuessRules {
+            never_guess: self
+                .never_guess
+                .iter()
+                .map(word)
+                .collect::<Result<_, _>>()?,
+            always_open: self.always_open.as_ref().map(word).transpose()?,
+        })
+    }
+
+    /// A fresh instance of the manifest's strategy.
+    pub fn strategy<const WORD_LENGTH: usize>(
+        &self,
+        word_list: &WordList<WORD_LENGTH>,
+    ) -> Result<Box<dyn Strategy<WORD_LENGTH>>, ManifestError> {
+        let (_, known) = STRATEGIES
+            .iter()
+            .find(|(name, _)| *name == self.strategy)
+            .ok_or_else(|| ManifestError::UnknownStrategy(self.strategy.clone()))?;
+        if let Some(name) = self
+            .parameters
+            .keys()
+            .find(|name| !known.contains(&name.as_str()))
+        {
+            return Err(ManifestError::UnknownParameter(name.clone()));
+        }
+
+        let rules = self.guess_rules(word_list)?;
+        let word_list = word_list.clone();
+        Ok(match self.strategy.as_str() {
+            "simple" => {
+                let mut strategy = SimpleStrategy::new(word_list);
+                strategy.set_guess_rules(rules);
+                Box::new(strategy)
+            }
+            "adaptive" => {
+                let mut strategy = AdaptiveStrategy::new(word_list);
+                strategy.set_guess_rules(rules);
+                Box::new(strategy)
+            }
+            "entropy" => {
+                let mut strategy = EntropyStrategy::new(word_list);
+                strategy.set_guess_rules(rules);
+                Box::new(strategy)
+            }
+            "human" => {
+                let mut strategy = HumanLikeStrategy::new(
+                    word_list,
+                    self.parameter("mistake_rate", 0.2)?,
+                    self.parameter("seed", self.seed)?,
+                );
+                strategy.set_guess_rules(rules);
+                Box::new(strategy)
+            }
+            "optimal" => {
+                let mut strategy = OptimalStrategy::new(word_list)
+                    .with_max_exact(self.parameter("max_exact", DEFAULT_MAX_EXACT)?)
+                    .with_shortlist(self.parameter("shortlist", DEFAULT_SHORTLIST)?);
+                strategy.set_guess_rules(rules);
+                Box::new(strategy)
+            }
+            _ => unreachable!("checked against STRATEGIES"),
+        })
+    }
+
+    /// Plays the run on `word_list` and returns how it went, refusing if the
+    /// word list isn't the one the manifest was made with.
+    pub fn run<const WORD_LENGTH: usize>(
+        &self,
+        word_list: &WordList<WORD_LENGTH>,
+    ) -> Result<EvaluationSummary, ManifestError> {
+        let hash = format!("{:016x}", word_list.content_hash());
+        if hash != self.word_list_hash {
+            return Err(ManifestError::WordListMismatch {
+                expected: hash,
+                found: self.word_list_hash.clone(),
+            });
+        }
+        // Check the strategy once up front, so a bad one is an error rather
+        // than a panic inside the evaluation.
+        self.strategy(word_list)?;
+        Ok(evaluate_games(
+            |game| {
+                let make_strategy = || self.strategy(word_list).expect("already checked");
+                if self.human_errors.is_none() {
+                    return make_strategy();
+                }
+                // Each game slips up differently, but the same way every run.
+                let seed = self.seed.wrapping_add(game as u64 + 1);
+                Box::new(Noisy::new(
+                    make_strategy,
+                    word_list.clone(),
+                    self.human_errors,
+                    seed,
+                ))
+            },
+            &self.secrets(word_list),
+            word_list,
+            self.max_guesses,
+        ))
+    }
+}
@@ -0,0 +1,46 @@
+//! Counts of the work a strategy did to come up with a guess, for telling
+//! where the time goes without reaching for a profiler.
+
+use std::fmt;
+use std::ops::AddAssign;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// Words weighed up as the guess.
+    pub candidates_evaluated: usize,
+    /// Scores worked out letter by letter.
+    pub scores_computed: usize,
+    /// Scores looked up in a precomputed table instead.
+    pub cache_hits: usize,
+    /// Words left unweighed because the thinking time ran out.
+    pub candidates_skipped: usize,
+    pub think_time: Duration,
+}
+
+impl AddAssign for Metrics {
+    fn add_assign(&mut self, other: Self) {
+        self.candidates_evaluated += other.candidates_evaluated;
+        self.scores_computed += other.scores_computed;
+        self.cache_hits += other.cache_hits;
+        self.candidates_skipped += other.candidates_skipped;
+        self.think_time += other.think_time;
+    }
+}
+
+impl fmt::Display for Metrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} candidates, {} scores computed, {} looked up, {:.1} ms",
+            self.candidates_evaluated,
+            self.scores_computed,
+            self.cache_hits,
+            self.think_time.as_secs_f64() * 1000.0
+        )?;
+        if self.candidates_skipped > 0 {
+            write!(f, ", {} skipped for time", self.candidates_skipped)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+//! A strategy for Dordle, Quordle and the like, where every guess is scored
+//! on several boards at once; see [`crate::engine::MultiBoardEngine`].
+
+use crate::strategy::{GuessRules, Strategy, StrategyError};
+use crate::word::*;
+use std::collections::HashSet;
+
+/// Keeps the viable words for each board apart, and picks guesses by the
+/// information they reveal across all the boards still unsolved. Whenever a
+/// board is down to one word, that word is played first, since it has to be
+/// guessed sooner or later anyway.
+pub struct MultiBoardStrategy<const WORD_LENGTH: usize, const BOARDS: usize> {
+    word_list: WordList<WORD_LENGTH>,
+    viable_words: [WordList<WORD_LENGTH>; BOARDS],
+    solved: [bool; BOARDS],
+    guessed: HashSet<Word<WORD_LENGTH>>,
+    last_guess: Option<Word<WORD_LENGTH>>,
+    rules: GuessRules<WORD_LENGTH>,
+}
+
+impl<const WORD_LENGTH: usize, const BOARDS: usize> MultiBoardStrategy<WORD_LENGTH, BOARDS> {
+    pub fn new(word_list: WordList<WORD_LENGTH>) -> Self {
+        Self {
+            viable_words: std::array::from_fn(|_| word_list.clone()),
+            word_list,
+            solved: [false; BOARDS],
+            guessed: HashSet::new(),
+            last_guess: None,
+            rules: GuessRules::default(),
+        }
+    }
+
+    pub fn set_guess_rules(&mut self, rules: GuessRules<WORD_LENGTH>) {
+        self.rules = rules;
+    }
+
+    /// The words that could still be the secret on each board.
+    pub fn viable_words(&self) -> &[WordList<WORD_LENGTH>; BOARDS] {
+        &self.viable_words
+    }
+
+    /// Narrows down each board by its score for the last guess.
+    fn narrow(&mut self, scores: &[Option<Score<WORD_LENGTH>>]) {
+        assert_eq!(scores.len(), BOARDS, "expected a score for each board");
+        let last_guess = self.last_guess.expect("should've made a guess by now");
+        for ((viable_words, solved), score) in self
+            .viable_words
+            .iter_mut()
+            .zip(&mut self.solved)
+            .zip(scores)
+        {
+            if let Some(score) = score {
+                viable_words.retain_viable_words(&last_guess, score);
+                *solved = score.iter().all(|tile| *tile == LetterScore::RightPlace);
+            }
+        }
+    }
+
+    fn unsolved(&self) -> impl Iterator<Item = &WordList<WORD_LENGTH>> {
+        self.viable_words
+            .iter()
+            .zip(self.solved)
+            .filter(|(_, solved)| !solved)
+            .map(|(viable_words, _)| viable_words)
+    }
+}
+
+impl<const WORD_LENGTH: usize, const BOARDS: usize> Strategy<WORD_LENGTH>
+    for MultiBoardStrategy<WORD_LENGTH, BOARDS>
+{
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        let known = self
+            .unsolved()
+            .filter(|viable_words| viable_words.0.len() == 1)
+            .map(|viable_words| viable_words.0[0])
+            .find(|word| !self.guessed.contains(word));
+        let guess = match (known, self.rules.always_open) {
+            (Some(word), _) => word,
+            (None, Some(opener)) if self.guessed.is_empty() => opener,
+            (None, _) => {
+                let viable = self
+                    .unsolved()
+                    .flat_map(|viable_words| &viable_words.0)
+                    .collect::<HashSet<_>>();
+                self.word_list
+                    .0
+                    .iter()
+                    .filter(|word| !self.guessed.contains(word) && self.rules.allows(word))
+                    .map(|word| {
+                        let entropy = self
+                            .unsolved()
+                            .map(|viable_words| viable_words.entropy(word))
+                            .sum::<f64>();
+                        (*word, entropy, viable.contains(word))
+                    })
+                    // `max_by` keeps the last of equals; go backwards so the
+                    // earliest word wins exact ties.
+                    .rev()
+                    .max_by(|(_, a, a_viable), (_, b, b_viable)| {
+                        a.total_cmp(b).then(a_viable.cmp(b_viable))
+                    })
+                    .ok_or(StrategyError::OutOfCandidates)?
+                    .0
+            }
+        };
+        self.guessed.insert(guess);
+        self.last_guess = Some(guess);
+        Ok(guess)
+    }
+
+    /// A single score only makes sense when there's a single board.
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        assert_eq!(BOARDS, 1, "expected a score for each of {} boards", BOARDS);
+        self.narrow(&[Some(*score)]);
+    }
+
+    fn receive_scores(
+        &mut self,
+        scores: &[Option<Score<WORD_LENGTH>>],
+    ) -> Result<(), StrategyError> {
+        self.narrow(scores);
+        Ok(())
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        if let Some(suggested) = self.last_guess.replace(*played) {
+            self.guessed.remove(&suggested);
+        }
+        self.guessed.insert(*played);
+        Ok(())
+    }
+}
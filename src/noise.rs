@@ -0,0 +1,206 @@
+//! Simulated human error, for measuring how well a strategy holds up when the
+//! person following it doesn't always do what it says.
+
+use crate::engine::GuessError;
+use crate::strategy::{Strategy, StrategyError};
+use crate::word::*;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashSet;
+
+/// How often the simulated player slips up.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HumanErrors {
+    /// Chance, each turn, of playing some other word that still fits the
+    /// clues instead of the strategy's guess.
+    pub guess_error: f64,
+    /// Chance, each turn, of entering one tile of the score wrong.
+    pub score_error: f64,
+}
+
+impl HumanErrors {
+    pub fn is_none(&self) -> bool {
+        self.guess_error == 0.0 && self.score_error == 0.0
+    }
+}
+
+/// Plays a strategy's guesses the way a fallible person would: sometimes
+/// playing a different word from the candidates, and sometimes passing a
+/// score back with a tile wrong.
+///
+/// Once a wrongly entered score leaves no word that fits the real clues, the
+/// player notices, starts a fresh strategy from `make_inner` and enters the
+/// game so far again, correctly this time.
+pub struct Noisy<S, F, const WORD_LENGTH: usize> {
+    inner: S,
+    make_inner: F,
+    word_list: WordList<WORD_LENGTH>,
+    errors: HumanErrors,
+    rng: ChaCha8Rng,
+    /// The words that fit the scores as entered, which isn't necessarily the
+    /// truth.
+    believed_viable: WordList<WORD_LENGTH>,
+    guessed: HashSet<Word<WORD_LENGTH>>,
+    last_guess: Option<Word<WORD_LENGTH>>,
+    clues: Vec<Clue<WORD_LENGTH>>,
+    /// Every guess played with its real score.
+    history: Vec<(Word<WORD_LENGTH>, Score<WORD_LENGTH>)>,
+    /// Why a fresh strategy couldn't follow the game so far, to give up with
+    /// at the next guess.
+    stuck: Option<StrategyError>,
+}
+
+impl<S, F, const WORD_LENGTH: usize> Noisy<S, F, WORD_LENGTH>
+where
+    S: Strategy<WORD_LENGTH>,
+    F: FnMut() -> S,
+{
+    pub fn new(
+        mut make_inner: F,
+        word_list: WordList<WORD_LENGTH>,
+        errors: HumanErrors,
+        seed: u64,
+    ) -> Self {
+        Self {
+            inner: make_inner(),
+            make_inner,
+            believed_viable: word_list.clone(),
+            word_list,
+            errors,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            guessed: HashSet::new(),
+            last_guess: None,
+            clues: Vec::new(),
+            history: Vec::new(),
+            stuck: None,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// `score` with one tile changed, if that still leaves some word fitting
+    /// the clues. A slip that rules out every word would be noticed and
+    /// fixed straight away, so it isn't simulated.
+    fn misentered(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        score: &Score<WORD_LENGTH>,
+    ) -> Score<WORD_LENGTH> {
+        use LetterScore::*;
+        let mut slips = Vec::new();
+        for i in 0..WORD_LENGTH {
+            for tile in [RightPlace, RightLetter, Wrong] {
+                if tile == score[i] {
+                    continue;
+                }
+                let mut slip = *score;
+                slip[i] = tile;
+                let solved = slip.iter().all(|tile| *tile == RightPlace);
+                if !solved
+                    && self
+                        .believed_viable
+                        .0
+                        .iter()
+                        .any(|word| word.is_consistent_with(guess, &slip))
+                {
+                    slips.push(slip);
+                }
+            }
+        }
+        slips.choose(&mut self.rng).copied().unwrap_or(*score)
+    }
+
+    /// Starts over with a fresh strategy and feeds it the real game so far.
+    fn start_over(&mut self) -> Result<(), StrategyError> {
+        self.inner = (self.make_inner)();
+        self.believed_viable = self.word_list.clone();
+        self.inner.prime(&self.clues);
+        self.believed_viable.retain_fitting(&self.clues);
+        for (guess, score) in &self.history {
+            if self.inner.make_guess()? != *guess {
+                self.inner.guess_replaced(guess)?;
+            }
+            self.inner.receive_score(score);
+            self.believed_viable.retain_viable_words(guess, score);
+        }
+        Ok(())
+    }
+}
+
+impl<S, F, const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for Noisy<S, F, WORD_LENGTH>
+where
+    S: Strategy<WORD_LENGTH>,
+    F: FnMut() -> S,
+{
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        if let Some(error) = self.stuck.take() {
+            return Err(error);
+        }
+        let mut guess = self.inner.make_guess()?;
+        if self.rng.gen_bool(self.errors.guess_error) {
+            let others = self
+                .believed_viable
+                .0
+                .iter()
+                .filter(|word| **word != guess && !self.guessed.contains(*word))
+                .copied()
+                .collect::<Vec<_>>();
+            if let Some(other) = others.choose(&mut self.rng) {
+                guess = *other;
+                self.inner.guess_replaced(&guess)?;
+            }
+        }
+        self.guessed.insert(guess);
+        self.last_guess = Some(guess);
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        let guess = self.last_guess.expect("should've made a guess by now");
+        self.history.push((guess, *score));
+        let entered = if self.rng.gen_bool(self.errors.score_error) {
+            self.misentered(&guess, score)
+        } else {
+            *score
+        };
+        self.believed_viable.retain_viable_words(&guess, &entered);
+        if self.believed_viable.0.is_empty() {
+            self.stuck = self.start_over().err();
+        } else {
+            self.inner.receive_score(&entered)
+        }
+    }
+
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        self.guessed.remove(guess);
+        self.inner.guess_rejected(guess, error)
+    }
+
+    fn guess_wasted(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        // Unlike a rejected guess, this one stays guessed: trying it again
+        // would only waste another turn.
+        self.inner.guess_wasted(guess, error)
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.clues.extend_from_slice(clues);
+        self.believed_viable.retain_fitting(clues);
+        for clue in clues {
+            if let Clue::Scored(guess, _) = clue {
+                self.guessed.insert(*guess);
+            }
+        }
+        self.inner.prime(clues)
+    }
+}
@@ -0,0 +1,157 @@
+//! The NYT's daily answers, by puzzle number, up to a fixed snapshot.
+
+use crate::policy::WordPolicy;
+use crate::word::*;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Answers in puzzle order: `ANSWERS[n]` is the answer to puzzle `n`. The
+/// snapshot ends at puzzle 219 (2022-01-24).
+pub const ANSWERS: &[&str] = &[
+    "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade", "naval", "serve",
+    "heath", "dwarf", "model", "karma", "stink", "grade", "quiet", "bench", "abate", "feign",
+    "major", "death", "fresh", "crust", "stool", "colon", "abase", "marry", "react", "batty",
+    "pride", "floss", "helix", "croak", "staff", "paper", "unfed", "whelp", "trawl", "outdo",
+    "adobe", "crazy", "sower", "repay", "digit", "crate", "cluck", "spike", "mimic", "pound",
+    "maxim", "linen", "unmet", "flesh", "booby", "forth", "first", "stand", "belly", "ivory",
+    "seedy", "print", "yearn", "drain", "bribe", "stout", "panel", "crass", "flume", "offal",
+    "agree", "error", "swirl", "argue", "bleed", "delta", "flick", "totem", "wooer", "front",
+    "shrub", "parry", "biome", "lapel", "start", "greet", "goner", "golem", "lusty", "loopy",
+    "round", "audit", "lying", "gamma", "labor", "islet", "civic", "forge", "corny", "moult",
+    "basic", "salad", "agate", "spicy", "spray", "essay", "fjord", "spend", "kebab", "guild",
+    "aback", "motor", "alone", "hatch", "hyper", "thumb", "dowry", "ought", "belch", "dutch",
+    "pilot", "tweed", "comet", "jaunt", "enema", "steed", "abyss", "growl", "fling", "dozen",
+    "boozy", "erode", "world", "gouge", "click", "briar", "great", "altar", "pulpy", "blurt",
+    "coast", "duchy", "groin", "fixer", "group", "rogue", "badly", "smart", "pithy", "gaudy",
+    "chill", "heron", "vodka", "finer", "surer", "radio", "rouge", "perch", "retch", "wrote",
+    "clock", "tilde", "store", "prove", "bring", "solve", "cheat", "grime", "exult", "usher",
+    "epoch", "triad", "break", "rhino", "viral", "conic", "masse", "sonic", "vital", "trace",
+    "using", "peach", "champ", "baton", "brake", "pluck", "craze", "gripe", "weary", "picky",
+    "acute", "ferry", "aside", "tapir", "troll", "unify", "rebus", "boost", "truss", "siege",
+    "tiger", "banal", "slump", "crank", "gorge", "query", "drink", "favor", "abbey", "tangy",
+    "panic", "solar", "shire", "proxy", "point", "robot", "prick", "wince", "crimp", "knoll",
+];
+
+/// A calendar date, for mapping puzzle numbers to the day they ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+/// The day puzzle 0 ran.
+pub const FIRST_PUZZLE: Date = Date {
+    year: 2021,
+    month: 6,
+    day: 19,
+};
+
+impl Date {
+    /// Days since 1970-01-01, using the proleptic Gregorian calendar.
+    pub fn days_since_epoch(&self) -> i64 {
+        // Howard Hinnant's days_from_civil.
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(self.month);
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+
+    /// Today's date in UTC, or 1970-01-01 if the clock is set before then.
+    pub fn today() -> Self {
+        let since_epoch = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        Self::from_days_since_epoch((since_epoch.as_secs() / 86400) as i64)
+    }
+
+    /// The inverse of [`Date::days_since_epoch`].
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days.rem_euclid(146097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Self {
+            year: year as i32,
+            month: month as u32,
+            day: day as u32,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl FromStr for Date {
+    type Err = String;
+
+    /// Parses a `YYYY-MM-DD` date.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("expected a YYYY-MM-DD date, got {:?}", s);
+        let mut parts = s.splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(error);
+        let (year, month, day) = (next()?, next()?, next()?);
+        let date = Self {
+            year: year.parse().map_err(|_| error())?,
+            month: month.parse().map_err(|_| error())?,
+            day: day.parse().map_err(|_| error())?,
+        };
+        // Round-tripping rejects out-of-range months and days.
+        if Self::from_days_since_epoch(date.days_since_epoch()) == date {
+            Ok(date)
+        } else {
+            Err(error())
+        }
+    }
+}
+
+/// The answer to `puzzle`, if it's within the snapshot.
+pub fn answer<const WORD_LENGTH: usize>(puzzle: u32) -> Option<Word<WORD_LENGTH>> {
+    let answer = ANSWERS.get(puzzle as usize)?;
+    Word::try_from(*answer).ok()
+}
+
+/// The day `puzzle` ran.
+pub fn puzzle_date(puzzle: u32) -> Date {
+    Date::from_days_since_epoch(FIRST_PUZZLE.days_since_epoch() + i64::from(puzzle))
+}
+
+/// The puzzle that ran on `date`, or `None` for dates before the first one.
+pub fn puzzle_for_date(date: Date) -> Option<u32> {
+    u32::try_from(date.days_since_epoch() - FIRST_PUZZLE.days_since_epoch()).ok()
+}
+
+/// Rejects words that were already the answer before `puzzle`, since the NYT
+/// doesn't reuse answers.
+pub struct ExcludePastAnswers<const WORD_LENGTH: usize>(HashSet<Word<WORD_LENGTH>>);
+
+impl<const WORD_LENGTH: usize> ExcludePastAnswers<WORD_LENGTH> {
+    pub fn before(puzzle: u32) -> Self {
+        Self((0..puzzle).filter_map(answer).collect())
+    }
+}
+
+impl<const WORD_LENGTH: usize> WordPolicy<WORD_LENGTH> for ExcludePastAnswers<WORD_LENGTH> {
+    fn allows(&self, word: &Word<WORD_LENGTH>) -> bool {
+        !self.0.contains(word)
+    }
+}
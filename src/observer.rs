@@ -0,0 +1,41 @@
+//! Watching a game from outside the strategy and the engine, for UIs,
+//! loggers and stat collectors, through
+//! [`run_game_with_observer`](crate::run_game_with_observer).
+
+use crate::engine::{GameTranscript, GuessResult};
+use crate::metrics::Metrics;
+use crate::word::*;
+
+/// Callbacks for each step of a game. Every one defaults to doing nothing,
+/// so an observer only implements what it cares about.
+pub trait Observer<const WORD_LENGTH: usize> {
+    /// The strategy guessed `guess`, with the work it took if the strategy
+    /// keeps count.
+    fn on_guess(&mut self, _guess: &Word<WORD_LENGTH>, _metrics: Option<Metrics>) {}
+
+    /// The engine made `result` of `guess`.
+    fn on_score(&mut self, _guess: &Word<WORD_LENGTH>, _result: &GuessResult<WORD_LENGTH>) {}
+
+    /// The game is over.
+    fn on_end(&mut self, _transcript: &GameTranscript<WORD_LENGTH>) {}
+}
+
+/// Watches nothing.
+impl<const WORD_LENGTH: usize> Observer<WORD_LENGTH> for () {}
+
+impl<O, const WORD_LENGTH: usize> Observer<WORD_LENGTH> for &mut O
+where
+    O: Observer<WORD_LENGTH> + ?Sized,
+{
+    fn on_guess(&mut self, guess: &Word<WORD_LENGTH>, metrics: Option<Metrics>) {
+        (**self).on_guess(guess, metrics)
+    }
+
+    fn on_score(&mut self, guess: &Word<WORD_LENGTH>, result: &GuessResult<WORD_LENGTH>) {
+        (**self).on_score(guess, result)
+    }
+
+    fn on_end(&mut self, transcript: &GameTranscript<WORD_LENGTH>) {
+        (**self).on_end(transcript)
+    }
+}
@@ -0,0 +1,210 @@
+//! A strategy that plays perfectly once few enough words are left: it
+//! searches every guess and every score that could follow, remembering the
+//! positions it has already solved, and picks the guess that needs the fewest
+//! guesses on average. Bigger positions are left to a heuristic.
+
+use crate::advice::Advisor;
+use crate::oracle::Oracle;
+use crate::ranking::Ranker;
+use crate::strategy::{GuessRules, Strategy, StrategyError, StrategyInfo};
+use crate::word::*;
+use std::collections::HashSet;
+
+/// Above this many viable words, guesses come from the fallback heuristic.
+pub const DEFAULT_MAX_EXACT: usize = 40;
+/// Probes, on top of the viable words, that the search considers guessing.
+pub const DEFAULT_SHORTLIST: usize = 20;
+
+pub struct OptimalStrategy<const WORD_LENGTH: usize> {
+    word_list: WordList<WORD_LENGTH>,
+    viable_words: WordList<WORD_LENGTH>,
+    guessed: HashSet<Word<WORD_LENGTH>>,
+    last_guess: Option<Word<WORD_LENGTH>>,
+    rules: GuessRules<WORD_LENGTH>,
+    max_exact: usize,
+    shortlist: usize,
+    fallback: Box<dyn Ranker<WORD_LENGTH>>,
+    /// The search's guesses and its table of solved positions, kept from
+    /// turn to turn once the search has taken over. Later positions are
+    /// subsets of the first, so what was worked out for one turn still holds
+    /// for the next.
+    search: Option<(Vec<Word<WORD_LENGTH>>, Oracle<WORD_LENGTH>)>,
+    last_exact: bool,
+}
+
+impl<const WORD_LENGTH: usize> OptimalStrategy<WORD_LENGTH> {
+    pub const INFO: StrategyInfo = StrategyInfo {
+        supports_hard_mode: true,
+        deterministic: true,
+        needs_score_matrix: false,
+        approximate: false,
+    };
+
+    /// Searches exactly at or below [`DEFAULT_MAX_EXACT`] viable words and
+    /// ranks by entropy above it.
+    pub fn new(word_list: WordList<WORD_LENGTH>) -> Self {
+        Self {
+            viable_words: word_list.clone(),
+            word_list,
+            guessed: HashSet::new(),
+            last_guess: None,
+            rules: GuessRules::default(),
+            max_exact: DEFAULT_MAX_EXACT,
+            shortlist: DEFAULT_SHORTLIST,
+            fallback: Box::new(Advisor::Entropy),
+            search: None,
+            last_exact: false,
+        }
+    }
+
+    /// Like [`OptimalStrategy::new`], but guesses from `guess_list` while only
+    /// considering words in `answer_list` as the secret.
+    pub fn with_guess_list(
+        guess_list: WordList<WORD_LENGTH>,
+        answer_list: WordList<WORD_LENGTH>,
+    ) -> Self {
+        Self {
+            viable_words: answer_list,
+            ..Self::new(guess_list)
+        }
+    }
+
+    /// Searches exactly once at most `max_exact` words are left. The search
+    /// grows exponentially, so this is the main knob on how long a turn
+    /// can take.
+    pub fn with_max_exact(mut self, max_exact: usize) -> Self {
+        self.max_exact = max_exact;
+        self
+    }
+
+    /// Lets the search consider the `shortlist` best probes by entropy, on
+    /// top of the viable words.
+    pub fn with_shortlist(mut self, shortlist: usize) -> Self {
+        self.shortlist = shortlist;
+        self
+    }
+
+    /// Ranks guesses with `ranker` while too many words are left to search.
+    pub fn with_fallback(mut self, ranker: impl Ranker<WORD_LENGTH> + 'static) -> Self {
+        self.fallback = Box::new(ranker);
+        self
+    }
+
+    pub fn set_guess_rules(&mut self, rules: GuessRules<WORD_LENGTH>) {
+        self.rules = rules;
+    }
+
+    /// Whether the most recent guess came from the exact search.
+    pub fn last_guess_exact(&self) -> bool {
+        self.last_exact
+    }
+
+    fn playable(&self) -> WordList<WORD_LENGTH> {
+        let mut candidates = self.word_list.clone();
+        candidates
+            .0
+            .retain(|word| !self.guessed.contains(word) && self.rules.allows(word));
+        candidates
+    }
+
+    /// The guess needing the fewest guesses in total over the viable words,
+    /// or `None` if none of the search's guesses makes progress.
+    fn search(&mut self) -> Option<Word<WORD_LENGTH>> {
+        if self.search.is_none() {
+            let viable = self.viable_words.0.iter().collect::<HashSet<_>>();
+            let mut extra = 0;
+            let playable = self.playable();
+            let shortlist = Advisor::Entropy
+                .rank(&playable, &self.viable_words)
+                .into_iter()
+                .filter(|word| {
+                    let keep = viable.contains(word) || extra < self.shortlist;
+                    extra += !viable.contains(word) as usize;
+                    keep
+                })
+                .collect::<HashSet<_>>();
+            // Keep list order, so ties always go the same way.
+            let guesses = playable
+                .0
+                .into_iter()
+                .filter(|word| shortlist.contains(word))
+                .collect::<Vec<_>>();
+            let oracle = Oracle::new(WordList(guesses.clone()));
+            self.search = Some((guesses, oracle));
+        }
+        let Self {
+            search,
+            guessed,
+            viable_words,
+            ..
+        } = self;
+        let (guesses, oracle) = search.as_mut().expect("just set up");
+        let viable = viable_words.0.iter().collect::<HashSet<_>>();
+        guesses
+            .iter()
+            .filter(|word| !guessed.contains(word))
+            .filter_map(|word| {
+                let total = oracle.total_guesses_after(word, viable_words)?;
+                Some((*word, total, !viable.contains(word)))
+            })
+            // Ties go to guesses that could be the answer, then to the
+            // earliest.
+            .min_by_key(|(_, total, probe)| (*total, *probe))
+            .map(|(word, _, _)| word)
+    }
+}
+
+impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for OptimalStrategy<WORD_LENGTH> {
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        if self.viable_words.0.is_empty() {
+            return Err(StrategyError::NeedsReset);
+        }
+        let guess = match self.rules.always_open {
+            Some(opener) if self.guessed.is_empty() => {
+                self.last_exact = false;
+                opener
+            }
+            _ => {
+                let searched = if self.viable_words.0.len() <= self.max_exact {
+                    self.search()
+                } else {
+                    None
+                };
+                self.last_exact = searched.is_some();
+                match searched {
+                    Some(guess) => guess,
+                    None => *self
+                        .fallback
+                        .rank(&self.playable(), &self.viable_words)
+                        .first()
+                        .ok_or(StrategyError::OutOfCandidates)?,
+                }
+            }
+        };
+        self.guessed.insert(guess);
+        self.last_guess = Some(guess);
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        let last_guess = self.last_guess.expect("should've made a guess by now");
+        self.viable_words.retain_viable_words(&last_guess, score);
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        if let Some(suggested) = self.last_guess.replace(*played) {
+            self.guessed.remove(&suggested);
+        }
+        self.guessed.insert(*played);
+        Ok(())
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.viable_words.retain_fitting(clues);
+        for clue in clues {
+            if let Clue::Scored(guess, _) = clue {
+                self.guessed.insert(*guess);
+            }
+        }
+    }
+}
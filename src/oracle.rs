@@ -0,0 +1,120 @@
+//! Brute-force optimal play for tiny games, used to check the heuristic and
+//! search strategies against the true optimum. Everything here is exponential
+//! and only meant for small games: word lengths up to 3 over a handful of
+//! letters, or a few dozen candidates with a short list of guesses.
+
+use crate::word::*;
+use std::collections::HashMap;
+
+/// Every word of length `WORD_LENGTH` over `alphabet`; none if `alphabet` is
+/// empty.
+pub fn all_words<const WORD_LENGTH: usize>(alphabet: &[char]) -> WordList<WORD_LENGTH> {
+    let first = match alphabet.first() {
+        Some(first) => *first,
+        None => return WordList(Vec::new()),
+    };
+    let mut words = vec![Word([first; WORD_LENGTH])];
+    for position in 0..WORD_LENGTH {
+        words = words
+            .iter()
+            .flat_map(|word| {
+                alphabet.iter().map(move |letter| {
+                    let mut word = *word;
+                    word.0[position] = *letter;
+                    word
+                })
+            })
+            .collect();
+    }
+    WordList(words)
+}
+
+pub struct Oracle<const WORD_LENGTH: usize> {
+    guesses: WordList<WORD_LENGTH>,
+    memo: HashMap<Vec<Word<WORD_LENGTH>>, usize>,
+}
+
+impl<const WORD_LENGTH: usize> Oracle<WORD_LENGTH> {
+    pub fn new(guesses: WordList<WORD_LENGTH>) -> Self {
+        Self {
+            guesses,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// The fewest guesses, summed over every secret in `candidates`, that any
+    /// strategy guessing from the oracle's list needs to solve them all with
+    /// no turn limit.
+    pub fn optimal_total_guesses(&mut self, candidates: &WordList<WORD_LENGTH>) -> usize {
+        let mut key = candidates.0.clone();
+        key.sort_unstable();
+        key.dedup();
+        self.solve(key)
+    }
+
+    /// [`Oracle::optimal_total_guesses`] averaged over the candidates.
+    pub fn optimal_average_guesses(&mut self, candidates: &WordList<WORD_LENGTH>) -> f64 {
+        self.optimal_total_guesses(candidates) as f64 / candidates.0.len() as f64
+    }
+
+    /// The fewest guesses, summed over every secret in `candidates`, needed
+    /// to solve them all when `guess` is played first. `None` if `guess`
+    /// can't tell any of the candidates apart.
+    pub fn total_guesses_after(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        candidates: &WordList<WORD_LENGTH>,
+    ) -> Option<usize> {
+        self.total_after(guess, candidates, usize::MAX)
+    }
+
+    /// Like [`Oracle::total_guesses_after`], but gives up with whatever it has
+    /// once the total reaches `bound`.
+    fn total_after(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        candidates: &WordList<WORD_LENGTH>,
+        bound: usize,
+    ) -> Option<usize> {
+        let solved = [LetterScore::RightPlace; WORD_LENGTH];
+        let partition = candidates.partition(guess);
+        // A guess that leaves every candidate in one unsolved bucket makes no
+        // progress.
+        if partition.len() == 1 && !partition.contains_key(&solved) {
+            return None;
+        }
+        // Every candidate pays for this guess; only the ones it doesn't solve
+        // go on to the subgames.
+        let mut total = candidates.0.len();
+        for (score, mut bucket) in partition {
+            if score != solved {
+                bucket.0.sort_unstable();
+                total = total.saturating_add(self.solve(bucket.0));
+                if total >= bound {
+                    break;
+                }
+            }
+        }
+        Some(total)
+    }
+
+    fn solve(&mut self, candidates: Vec<Word<WORD_LENGTH>>) -> usize {
+        if candidates.len() <= 1 {
+            return candidates.len();
+        }
+        if let Some(total) = self.memo.get(&candidates) {
+            return *total;
+        }
+
+        let candidate_list = WordList(candidates.clone());
+        let mut best = usize::MAX;
+        for guess in self.guesses.0.clone() {
+            if let Some(total) = self.total_after(&guess, &candidate_list, best) {
+                best = best.min(total);
+            }
+        }
+
+        self.memo.insert(candidates, best);
+        best
+    }
+}
@@ -0,0 +1,255 @@
+use crate::board::Board;
+use crate::display::{renderer_for, Renderer};
+use crate::engine::{GameTranscript, GuessError};
+use crate::error::{read_stdin_line, WordleError};
+use crate::record::GameRecord;
+use crate::strategy::{SimpleStrategy, Strategy, StrategyError};
+use crate::tiles::TileStyle;
+use crate::word::*;
+use std::convert::TryInto;
+use std::time::{Duration, Instant};
+
+/// Lets a human play from stdin, with a limited number of hints they can
+/// spend on the solver's suggestion for the current turn.
+pub struct AssistedGuesser<const WORD_LENGTH: usize> {
+    word_list: WordList<WORD_LENGTH>,
+    viable_words: WordList<WORD_LENGTH>,
+    board: Board<WORD_LENGTH>,
+    last_guess: Option<Word<WORD_LENGTH>>,
+    hints_remaining: usize,
+    hints_used: usize,
+    /// Thinking time for each guess, if guesses are being timed.
+    think_times: Option<Vec<Duration>>,
+    carried_time: Duration,
+    renderer: Box<dyn Renderer<WORD_LENGTH>>,
+}
+
+enum Input<const WORD_LENGTH: usize> {
+    Guess(Word<WORD_LENGTH>),
+    Hint,
+}
+
+impl<const WORD_LENGTH: usize> AssistedGuesser<WORD_LENGTH> {
+    pub fn new(word_list: WordList<WORD_LENGTH>, hints: usize) -> Self {
+        Self {
+            viable_words: word_list.clone(),
+            word_list,
+            board: Board::new(),
+            last_guess: None,
+            hints_remaining: hints,
+            hints_used: 0,
+            think_times: None,
+            carried_time: Duration::ZERO,
+            renderer: renderer_for(TileStyle::default()),
+        }
+    }
+
+    /// Times how long the player takes over each guess, from the prompt to a
+    /// valid guess.
+    pub fn time_guesses(&mut self) {
+        self.think_times = Some(Vec::new());
+    }
+
+    /// How the board is drawn after each guess.
+    pub fn set_tile_style(&mut self, tiles: TileStyle) {
+        self.renderer = renderer_for(tiles);
+    }
+
+    /// Draws the board some other way, e.g. as CSV.
+    pub fn set_renderer(&mut self, renderer: Box<dyn Renderer<WORD_LENGTH>>) {
+        self.renderer = renderer;
+    }
+
+    /// How long each guess took, including the winning one, if guesses are
+    /// being timed.
+    pub fn think_times(&self) -> Option<&[Duration]> {
+        self.think_times.as_deref()
+    }
+
+    /// The finished game played out in `transcript` as a record, with the
+    /// player's thinking times. Its board has the last row, won or lost.
+    pub fn to_record(
+        &self,
+        transcript: &GameTranscript<WORD_LENGTH>,
+        max_guesses: usize,
+    ) -> GameRecord<WORD_LENGTH> {
+        GameRecord {
+            think_times: self.think_times.clone().unwrap_or_default(),
+            ..transcript.to_record(max_guesses)
+        }
+    }
+
+    pub fn hints_used(&self) -> usize {
+        self.hints_used
+    }
+
+    /// Guesses played so far. The winning guess never receives a score, so it
+    /// isn't included.
+    pub fn board(&self) -> &Board<WORD_LENGTH> {
+        &self.board
+    }
+
+    fn suggestion(&self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        let mut solver = SimpleStrategy::new(self.word_list.clone());
+        let played = self
+            .board
+            .rows()
+            .iter()
+            .map(|row| Clue::Scored(row.guess, row.score))
+            .collect::<Vec<_>>();
+        solver.prime(&played);
+        solver.make_guess()
+    }
+
+    fn read_input(&self) -> Result<Option<Input<WORD_LENGTH>>, WordleError> {
+        let buffer = read_stdin_line()?.trim().to_ascii_lowercase();
+        if buffer == "?" {
+            return Ok(Some(Input::Hint));
+        }
+        let guess: Word<WORD_LENGTH> = match buffer.as_str().try_into() {
+            Ok(guess) => guess,
+            Err(_) => return Ok(None),
+        };
+        Ok(self
+            .word_list
+            .0
+            .contains(&guess)
+            .then_some(Input::Guess(guess)))
+    }
+
+    /// Time spent on a refused guess counts towards the next one.
+    fn carry_think_time(&mut self) {
+        if let Some(think_times) = &mut self.think_times {
+            self.carried_time += think_times.pop().unwrap_or_default();
+        }
+    }
+}
+
+impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for AssistedGuesser<WORD_LENGTH> {
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        println!(
+            "Enter guess ('?' for a hint, {} left):",
+            self.hints_remaining
+        );
+        let started = Instant::now();
+        let guess = loop {
+            match self.read_input()? {
+                Some(Input::Guess(guess)) => break guess,
+                Some(Input::Hint) if self.hints_remaining > 0 => match self.suggestion() {
+                    Ok(hint) => {
+                        self.hints_remaining -= 1;
+                        self.hints_used += 1;
+                        println!("Hint: try {}", hint);
+                    }
+                    Err(error) => println!("No hint to give: {}", error),
+                },
+                Some(Input::Hint) => println!("No hints left, enter a guess:"),
+                None => println!("Not valid guess:"),
+            }
+        };
+        if let Some(think_times) = &mut self.think_times {
+            think_times.push(std::mem::take(&mut self.carried_time) + started.elapsed());
+        }
+        self.last_guess = Some(guess);
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        let last_guess = self.last_guess.expect("should've made a guess by now");
+        self.viable_words.retain_viable_words(&last_guess, score);
+        self.board
+            .push(last_guess, *score, Some(self.viable_words.0.len()));
+        print!("{}", self.renderer.render(&self.board));
+    }
+
+    fn guess_rejected(
+        &mut self,
+        _guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        println!("Guess rejected: {}", error);
+        self.carry_think_time();
+        Ok(())
+    }
+
+    fn guess_wasted(
+        &mut self,
+        _guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        println!("Guess rejected, and it used up a turn: {}", error);
+        self.carry_think_time();
+        Ok(())
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        for clue in clues {
+            self.viable_words.retain_fitting(std::slice::from_ref(clue));
+            match clue {
+                Clue::Scored(guess, score) => {
+                    self.board
+                        .push(*guess, *score, Some(self.viable_words.0.len()));
+                }
+                Clue::Letter(free_letter) => println!("Free letter: {}", free_letter),
+            }
+        }
+        print!("{}", self.renderer.render(&self.board));
+    }
+}
+
+/// Renders a finished game as a shareable grid of `tiles`, noting how many
+/// hints were spent. If the game was won but `board` lacks the winning row, it is
+/// added automatically.
+pub fn share_grid<const WORD_LENGTH: usize>(
+    board: &Board<WORD_LENGTH>,
+    won: bool,
+    num_guesses: usize,
+    hints_used: usize,
+    tiles: TileStyle,
+) -> String {
+    numbered_share_grid(board, None, won, num_guesses, hints_used, tiles)
+}
+
+/// Like [`share_grid`], with the header naming the `puzzle` the way the NYT
+/// does, as in `Wordle 1,210 3/6`.
+pub fn numbered_share_grid<const WORD_LENGTH: usize>(
+    board: &Board<WORD_LENGTH>,
+    puzzle: Option<u32>,
+    won: bool,
+    num_guesses: usize,
+    hints_used: usize,
+    tiles: TileStyle,
+) -> String {
+    let missing_winning_row = won && !board.is_solved();
+    let result = if won {
+        (board.len() + missing_winning_row as usize).to_string()
+    } else {
+        "X".to_owned()
+    };
+    let mut grid = "Wordle ".to_owned();
+    if let Some(puzzle) = puzzle {
+        let (thousands, rest) = (puzzle / 1000, puzzle % 1000);
+        if thousands > 0 {
+            grid.push_str(&format!("{},{:03} ", thousands, rest));
+        } else {
+            grid.push_str(&format!("{} ", rest));
+        }
+    }
+    grid.push_str(&format!("{}/{}", result, num_guesses));
+    match hints_used {
+        0 => {}
+        1 => grid.push_str(" (1 hint)"),
+        n => grid.push_str(&format!(" ({} hints)", n)),
+    }
+    grid.push('\n');
+
+    let winning_row = [LetterScore::RightPlace; WORD_LENGTH];
+    let rows = board
+        .scores()
+        .chain(missing_winning_row.then_some(&winning_row));
+    for row in rows {
+        grid.push('\n');
+        grid.push_str(&tiles.tiles(row));
+    }
+    grid
+}
@@ -0,0 +1,120 @@
+//! Policies restricting which dictionary words are plausible answers. The NYT
+//! curates its answers (no plurals, no past tenses, ...), so filtering the
+//! answer list the same way sharpens the solver considerably.
+
+use crate::word::*;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+pub trait WordPolicy<const WORD_LENGTH: usize> {
+    /// Whether `word` may be an answer under this policy.
+    fn allows(&self, word: &Word<WORD_LENGTH>) -> bool;
+}
+
+impl<F, const WORD_LENGTH: usize> WordPolicy<WORD_LENGTH> for F
+where
+    F: Fn(&Word<WORD_LENGTH>) -> bool,
+{
+    fn allows(&self, word: &Word<WORD_LENGTH>) -> bool {
+        self(word)
+    }
+}
+
+fn ends_with<const WORD_LENGTH: usize>(word: &Word<WORD_LENGTH>, suffix: &str) -> bool {
+    let suffix = suffix.chars().collect::<Vec<_>>();
+    word.0.ends_with(&suffix)
+}
+
+/// Rejects words that look like plurals: a trailing `s` that isn't part of
+/// `ss`, `us` or `is` (so "class", "focus" and "basis" survive).
+pub struct ExcludePlurals;
+
+impl<const WORD_LENGTH: usize> WordPolicy<WORD_LENGTH> for ExcludePlurals {
+    fn allows(&self, word: &Word<WORD_LENGTH>) -> bool {
+        !ends_with(word, "s") || ["ss", "us", "is"].iter().any(|s| ends_with(word, s))
+    }
+}
+
+/// Rejects words that look like past tenses: a trailing `ed` that isn't part
+/// of `eed` (so "greed" and "breed" survive).
+pub struct ExcludePastTense;
+
+impl<const WORD_LENGTH: usize> WordPolicy<WORD_LENGTH> for ExcludePastTense {
+    fn allows(&self, word: &Word<WORD_LENGTH>) -> bool {
+        !ends_with(word, "ed") || ends_with(word, "eed")
+    }
+}
+
+/// The built-in heuristics, by name, for picking from the command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exclusion {
+    Plurals,
+    PastTense,
+}
+
+impl<const WORD_LENGTH: usize> WordPolicy<WORD_LENGTH> for Exclusion {
+    fn allows(&self, word: &Word<WORD_LENGTH>) -> bool {
+        match self {
+            Self::Plurals => ExcludePlurals.allows(word),
+            Self::PastTense => ExcludePastTense.allows(word),
+        }
+    }
+}
+
+impl fmt::Display for Exclusion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Plurals => "plurals",
+            Self::PastTense => "past-tense",
+        })
+    }
+}
+
+impl FromStr for Exclusion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "plurals" => Ok(Self::Plurals),
+            "past-tense" => Ok(Self::PastTense),
+            _ => Err(format!(
+                "unknown exclusion {:?} (expected plurals or past-tense)",
+                s
+            )),
+        }
+    }
+}
+
+/// Rejects an explicit set of words, e.g. ones tagged as proper nouns or
+/// inflections in external data.
+pub struct ExcludeTagged<const WORD_LENGTH: usize>(pub HashSet<Word<WORD_LENGTH>>);
+
+impl<const WORD_LENGTH: usize> WordPolicy<WORD_LENGTH> for ExcludeTagged<WORD_LENGTH> {
+    fn allows(&self, word: &Word<WORD_LENGTH>) -> bool {
+        !self.0.contains(word)
+    }
+}
+
+/// Allows a word only if every member policy does.
+pub struct AllOf<const WORD_LENGTH: usize>(pub Vec<Box<dyn WordPolicy<WORD_LENGTH>>>);
+
+impl<const WORD_LENGTH: usize> WordPolicy<WORD_LENGTH> for AllOf<WORD_LENGTH> {
+    fn allows(&self, word: &Word<WORD_LENGTH>) -> bool {
+        self.0.iter().all(|policy| policy.allows(word))
+    }
+}
+
+impl<const WORD_LENGTH: usize> WordList<WORD_LENGTH> {
+    /// The words of this list that `policy` allows as answers.
+    pub fn filtered<P>(&self, policy: &P) -> Self
+    where
+        P: WordPolicy<WORD_LENGTH> + ?Sized,
+    {
+        self.0
+            .iter()
+            .filter(|word| policy.allows(word))
+            .copied()
+            .collect()
+    }
+}
@@ -0,0 +1,187 @@
+//! Targeted practice: find what kind of answers a player struggles with in
+//! their game archive, and pick a week of practice secrets of that kind.
+
+use crate::analysis::analyze_game;
+use crate::letter_stats::LetterStats;
+use crate::record::GameRecord;
+use crate::word::*;
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// The number of least common letters counted as rare.
+const RARE_LETTERS: usize = 8;
+
+/// How many more guesses than usual a player needs on some kind of answer
+/// before it counts as a weakness.
+const WEAKNESS_MARGIN: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weakness {
+    /// Answers with a repeated letter, like "sissy" or "eerie".
+    DoubleLetters,
+    /// Answers containing one of the least common letters in the list.
+    RareLetters,
+    /// Answers with many neighbours one letter away, like "_ight" words.
+    LargeFamilies,
+}
+
+impl fmt::Display for Weakness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::DoubleLetters => "double letters",
+            Self::RareLetters => "rare letters",
+            Self::LargeFamilies => "large word families",
+        })
+    }
+}
+
+/// Classifies answers by the features that make them hard.
+pub struct DifficultyEstimator<const WORD_LENGTH: usize> {
+    word_list: WordList<WORD_LENGTH>,
+    known: HashSet<Word<WORD_LENGTH>>,
+    rare_letters: HashSet<char>,
+    /// Number of words matching each pattern with one position blanked out.
+    patterns: HashMap<(usize, Word<WORD_LENGTH>), usize>,
+}
+
+impl<const WORD_LENGTH: usize> DifficultyEstimator<WORD_LENGTH> {
+    pub fn new(word_list: WordList<WORD_LENGTH>) -> Self {
+        let rare_letters = LetterStats::new(&word_list)
+            .rarest(RARE_LETTERS)
+            .into_iter()
+            .collect();
+        let mut patterns = HashMap::new();
+        for word in &word_list.0 {
+            for position in 0..WORD_LENGTH {
+                *patterns.entry(Self::pattern(word, position)).or_insert(0) += 1;
+            }
+        }
+        Self {
+            known: word_list.0.iter().copied().collect(),
+            word_list,
+            rare_letters,
+            patterns,
+        }
+    }
+
+    fn pattern(word: &Word<WORD_LENGTH>, position: usize) -> (usize, Word<WORD_LENGTH>) {
+        let mut pattern = *word;
+        pattern.0[position] = '_';
+        (position, pattern)
+    }
+
+    /// Number of words in the list that differ from `word` in exactly one
+    /// position.
+    pub fn family_size(&self, word: &Word<WORD_LENGTH>) -> usize {
+        // Don't count the word itself if it's in the list.
+        let own = self.known.contains(word) as usize;
+        (0..WORD_LENGTH)
+            .map(|position| {
+                let matches = self.patterns.get(&Self::pattern(word, position));
+                matches.copied().unwrap_or(0) - own
+            })
+            .sum()
+    }
+
+    pub fn has_weakness(&self, word: &Word<WORD_LENGTH>, weakness: Weakness) -> bool {
+        match weakness {
+            Weakness::DoubleLetters => HashSet::<char>::from(word).len() < WORD_LENGTH,
+            Weakness::RareLetters => word.0.iter().any(|c| self.rare_letters.contains(c)),
+            Weakness::LargeFamilies => self.family_size(word) >= 4,
+        }
+    }
+
+    /// A rough difficulty score: more neighbours and rarer letters make a
+    /// word harder to pin down.
+    pub fn difficulty(&self, word: &Word<WORD_LENGTH>) -> usize {
+        self.family_size(word)
+            + word
+                .0
+                .iter()
+                .filter(|c| self.rare_letters.contains(c))
+                .count()
+    }
+}
+
+/// The kinds of answers the player needs noticeably more guesses for than
+/// their average. Only won games count, since a lost game's answer is unknown.
+pub fn find_weaknesses<const WORD_LENGTH: usize>(
+    records: &[GameRecord<WORD_LENGTH>],
+    estimator: &DifficultyEstimator<WORD_LENGTH>,
+) -> Vec<Weakness> {
+    let games = records
+        .iter()
+        .filter_map(|record| {
+            let secret = record.board.last().filter(|row| row.is_solved())?.guess;
+            Some((secret, analyze_game(record, &estimator.word_list).guesses))
+        })
+        .collect::<Vec<_>>();
+    if games.is_empty() {
+        return Vec::new();
+    }
+    let average = |games: &[&(Word<WORD_LENGTH>, usize)]| {
+        games.iter().map(|(_, guesses)| *guesses).sum::<usize>() as f64 / games.len() as f64
+    };
+    let overall = average(&games.iter().collect::<Vec<_>>());
+
+    [
+        Weakness::DoubleLetters,
+        Weakness::RareLetters,
+        Weakness::LargeFamilies,
+    ]
+    .iter()
+    .copied()
+    .filter(|weakness| {
+        let affected = games
+            .iter()
+            .filter(|(secret, _)| estimator.has_weakness(secret, *weakness))
+            .collect::<Vec<_>>();
+        !affected.is_empty() && average(&affected) >= overall + WEAKNESS_MARGIN
+    })
+    .collect()
+}
+
+/// Seven practice secrets drawn from `answers`, rotating through the given
+/// weaknesses and favouring the hardest matching words. With no weaknesses,
+/// the week is just the hardest words overall.
+pub fn weekly_plan<const WORD_LENGTH: usize>(
+    weaknesses: &[Weakness],
+    answers: &WordList<WORD_LENGTH>,
+    estimator: &DifficultyEstimator<WORD_LENGTH>,
+    seed: u64,
+) -> Vec<Word<WORD_LENGTH>> {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let pool_for = |weakness: Option<Weakness>| {
+        let mut pool = answers
+            .0
+            .iter()
+            .filter(|word| weakness.is_none_or(|weakness| estimator.has_weakness(word, weakness)))
+            .copied()
+            .collect::<Vec<_>>();
+        pool.sort_by_cached_key(|word| std::cmp::Reverse(estimator.difficulty(word)));
+        pool.truncate(50);
+        pool
+    };
+    let pools = if weaknesses.is_empty() {
+        vec![pool_for(None)]
+    } else {
+        weaknesses.iter().map(|w| pool_for(Some(*w))).collect()
+    };
+
+    let mut plan = Vec::new();
+    for pool in pools.iter().cycle().take(7 * pools.len()) {
+        if plan.len() == 7 {
+            break;
+        }
+        let unused = pool
+            .iter()
+            .filter(|word| !plan.contains(*word))
+            .collect::<Vec<_>>();
+        if let Some(word) = unused.choose(&mut rng) {
+            plan.push(**word);
+        }
+    }
+    plan
+}
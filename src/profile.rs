@@ -0,0 +1,123 @@
+//! Engine settings for the Wordle clones people play, so a game can be
+//! simulated the way a particular site runs it rather than by piecing the
+//! rules together flag by flag.
+
+use crate::engine::{InvalidGuessPolicy, StandardEngine};
+use crate::word::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// Where a profile's secrets and guesses come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProfileWords {
+    /// Secrets from the NYT's answers, guesses from the whole dictionary.
+    Nyt,
+    /// Any word in the dictionary can be the secret.
+    Dictionary,
+    /// The site's own list, supplied as a word file, for both.
+    Custom,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    pub name: &'static str,
+    pub words: ProfileWords,
+    pub num_guesses: usize,
+    pub reject_repeats: bool,
+    pub hard_mode: bool,
+    pub invalid_guesses: InvalidGuessPolicy,
+}
+
+/// The bundled profiles.
+pub const PROFILES: &[Profile] = &[
+    Profile {
+        name: "nyt",
+        words: ProfileWords::Nyt,
+        num_guesses: 6,
+        reject_repeats: false,
+        hard_mode: false,
+        invalid_guesses: InvalidGuessPolicy::Reject,
+    },
+    Profile {
+        name: "wordle-unlimited",
+        words: ProfileWords::Dictionary,
+        num_guesses: 6,
+        reject_repeats: false,
+        hard_mode: false,
+        invalid_guesses: InvalidGuessPolicy::Reject,
+    },
+    Profile {
+        name: "lewdle",
+        words: ProfileWords::Custom,
+        num_guesses: 6,
+        reject_repeats: true,
+        hard_mode: false,
+        invalid_guesses: InvalidGuessPolicy::ConsumeTurn,
+    },
+    Profile {
+        name: "hard-mode",
+        words: ProfileWords::Nyt,
+        num_guesses: 6,
+        reject_repeats: false,
+        hard_mode: true,
+        invalid_guesses: InvalidGuessPolicy::Reject,
+    },
+];
+
+impl Default for Profile {
+    /// Plain rules, with any word in the dictionary as the secret.
+    fn default() -> Self {
+        Self {
+            name: "standard",
+            words: ProfileWords::Dictionary,
+            num_guesses: 6,
+            reject_repeats: false,
+            hard_mode: false,
+            invalid_guesses: InvalidGuessPolicy::Reject,
+        }
+    }
+}
+
+impl Profile {
+    /// An engine for `secret` with this profile's rules, accepting guesses
+    /// from `allowed`.
+    pub fn engine<const WORD_LENGTH: usize>(
+        &self,
+        secret: Word<WORD_LENGTH>,
+        allowed: WordList<WORD_LENGTH>,
+    ) -> StandardEngine<WORD_LENGTH> {
+        let mut engine = StandardEngine::new(secret, allowed, self.num_guesses)
+            .on_invalid_guess(self.invalid_guesses);
+        if self.reject_repeats {
+            engine = engine.reject_repeated_guesses();
+        }
+        if self.hard_mode {
+            engine = engine.hard_mode();
+        }
+        engine
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PROFILES
+            .iter()
+            .find(|profile| profile.name == s)
+            .copied()
+            .ok_or_else(|| {
+                let names = PROFILES
+                    .iter()
+                    .map(|profile| profile.name)
+                    .collect::<Vec<_>>();
+                format!("unknown profile {:?} (expected {})", s, names.join(", "))
+            })
+    }
+}
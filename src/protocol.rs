@@ -0,0 +1,254 @@
+//! A line protocol for playing against, or as, another program: one JSON
+//! message per line over any reader and writer, such as a child's stdin and
+//! stdout or a socket. [`JsonEngine`] has the other program score the
+//! solver's guesses, and [`JsonStrategy`] has it make the guesses.
+//!
+//! Every message carries the protocol [`VERSION`] and a `type`:
+//!
+//! ```text
+//! > {"version":1,"type":"score","guess":"crane"}
+//! < {"version":1,"type":"scored","score":"bybbg"}
+//! ```
+//!
+//! Only requests that ask for something get an answer; a strategy told its
+//! score doesn't reply. Multi-board games aren't covered.
+
+use crate::engine::{Engine, GuessError, GuessResult};
+use crate::error::WordleError;
+use crate::strategy::{Strategy, StrategyError};
+use crate::word::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::io::{BufRead, Write};
+
+/// Goes up whenever a change would break a program speaking the old
+/// protocol.
+pub const VERSION: u32 = 1;
+
+/// One line of the protocol.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Message<T> {
+    pub version: u32,
+    #[serde(flatten)]
+    pub body: T,
+}
+
+/// What the solver asks of an external engine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EngineRequest<const WORD_LENGTH: usize> {
+    /// Score `guess`; answered with an [`EngineResponse`].
+    Score { guess: Word<WORD_LENGTH> },
+}
+
+/// How an external engine answers [`EngineRequest::Score`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EngineResponse<const WORD_LENGTH: usize> {
+    Scored {
+        #[serde(with = "serde_score")]
+        score: Score<WORD_LENGTH>,
+    },
+    Won,
+    Lost,
+    /// The guess wasn't accepted, and didn't use up a turn.
+    Rejected,
+    /// The guess wasn't accepted, but still used up a turn.
+    Wasted,
+}
+
+/// What the solver tells, or asks of, an external strategy.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StrategyRequest<const WORD_LENGTH: usize> {
+    /// Make a guess; answered with a [`StrategyResponse`].
+    Guess,
+    /// The last guess scored `score`.
+    Score {
+        #[serde(with = "serde_score")]
+        score: Score<WORD_LENGTH>,
+    },
+    /// `guess` wasn't accepted, and didn't use up a turn.
+    Rejected {
+        guess: Word<WORD_LENGTH>,
+        reason: String,
+    },
+    /// `guess` wasn't accepted, but still used up a turn.
+    Wasted {
+        guess: Word<WORD_LENGTH>,
+        reason: String,
+    },
+    /// The player played `played` instead of the last guess, and the next
+    /// score is for it.
+    Replaced { played: Word<WORD_LENGTH> },
+}
+
+/// How an external strategy answers [`StrategyRequest::Guess`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StrategyResponse<const WORD_LENGTH: usize> {
+    Guess { guess: Word<WORD_LENGTH> },
+}
+
+/// Only the version, to check it before making sense of the rest.
+#[derive(Deserialize)]
+struct Version {
+    version: u32,
+}
+
+/// Both ends of the connection to the other program.
+struct Channel<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> Channel<R, W> {
+    fn send<T: Serialize>(&mut self, body: T) -> Result<(), WordleError> {
+        let message = Message {
+            version: VERSION,
+            body,
+        };
+        let line = serde_json::to_string(&message)
+            .map_err(|error| WordleError::Protocol(error.to_string()))?;
+        writeln!(self.writer, "{}", line)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// The next message, skipping blank lines.
+    fn receive<T: DeserializeOwned>(&mut self) -> Result<T, WordleError> {
+        let mut line = String::new();
+        while line.trim().is_empty() {
+            line.clear();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(WordleError::InputClosed);
+            }
+        }
+        let bad_message = |error: serde_json::Error| {
+            WordleError::Protocol(format!("{} in {:?}", error, line.trim_end()))
+        };
+        let Version { version } = serde_json::from_str(&line).map_err(bad_message)?;
+        if version != VERSION {
+            return Err(WordleError::Protocol(format!(
+                "version {} isn't supported (this build speaks version {})",
+                version, VERSION
+            )));
+        }
+        let message: Message<T> = serde_json::from_str(&line).map_err(bad_message)?;
+        Ok(message.body)
+    }
+}
+
+/// Has another program score the guesses, e.g. a game being played
+/// elsewhere.
+pub struct JsonEngine<R, W> {
+    channel: RefCell<Channel<R, W>>,
+}
+
+impl<R: BufRead, W: Write> JsonEngine<R, W> {
+    /// Sends requests to `writer` and reads the answers from `reader`.
+    pub fn new(reader: R, writer: W) -> Self {
+        Self {
+            channel: RefCell::new(Channel { reader, writer }),
+        }
+    }
+}
+
+impl<R: BufRead, W: Write, const WORD_LENGTH: usize> Engine<WORD_LENGTH> for JsonEngine<R, W> {
+    fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
+        self.try_score_guess(guess)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_score_guess(
+        &self,
+        guess: &Word<WORD_LENGTH>,
+    ) -> Result<GuessResult<WORD_LENGTH>, WordleError> {
+        let mut channel = self.channel.borrow_mut();
+        channel.send(EngineRequest::Score { guess: *guess })?;
+        Ok(match channel.receive()? {
+            EngineResponse::Scored { score } => GuessResult::Continue(score),
+            EngineResponse::Won => GuessResult::Done {
+                won: true,
+                score: Some([LetterScore::RightPlace; WORD_LENGTH]),
+            },
+            EngineResponse::Lost => GuessResult::Done {
+                won: false,
+                score: None,
+            },
+            EngineResponse::Rejected => GuessResult::Rejected(GuessError::NotInWordList(*guess)),
+            EngineResponse::Wasted => GuessResult::Wasted(GuessError::NotInWordList(*guess)),
+        })
+    }
+}
+
+/// Has another program make the guesses. It isn't told when the game ends;
+/// its input just closes when the caller drops the strategy.
+pub struct JsonStrategy<R, W> {
+    channel: Channel<R, W>,
+    /// The first failure to tell the other program a score, reported by the
+    /// next guess since hearing a score can't fail.
+    error: Option<WordleError>,
+}
+
+impl<R: BufRead, W: Write> JsonStrategy<R, W> {
+    /// Sends requests to `writer` and reads the guesses from `reader`.
+    pub fn new(reader: R, writer: W) -> Self {
+        Self {
+            channel: Channel { reader, writer },
+            error: None,
+        }
+    }
+
+    fn notify<const WORD_LENGTH: usize>(&mut self, request: StrategyRequest<WORD_LENGTH>) {
+        if self.error.is_none() {
+            self.error = self.channel.send(request).err();
+        }
+    }
+}
+
+impl<R: BufRead, W: Write, const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for JsonStrategy<R, W> {
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        if let Some(error) = self.error.take() {
+            return Err(error.into());
+        }
+        self.channel.send(StrategyRequest::<WORD_LENGTH>::Guess)?;
+        let StrategyResponse::Guess { guess } = self.channel.receive()?;
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        self.notify(StrategyRequest::Score { score: *score });
+    }
+
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        Ok(self
+            .channel
+            .send(StrategyRequest::<WORD_LENGTH>::Rejected {
+                guess: *guess,
+                reason: error.to_string(),
+            })?)
+    }
+
+    fn guess_wasted(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        Ok(self.channel.send(StrategyRequest::<WORD_LENGTH>::Wasted {
+            guess: *guess,
+            reason: error.to_string(),
+        })?)
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        Ok(self
+            .channel
+            .send(StrategyRequest::<WORD_LENGTH>::Replaced { played: *played })?)
+    }
+}
@@ -0,0 +1,121 @@
+//! Ways of ranking every candidate guess for a position, rather than just
+//! naming a favourite. Rankings can be blended together or swapped between
+//! turns, since a ranker keeps no state of its own.
+
+use crate::advice::Advisor;
+use crate::letter_stats::LetterStats;
+use crate::oracle::Oracle;
+use crate::trace::CandidateStats;
+use crate::word::*;
+use std::collections::HashSet;
+
+pub trait Ranker<const WORD_LENGTH: usize> {
+    /// The words of `guess_list`, best first, given the words that are still
+    /// viable. Rankers that only consider a shortlist leave the rest out.
+    fn rank(
+        &self,
+        guess_list: &WordList<WORD_LENGTH>,
+        viable_words: &WordList<WORD_LENGTH>,
+    ) -> Vec<Word<WORD_LENGTH>>;
+}
+
+impl<R, const WORD_LENGTH: usize> Ranker<WORD_LENGTH> for &R
+where
+    R: Ranker<WORD_LENGTH> + ?Sized,
+{
+    fn rank(
+        &self,
+        guess_list: &WordList<WORD_LENGTH>,
+        viable_words: &WordList<WORD_LENGTH>,
+    ) -> Vec<Word<WORD_LENGTH>> {
+        (**self).rank(guess_list, viable_words)
+    }
+}
+
+impl<const WORD_LENGTH: usize> Ranker<WORD_LENGTH> for Advisor {
+    fn rank(
+        &self,
+        guess_list: &WordList<WORD_LENGTH>,
+        viable_words: &WordList<WORD_LENGTH>,
+    ) -> Vec<Word<WORD_LENGTH>> {
+        let mut candidates = guess_list
+            .0
+            .iter()
+            .map(|word| CandidateStats::new(*word, viable_words))
+            .collect::<Vec<_>>();
+        candidates.sort_by(|a, b| self.compare(b, a));
+        candidates.into_iter().map(|stats| stats.word).collect()
+    }
+}
+
+/// A fast heuristic: favours guesses made of distinct letters that appear in
+/// many of the viable words. It never looks at how a guess splits the viable
+/// words, so it's cheap enough for the first turn of a big list.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LetterFrequency;
+
+impl<const WORD_LENGTH: usize> Ranker<WORD_LENGTH> for LetterFrequency {
+    fn rank(
+        &self,
+        guess_list: &WordList<WORD_LENGTH>,
+        viable_words: &WordList<WORD_LENGTH>,
+    ) -> Vec<Word<WORD_LENGTH>> {
+        let stats = LetterStats::new(viable_words);
+        let mut ranked = guess_list
+            .0
+            .iter()
+            .map(|word| {
+                let coverage = HashSet::<char>::from(word)
+                    .into_iter()
+                    .map(|letter| stats.containing(letter))
+                    .sum::<usize>();
+                (*word, coverage)
+            })
+            .collect::<Vec<_>>();
+        ranked.sort_by_key(|(_, coverage)| std::cmp::Reverse(*coverage));
+        ranked.into_iter().map(|(word, _)| word).collect()
+    }
+}
+
+/// Exhaustive search for the guesses that need the fewest guesses in total
+/// to finish off every viable word. The search only considers the viable
+/// words plus the `shortlist` best guesses by entropy, and is only practical
+/// once a few dozen words are left.
+#[derive(Debug, Clone, Copy)]
+pub struct Exact {
+    pub shortlist: usize,
+}
+
+impl Default for Exact {
+    fn default() -> Self {
+        Self { shortlist: 20 }
+    }
+}
+
+impl<const WORD_LENGTH: usize> Ranker<WORD_LENGTH> for Exact {
+    fn rank(
+        &self,
+        guess_list: &WordList<WORD_LENGTH>,
+        viable_words: &WordList<WORD_LENGTH>,
+    ) -> Vec<Word<WORD_LENGTH>> {
+        let viable = viable_words.0.iter().copied().collect::<HashSet<_>>();
+        let mut extra = 0;
+        let shortlist = Advisor::Entropy
+            .rank(guess_list, viable_words)
+            .into_iter()
+            .filter(|word| {
+                let keep = viable.contains(word) || extra < self.shortlist;
+                extra += !viable.contains(word) as usize;
+                keep
+            })
+            .collect::<Vec<_>>();
+        let mut oracle = Oracle::new(WordList(shortlist.clone()));
+        let mut ranked = shortlist
+            .into_iter()
+            .filter_map(|word| Some((word, oracle.total_guesses_after(&word, viable_words)?)))
+            .collect::<Vec<_>>();
+        // The shortlist is in entropy order, so that breaks ties.
+        ranked.sort_by_key(|(_, total)| *total);
+        ranked.into_iter().map(|(word, _)| word).collect()
+    }
+}
@@ -0,0 +1,297 @@
+//! A finished game as it's stored on disk, one game per file:
+//!
+//! ```text
+//! date: 2022-01-15
+//! puzzle: 210
+//! guesses: 6
+//! crane bybbg
+//! spilt bbgbb
+//! ```
+//!
+//! Header lines are optional; every other line is a guess and its score in
+//! tile shorthand. Timed games add the seconds spent on each guess, as in
+//! `crane bybbg 4.2s`.
+//!
+//! A transcript saves many games in one file: an artifact header, then the
+//! records separated by blank lines.
+
+use crate::artifact::{ArtifactError, ArtifactKind, Header};
+use crate::board::{Board, Row};
+use crate::play::numbered_share_grid;
+use crate::tiles::TileStyle;
+use crate::word::*;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{BufRead, Write};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord<const WORD_LENGTH: usize> {
+    /// Date the game was played, as `YYYY-MM-DD`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub date: Option<String>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub puzzle: Option<u32>,
+    /// Number of guesses the game allowed.
+    pub max_guesses: usize,
+    pub board: Board<WORD_LENGTH>,
+    /// Time spent thinking about each guess, one per row, for timed games;
+    /// empty otherwise.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub think_times: Vec<Duration>,
+}
+
+/// Where a record and the real game's grid part ways.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence<const WORD_LENGTH: usize> {
+    /// Counting from 1.
+    pub row: usize,
+    /// The recorded row, unless the grid has more rows than the record.
+    pub recorded: Option<Row<WORD_LENGTH>>,
+    /// The grid's tiles, unless the record has more rows than the grid.
+    pub tiles: Option<Vec<LetterScore>>,
+}
+
+impl<const WORD_LENGTH: usize> fmt::Display for Divergence<WORD_LENGTH> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tiles =
+            |tiles: &[LetterScore]| tiles.iter().map(|tile| tile.to_char()).collect::<String>();
+        match (&self.recorded, &self.tiles) {
+            (Some(row), Some(grid)) => write!(
+                f,
+                "row {}: {} was entered as {} but the game scored it {}",
+                self.row,
+                row.guess,
+                format_score(&row.score),
+                tiles(grid)
+            ),
+            (Some(row), None) => write!(
+                f,
+                "row {}: {} {} was entered but the game has no such row",
+                self.row,
+                row.guess,
+                format_score(&row.score)
+            ),
+            (None, Some(grid)) => write!(
+                f,
+                "row {}: the game has {} but nothing was entered",
+                self.row,
+                tiles(grid)
+            ),
+            (None, None) => write!(f, "row {}", self.row),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+impl<const WORD_LENGTH: usize> GameRecord<WORD_LENGTH> {
+    pub fn won(&self) -> bool {
+        self.board.is_solved()
+    }
+
+    /// Total thinking time, for timed games.
+    pub fn total_think_time(&self) -> Option<Duration> {
+        (!self.think_times.is_empty()).then(|| self.think_times.iter().sum())
+    }
+
+    /// `YYYY-MM` part of the date, if there is one.
+    pub fn month(&self) -> Option<&str> {
+        self.date.as_deref().and_then(|date| date.get(..7))
+    }
+
+    /// The game's share grid, drawn with `tiles`.
+    pub fn share_grid(&self, tiles: TileStyle) -> String {
+        numbered_share_grid(
+            &self.board,
+            self.puzzle,
+            self.won(),
+            self.max_guesses,
+            0,
+            tiles,
+        )
+    }
+
+    /// The first row where `grid`, the tiles of the real game's rows, differs
+    /// from the record, e.g. a score that was typed in wrong.
+    pub fn first_divergence(&self, grid: &[Vec<LetterScore>]) -> Option<Divergence<WORD_LENGTH>> {
+        let rows = self.board.rows();
+        (0..rows.len().max(grid.len())).find_map(|index| {
+            let recorded = rows.get(index).copied();
+            let tiles = grid.get(index);
+            let same = match (&recorded, tiles) {
+                (Some(row), Some(tiles)) => row.score[..] == tiles[..],
+                _ => false,
+            };
+            (!same).then(|| Divergence {
+                row: index + 1,
+                recorded,
+                tiles: tiles.cloned(),
+            })
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<Self, RecordError> {
+        let mut record = Self {
+            date: None,
+            puzzle: None,
+            max_guesses: 6,
+            board: Board::new(),
+            think_times: Vec::new(),
+        };
+
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| RecordError {
+                line: index + 1,
+                message,
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once(':') {
+                let value = value.trim();
+                match key.trim() {
+                    "date" => record.date = Some(value.to_owned()),
+                    "puzzle" => {
+                        record.puzzle = Some(
+                            value
+                                .parse()
+                                .map_err(|_| error(format!("bad puzzle number {:?}", value)))?,
+                        )
+                    }
+                    "guesses" => {
+                        record.max_guesses = value
+                            .parse()
+                            .map_err(|_| error(format!("bad guess count {:?}", value)))?
+                    }
+                    key => return Err(error(format!("unknown header {:?}", key))),
+                }
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let (guess, score) = match (fields.next(), fields.next()) {
+                (Some(guess), Some(score)) => (guess, score),
+                _ => {
+                    return Err(error(format!(
+                        "expected a guess and a score, got {:?}",
+                        line
+                    )))
+                }
+            };
+            let guess = Word::try_from(guess.to_ascii_lowercase().as_str())
+                .map_err(|_| error(format!("bad guess {:?}", guess)))?;
+            let score =
+                parse_score(score).ok_or_else(|| error(format!("bad score {:?}", score)))?;
+            if let Some(time) = fields.next() {
+                let seconds = time
+                    .strip_suffix('s')
+                    .and_then(|seconds| seconds.parse::<f64>().ok())
+                    .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+                    .ok_or_else(|| error(format!("bad time {:?}", time)))?;
+                if record.think_times.len() != record.board.len() {
+                    return Err(error("only some guesses are timed".to_owned()));
+                }
+                record.think_times.push(Duration::from_secs_f64(seconds));
+            } else if !record.think_times.is_empty() {
+                return Err(error("only some guesses are timed".to_owned()));
+            }
+            record.board.push(guess, score, None);
+        }
+
+        Ok(record)
+    }
+}
+
+impl<const WORD_LENGTH: usize> fmt::Display for GameRecord<WORD_LENGTH> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(date) = &self.date {
+            writeln!(f, "date: {}", date)?;
+        }
+        if let Some(puzzle) = self.puzzle {
+            writeln!(f, "puzzle: {}", puzzle)?;
+        }
+        writeln!(f, "guesses: {}", self.max_guesses)?;
+        for (index, row) in self.board.rows().iter().enumerate() {
+            write!(f, "{} {}", row.guess, format_score(&row.score))?;
+            if let Some(time) = self.think_times.get(index) {
+                write!(f, " {:.1}s", time.as_secs_f64())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `records` as a transcript tied to `word_list`.
+pub fn write_transcript<const WORD_LENGTH: usize>(
+    mut out: impl Write,
+    records: &[GameRecord<WORD_LENGTH>],
+    word_list: &WordList<WORD_LENGTH>,
+) -> std::io::Result<()> {
+    Header::new(ArtifactKind::Transcript, word_list).write(&mut out)?;
+    for record in records {
+        write!(out, "\n{}", record)?;
+    }
+    Ok(())
+}
+
+/// Reads a transcript, refusing one that was written for a different word
+/// list.
+pub fn read_transcript<const WORD_LENGTH: usize>(
+    mut input: impl BufRead,
+    word_list: &WordList<WORD_LENGTH>,
+) -> Result<Vec<GameRecord<WORD_LENGTH>>, ArtifactError> {
+    Header::read(&mut input)?.check(ArtifactKind::Transcript, word_list)?;
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+
+    let mut records = Vec::new();
+    let mut game = String::new();
+    // Line numbers in errors count from the top of the file, header included.
+    let mut first_line = 2;
+    for (index, line) in text.lines().chain(std::iter::once("")).enumerate() {
+        if !line.trim().is_empty() {
+            game.push_str(line);
+            game.push('\n');
+            continue;
+        }
+        if !game.is_empty() {
+            let record = GameRecord::parse(&game).map_err(|error| {
+                ArtifactError::Malformed(format!(
+                    "line {}: {}",
+                    first_line + error.line - 1,
+                    error.message
+                ))
+            })?;
+            records.push(record);
+            game.clear();
+        }
+        first_line = index + 3;
+    }
+    Ok(records)
+}
@@ -0,0 +1,318 @@
+//! The best reply to [`DEFAULT_OPENER`] for every score it can get, worked
+//! out ahead of time for the bundled lists. Choosing the second guess means
+//! scoring every word against hundreds of viable ones, which is the slowest
+//! turn of the game; with these tables the assistant answers at once, with
+//! no cache on disk.
+//!
+//! The tables are what [`best_replies`] gives, and only stand in for it
+//! when the game is exactly the one they were worked out for.
+
+use crate::nyt;
+use crate::strategy::{SimpleStrategy, Strategy, StrategyError, DEFAULT_OPENER};
+use crate::word::*;
+use crate::word_list::WORD_LIST;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+
+/// Replies by score when any word in [`WORD_LIST`] could be the answer.
+pub const WORD_LIST_REPLIES: &[(&str, &str)] = &[
+    ("gggbb", "zombi"),
+    ("ggyyb", "zincy"),
+    ("ggybb", "yirrs"),
+    ("ggbgg", "arise"),
+    ("ggbgb", "zymic"),
+    ("ggbyy", "styes"),
+    ("ggbyb", "pilau"),
+    ("ggbbg", "uteri"),
+    ("ggbby", "ycled"),
+    ("ggbbb", "thali"),
+    ("gygbg", "wifed"),
+    ("gygbb", "tabun"),
+    ("gyyyy", "aeros"),
+    ("gyyyb", "scurf"),
+    ("gyybb", "ungot"),
+    ("gybyy", "viper"),
+    ("gybyb", "unrig"),
+    ("gybbg", "yurts"),
+    ("gybby", "ident"),
+    ("gybbb", "yarta"),
+    ("gbgyy", "zymes"),
+    ("gbgyb", "until"),
+    ("gbgbg", "veldt"),
+    ("gbgby", "aloed"),
+    ("gbgbb", "glint"),
+    ("gbygb", "aviso"),
+    ("gbyyb", "slits"),
+    ("gbyby", "aweto"),
+    ("gbybb", "glint"),
+    ("gbbgg", "urban"),
+    ("gbbgb", "thagi"),
+    ("gbbyg", "zoril"),
+    ("gbbyy", "blins"),
+    ("gbbyb", "liman"),
+    ("gbbbg", "liang"),
+    ("gbbby", "unlid"),
+    ("gbbbb", "until"),
+    ("yggyb", "proas"),
+    ("yggbb", "unmet"),
+    ("ygyyb", "zupas"),
+    ("ygybg", "orate"),
+    ("ygyby", "oread"),
+    ("ygybb", "viand"),
+    ("ygbgg", "zupas"),
+    ("ygbgy", "presa"),
+    ("ygbgb", "fight"),
+    ("ygbyg", "ursae"),
+    ("ygbyy", "zymes"),
+    ("ygbyb", "bundy"),
+    ("ygbbg", "cubit"),
+    ("ygbby", "ducat"),
+    ("ygbbb", "clint"),
+    ("yyggb", "roosa"),
+    ("yygyb", "psora"),
+    ("yygbb", "zizit"),
+    ("yyygb", "zurfs"),
+    ("yyyyg", "soare"),
+    ("yyyyb", "torrs"),
+    ("yyybg", "zupas"),
+    ("yyyby", "yarto"),
+    ("yyybb", "morat"),
+    ("yybgg", "spims"),
+    ("yybgy", "zurfs"),
+    ("yybgb", "yourt"),
+    ("yybyg", "watch"),
+    ("yybyy", "reses"),
+    ("yybyb", "skran"),
+    ("yybbg", "rerig"),
+    ("yybby", "tryer"),
+    ("yybbb", "maria"),
+    ("ybgyg", "zupas"),
+    ("ybgyb", "sisal"),
+    ("ybgbg", "haole"),
+    ("ybgby", "zooea"),
+    ("ybgbb", "lotah"),
+    ("ybygb", "telco"),
+    ("ybyyg", "soave"),
+    ("ybyyy", "zests"),
+    ("ybyyb", "moats"),
+    ("ybybg", "cotan"),
+    ("ybyby", "hoven"),
+    ("ybybb", "colin"),
+    ("ybbgg", "sauls"),
+    ("ybbgy", "shaly"),
+    ("ybbgb", "lathy"),
+    ("ybbyg", "spalt"),
+    ("ybbyy", "malts"),
+    ("ybbyb", "natal"),
+    ("ybbbg", "liang"),
+    ("ybbby", "wedel"),
+    ("ybbbb", "nyala"),
+    ("bgggg", "zebec"),
+    ("bgggb", "typps"),
+    ("bggyy", "froes"),
+    ("bggyb", "whipt"),
+    ("bggbg", "pwned"),
+    ("bggby", "proem"),
+    ("bggbb", "piton"),
+    ("bgyyy", "zygal"),
+    ("bgyyb", "zymic"),
+    ("bgybg", "orgue"),
+    ("bgyby", "ungod"),
+    ("bgybb", "ungot"),
+    ("bgbgg", "pubic"),
+    ("bgbgy", "typic"),
+    ("bgbgb", "yufts"),
+    ("bgbyy", "tweed"),
+    ("bgbyb", "bigot"),
+    ("bgbbg", "cubit"),
+    ("bgbby", "deter"),
+    ("bgbbb", "clint"),
+    ("byggg", "roose"),
+    ("byggb", "roost"),
+    ("bygyg", "witch"),
+    ("bygyy", "shoer"),
+    ("bygyb", "thars"),
+    ("bygbg", "whirl"),
+    ("bygby", "welch"),
+    ("bygbb", "thirl"),
+    ("byygg", "mutch"),
+    ("byygy", "verso"),
+    ("byygb", "wight"),
+    ("byyyg", "soree"),
+    ("byyyy", "torrs"),
+    ("byyyb", "strim"),
+    ("byybg", "burfi"),
+    ("byyby", "pownd"),
+    ("byybb", "curny"),
+    ("bybgg", "rhumb"),
+    ("bybgy", "virtu"),
+    ("bybgb", "witch"),
+    ("bybyg", "virtu"),
+    ("bybyy", "tired"),
+    ("bybyb", "burnt"),
+    ("bybbg", "murti"),
+    ("bybby", "tiled"),
+    ("bybbb", "lurry"),
+    ("bbggg", "wonga"),
+    ("bbggb", "synth"),
+    ("bbgyg", "cloth"),
+    ("bbgyy", "nelis"),
+    ("bbgyb", "polts"),
+    ("bbgbg", "elchi"),
+    ("bbgby", "poncy"),
+    ("bbgbb", "glyph"),
+    ("bbygg", "wussy"),
+    ("bbygy", "yogis"),
+    ("bbygb", "umpty"),
+    ("bbyyg", "synch"),
+    ("bbyyy", "molts"),
+    ("bbyyb", "until"),
+    ("bbybg", "pling"),
+    ("bbyby", "docht"),
+    ("bbybb", "linty"),
+    ("bbbgg", "suets"),
+    ("bbbgy", "thing"),
+    ("bbbgb", "tusks"),
+    ("bbbyg", "spilt"),
+    ("bbbyy", "lenti"),
+    ("bbbyb", "kitul"),
+    ("bbbbg", "until"),
+    ("bbbby", "lindy"),
+    ("bbbbb", "lying"),
+];
+
+/// Replies by score when only the [NYT answers](nyt::ANSWERS) could be.
+pub const NYT_ANSWER_REPLIES: &[(&str, &str)] = &[
+    ("ggbbg", "argue"),
+    ("gybbg", "agree"),
+    ("gybbb", "altar"),
+    ("gbgbg", "zygon"),
+    ("gbbgg", "abase"),
+    ("gbbgb", "abyss"),
+    ("gbbyg", "aside"),
+    ("gbbbg", "wrung"),
+    ("gbbby", "abbey"),
+    ("gbbbb", "zymic"),
+    ("yggbb", "croak"),
+    ("ygbgb", "crass"),
+    ("ygbbg", "tucks"),
+    ("ygbby", "zygon"),
+    ("ygbbb", "zoist"),
+    ("yyyyb", "solar"),
+    ("yyybb", "zombi"),
+    ("yybyb", "zymic"),
+    ("yybby", "zippy"),
+    ("yybbb", "zymic"),
+    ("ybygb", "coast"),
+    ("ybybb", "zoril"),
+    ("ybbgg", "masse"),
+    ("ybbyy", "essay"),
+    ("ybbyb", "zigan"),
+    ("ybbbg", "evade"),
+    ("ybbby", "plead"),
+    ("ybbbb", "zingy"),
+    ("bggbg", "zowee"),
+    ("bggbb", "young"),
+    ("bgyby", "error"),
+    ("bgbgy", "fresh"),
+    ("bgbgb", "zymic"),
+    ("bgbbg", "yumps"),
+    ("bgbby", "greet"),
+    ("bgbbb", "uptak"),
+    ("bygyg", "store"),
+    ("bygby", "wooer"),
+    ("bygbb", "zymic"),
+    ("byyyy", "sower"),
+    ("byybg", "woful"),
+    ("byyby", "zymes"),
+    ("byybb", "wurst"),
+    ("bybgb", "first"),
+    ("bybyg", "zymic"),
+    ("bybyy", "zurfs"),
+    ("bybyb", "zymic"),
+    ("bybby", "unity"),
+    ("bybbb", "blurt"),
+    ("bbggb", "zymes"),
+    ("bbgyb", "zygon"),
+    ("bbgbg", "biome"),
+    ("bbgby", "epoch"),
+    ("bbgbb", "zygal"),
+    ("bbyyg", "solve"),
+    ("bbyyb", "sonic"),
+    ("bbybg", "gouge"),
+    ("bbyby", "zilch"),
+    ("bbybb", "zooid"),
+    ("bbbgy", "flesh"),
+    ("bbbgb", "zymic"),
+    ("bbbyg", "zygon"),
+    ("bbbyy", "zowee"),
+    ("bbbyb", "zygal"),
+    ("bbbbg", "zymic"),
+    ("bbbby", "tholi"),
+    ("bbbbb", "curly"),
+];
+
+/// What [`SimpleStrategy`] guesses second, guessing from `guess_list` with
+/// the answer one of `answer_list`, for each score its opener can get.
+pub fn best_replies<const WORD_LENGTH: usize>(
+    guess_list: &WordList<WORD_LENGTH>,
+    answer_list: &WordList<WORD_LENGTH>,
+) -> Result<Vec<(Score<WORD_LENGTH>, Word<WORD_LENGTH>)>, StrategyError> {
+    let opener =
+        SimpleStrategy::with_guess_list(guess_list.clone(), answer_list.clone()).make_guess()?;
+    let scores = answer_list
+        .0
+        .iter()
+        .map(|answer| answer.evaluate_guess(&opener))
+        .collect::<BTreeSet<_>>();
+    scores
+        .into_iter()
+        .map(|score| {
+            let mut strategy =
+                SimpleStrategy::with_guess_list(guess_list.clone(), answer_list.clone());
+            strategy.ignore_bundled_replies();
+            strategy.make_guess()?;
+            strategy.receive_score(&score);
+            Ok((score, strategy.make_guess()?))
+        })
+        .collect()
+}
+
+/// The bundled reply for a game guessing from `guess_list` whose viable
+/// words are `viable_words`, if that's the answer list of one of the tables
+/// narrowed down by the opener's score.
+pub(crate) fn bundled_reply<const WORD_LENGTH: usize>(
+    guess_list: &WordList<WORD_LENGTH>,
+    viable_words: &WordList<WORD_LENGTH>,
+) -> Option<Word<WORD_LENGTH>> {
+    if !is_list(&guess_list.0, WORD_LIST) {
+        return None;
+    }
+    let opener = Word::try_from(DEFAULT_OPENER).ok()?;
+    let score = viable_words.0.first()?.evaluate_guess(&opener);
+    let fits = |text: &&str| {
+        Word::try_from(*text)
+            .is_ok_and(|word: Word<WORD_LENGTH>| word.is_consistent_with(&opener, &score))
+    };
+    let (_, replies) = [
+        (WORD_LIST, WORD_LIST_REPLIES),
+        (nyt::ANSWERS, NYT_ANSWER_REPLIES),
+    ]
+    .iter()
+    .find(|(answers, _)| {
+        let narrowed = answers.iter().copied().filter(fits).collect::<Vec<_>>();
+        is_list(&viable_words.0, &narrowed)
+    })?;
+    let score = format_score(&score);
+    let (_, reply) = replies.iter().find(|(key, _)| *key == score)?;
+    Word::try_from(*reply).ok()
+}
+
+/// Whether `words` are exactly `list`, in order.
+fn is_list<const WORD_LENGTH: usize>(words: &[Word<WORD_LENGTH>], list: &[&str]) -> bool {
+    words.len() == list.len()
+        && words
+            .iter()
+            .zip(list)
+            .all(|(word, text)| word.0.iter().copied().eq(text.chars()))
+}
@@ -0,0 +1,102 @@
+//! Private leagues' answer schedules: one date and answer per line, e.g.
+//!
+//! ```text
+//! # office league, January
+//! 2022-01-15 crane
+//! 2022-01-16 spilt
+//! ```
+//!
+//! Blank lines and lines starting with `#` are ignored.
+
+use crate::engine::{Engine, GuessResult, StandardEngine};
+use crate::nyt::Date;
+use crate::word::*;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule<const WORD_LENGTH: usize> {
+    answers: BTreeMap<Date, Word<WORD_LENGTH>>,
+}
+
+impl<const WORD_LENGTH: usize> Schedule<WORD_LENGTH> {
+    /// Parses a schedule, checking every answer against `word_list` up front
+    /// so a typo shows up when the file is loaded rather than on the day.
+    pub fn parse(text: &str, word_list: &WordList<WORD_LENGTH>) -> Result<Self, ScheduleError> {
+        let mut answers = BTreeMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| ScheduleError {
+                line: index + 1,
+                message,
+            };
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (date, word) = line
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| error(format!("expected a date and a word, got {:?}", line)))?;
+            let date = date.parse::<Date>().map_err(error)?;
+            let word = word.trim();
+            let answer = Word::try_from(word.to_ascii_lowercase().as_str())
+                .ok()
+                .filter(|answer| word_list.0.contains(answer))
+                .ok_or_else(|| error(format!("{:?} isn't in the dictionary", word)))?;
+            if answers.insert(date, answer).is_some() {
+                return Err(error(format!("{} is scheduled twice", date)));
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn answer(&self, date: Date) -> Option<Word<WORD_LENGTH>> {
+        self.answers.get(&date).copied()
+    }
+
+    /// The last scheduled day, to say how far the schedule runs.
+    pub fn last_date(&self) -> Option<Date> {
+        self.answers.keys().next_back().copied()
+    }
+}
+
+/// Plays the scheduled answer for one day.
+pub struct ScheduleEngine<const WORD_LENGTH: usize> {
+    engine: StandardEngine<WORD_LENGTH>,
+}
+
+impl<const WORD_LENGTH: usize> ScheduleEngine<WORD_LENGTH> {
+    /// An engine for `date`, or `None` if nothing is scheduled that day.
+    pub fn new(
+        schedule: &Schedule<WORD_LENGTH>,
+        date: Date,
+        word_list: WordList<WORD_LENGTH>,
+        num_guesses: usize,
+    ) -> Option<Self> {
+        let secret = schedule.answer(date)?;
+        Some(Self {
+            engine: StandardEngine::new(secret, word_list, num_guesses),
+        })
+    }
+}
+
+impl<const WORD_LENGTH: usize> Engine<WORD_LENGTH> for ScheduleEngine<WORD_LENGTH> {
+    fn score_guess(&self, guess: &Word<WORD_LENGTH>) -> GuessResult<WORD_LENGTH> {
+        self.engine.score_guess(guess)
+    }
+}
@@ -0,0 +1,66 @@
+//! JSON Schemas for the JSON the solver writes, so other programs can rely
+//! on its shape. A schema's version only goes up when a change would break
+//! a reader of the old one; adding an optional field doesn't count.
+
+use std::fmt;
+use std::str::FromStr;
+
+pub const VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schema {
+    /// A candidate guess, as [`crate::trace::CandidateStats`].
+    Suggestion,
+    GameRecord,
+    /// A [`crate::bench::BenchmarkReport`].
+    Benchmark,
+    DecisionTree,
+}
+
+impl Schema {
+    pub const ALL: [Self; 4] = [
+        Self::Suggestion,
+        Self::GameRecord,
+        Self::Benchmark,
+        Self::DecisionTree,
+    ];
+
+    /// The schema document itself.
+    pub fn text(self) -> &'static str {
+        match self {
+            Self::Suggestion => include_str!("../schemas/v1/suggestion.schema.json"),
+            Self::GameRecord => include_str!("../schemas/v1/game-record.schema.json"),
+            Self::Benchmark => include_str!("../schemas/v1/benchmark.schema.json"),
+            Self::DecisionTree => include_str!("../schemas/v1/decision-tree.schema.json"),
+        }
+    }
+}
+
+impl fmt::Display for Schema {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Suggestion => "suggestion",
+            Self::GameRecord => "game-record",
+            Self::Benchmark => "benchmark",
+            Self::DecisionTree => "decision-tree",
+        })
+    }
+}
+
+impl FromStr for Schema {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        Self::ALL
+            .iter()
+            .find(|schema| schema.to_string() == s)
+            .copied()
+            .ok_or_else(|| {
+                let names = Self::ALL
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                format!("unknown schema {:?} (expected {})", s, names.join(", "))
+            })
+    }
+}
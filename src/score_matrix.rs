@@ -0,0 +1,98 @@
+//! Every score a list of guesses can get against a list of secrets, worked
+//! out once up front so strategies can look scores up instead of computing
+//! them again for every candidate on every turn.
+
+use crate::word::*;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Packs a score into a number below `3^WORD_LENGTH`, reading the tiles as
+/// base-3 digits with the first tile lowest. Only fits in a `u8` for words of
+/// up to five letters.
+pub fn pattern_id<const WORD_LENGTH: usize>(score: &Score<WORD_LENGTH>) -> u8 {
+    assert!(
+        WORD_LENGTH <= 5,
+        "scores of {} letters don't fit a u8",
+        WORD_LENGTH
+    );
+    score.iter().rev().fold(0, |id, tile| {
+        id * 3
+            + match tile {
+                LetterScore::Wrong => 0,
+                LetterScore::RightLetter => 1,
+                LetterScore::RightPlace => 2,
+            }
+    })
+}
+
+/// The pattern id of the all-green score.
+pub fn solved_pattern<const WORD_LENGTH: usize>() -> u8 {
+    pattern_id(&[LetterScore::RightPlace; WORD_LENGTH])
+}
+
+pub struct ScoreMatrix<const WORD_LENGTH: usize> {
+    guess_index: HashMap<Word<WORD_LENGTH>, usize>,
+    secret_index: HashMap<Word<WORD_LENGTH>, usize>,
+    secrets: usize,
+    /// Pattern ids, a row of secrets for each guess.
+    patterns: Vec<u8>,
+}
+
+impl<const WORD_LENGTH: usize> ScoreMatrix<WORD_LENGTH> {
+    /// Scores every word in `guesses` against every word in `secrets`. That's
+    /// one byte per pair, so the secrets are best kept to the answer list.
+    pub fn new(guesses: &WordList<WORD_LENGTH>, secrets: &WordList<WORD_LENGTH>) -> Self {
+        let patterns = guesses
+            .0
+            .par_iter()
+            .flat_map_iter(|guess| {
+                secrets
+                    .0
+                    .iter()
+                    .map(move |secret| pattern_id(&secret.evaluate_guess(guess)))
+            })
+            .collect();
+        let index = |list: &WordList<WORD_LENGTH>| {
+            let mut index = HashMap::new();
+            for (position, word) in list.0.iter().enumerate() {
+                index.entry(*word).or_insert(position);
+            }
+            index
+        };
+        Self {
+            guess_index: index(guesses),
+            secret_index: index(secrets),
+            secrets: secrets.0.len(),
+            patterns,
+        }
+    }
+
+    pub fn guess_index(&self, guess: &Word<WORD_LENGTH>) -> Option<usize> {
+        self.guess_index.get(guess).copied()
+    }
+
+    pub fn secret_index(&self, secret: &Word<WORD_LENGTH>) -> Option<usize> {
+        self.secret_index.get(secret).copied()
+    }
+
+    /// The indices of `words` as secrets, or `None` if any of them isn't one.
+    pub fn secret_indices(&self, words: &WordList<WORD_LENGTH>) -> Option<Vec<usize>> {
+        words.0.iter().map(|word| self.secret_index(word)).collect()
+    }
+
+    /// The pattern id of the score the guess at `guess` gets against the
+    /// secret at `secret`.
+    pub fn pattern(&self, guess: usize, secret: usize) -> u8 {
+        self.patterns[guess * self.secrets + secret]
+    }
+
+    /// Number of `secrets` getting each pattern id for the guess at `guess`.
+    pub fn partition_sizes(&self, guess: usize, secrets: &[usize]) -> [usize; 243] {
+        let row = &self.patterns[guess * self.secrets..][..self.secrets];
+        let mut sizes = [0; 243];
+        for secret in secrets {
+            sizes[row[*secret] as usize] += 1;
+        }
+        sizes
+    }
+}
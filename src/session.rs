@@ -0,0 +1,160 @@
+use crate::strategy::{SimpleStrategy, Strategy, StrategyError};
+use crate::word::*;
+use std::convert::TryFrom;
+use std::fmt;
+
+pub const MIN_WORD_LENGTH: usize = 4;
+pub const MAX_WORD_LENGTH: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionError {
+    UnsupportedLength(usize),
+    NoWordsOfLength(usize),
+    NoActiveGame,
+    /// A score was given before the solver suggested a guess to score.
+    NoPendingGuess,
+    InvalidScore(String),
+    /// The solver has nothing left to suggest.
+    GaveUp(StrategyError),
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedLength(length) => write!(
+                f,
+                "word length {} is not supported (expected {}-{})",
+                length, MIN_WORD_LENGTH, MAX_WORD_LENGTH
+            ),
+            Self::NoWordsOfLength(length) => {
+                write!(f, "dictionary has no words of length {}", length)
+            }
+            Self::NoActiveGame => write!(f, "no game in progress"),
+            Self::NoPendingGuess => write!(f, "no suggested guess to score yet"),
+            Self::InvalidScore(score) => write!(f, "invalid score: {:?}", score),
+            Self::GaveUp(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+/// An assistant that can follow games of any supported length without being
+/// rebuilt. Each length gets its own solver, created when a game of that
+/// length starts, and every command is routed to the solver for the game in
+/// progress.
+pub struct MultiLengthSession {
+    dictionary: Vec<String>,
+    current_length: Option<usize>,
+    /// Whether the current game's last suggestion is still waiting for its
+    /// score.
+    pending_guess: bool,
+    four: Option<SimpleStrategy<4>>,
+    five: Option<SimpleStrategy<5>>,
+    six: Option<SimpleStrategy<6>>,
+    seven: Option<SimpleStrategy<7>>,
+    eight: Option<SimpleStrategy<8>>,
+}
+
+/// Runs `$body` with `$solver` bound to the solver for the current game.
+macro_rules! with_solver {
+    ($session:expr, |$solver:ident| $body:expr) => {
+        match $session.current_length {
+            Some(4) => $session.four.as_mut().map(|$solver| $body),
+            Some(5) => $session.five.as_mut().map(|$solver| $body),
+            Some(6) => $session.six.as_mut().map(|$solver| $body),
+            Some(7) => $session.seven.as_mut().map(|$solver| $body),
+            Some(8) => $session.eight.as_mut().map(|$solver| $body),
+            _ => None,
+        }
+        .ok_or(SessionError::NoActiveGame)
+    };
+}
+
+impl MultiLengthSession {
+    pub fn new<I, S>(dictionary: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            dictionary: dictionary
+                .into_iter()
+                .map(|word| word.into().to_ascii_lowercase())
+                .collect(),
+            current_length: None,
+            pending_guess: false,
+            four: None,
+            five: None,
+            six: None,
+            seven: None,
+            eight: None,
+        }
+    }
+
+    pub fn current_length(&self) -> Option<usize> {
+        self.current_length
+    }
+
+    fn solver_for<const WORD_LENGTH: usize>(
+        &self,
+    ) -> Result<SimpleStrategy<WORD_LENGTH>, SessionError> {
+        let word_list = self
+            .dictionary
+            .iter()
+            .filter_map(|word| Word::<WORD_LENGTH>::try_from(word.as_str()).ok())
+            .collect::<WordList<WORD_LENGTH>>();
+        if word_list.0.is_empty() {
+            Err(SessionError::NoWordsOfLength(WORD_LENGTH))
+        } else {
+            Ok(SimpleStrategy::new(word_list))
+        }
+    }
+
+    /// Starts a fresh game of the given length, discarding any earlier game of
+    /// that length. Solvers for other lengths are left untouched.
+    pub fn start_game(&mut self, length: usize) -> Result<(), SessionError> {
+        match length {
+            4 => self.four = Some(self.solver_for()?),
+            5 => self.five = Some(self.solver_for()?),
+            6 => self.six = Some(self.solver_for()?),
+            7 => self.seven = Some(self.solver_for()?),
+            8 => self.eight = Some(self.solver_for()?),
+            _ => return Err(SessionError::UnsupportedLength(length)),
+        }
+        self.current_length = Some(length);
+        self.pending_guess = false;
+        Ok(())
+    }
+
+    /// Asks the current game's solver for its next guess.
+    pub fn suggest(&mut self) -> Result<String, SessionError> {
+        let guess = with_solver!(self, |solver| solver
+            .make_guess()
+            .map(|guess| guess.to_string()))?
+        .map_err(SessionError::GaveUp)?;
+        self.pending_guess = true;
+        Ok(guess)
+    }
+
+    /// Feeds the score of the last suggestion, written as tile shorthand
+    /// (e.g. `"gybbg"`), to the current game's solver. Each suggestion takes
+    /// one score.
+    pub fn record_score(&mut self, score: &str) -> Result<(), SessionError> {
+        if self.current_length.is_some() && !self.pending_guess {
+            return Err(SessionError::NoPendingGuess);
+        }
+        with_solver!(self, |solver| receive_score_str(solver, score))??;
+        self.pending_guess = false;
+        Ok(())
+    }
+}
+
+fn receive_score_str<const WORD_LENGTH: usize>(
+    solver: &mut SimpleStrategy<WORD_LENGTH>,
+    score: &str,
+) -> Result<(), SessionError> {
+    let score = parse_score(score).ok_or_else(|| SessionError::InvalidScore(score.to_owned()))?;
+    solver.receive_score(&score);
+    Ok(())
+}
@@ -0,0 +1,66 @@
+//! Statistics for reading benchmark results, so that a small difference
+//! between two strategies isn't taken for a real one.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::fmt;
+
+/// Resamples drawn for a bootstrap interval by default.
+pub const BOOTSTRAP_RESAMPLES: usize = 1000;
+
+/// A range that likely holds the true value of an estimate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConfidenceInterval {
+    pub low: f64,
+    pub high: f64,
+}
+
+impl ConfidenceInterval {
+    pub fn contains(&self, value: f64) -> bool {
+        self.low <= value && value <= self.high
+    }
+
+    /// Whether the two ranges share any values. If they do, the estimates
+    /// they come from can't be told apart with confidence.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.low <= other.high && other.low <= self.high
+    }
+}
+
+impl fmt::Display for ConfidenceInterval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.3}-{:.3}", self.low, self.high)
+    }
+}
+
+/// A percentile bootstrap interval for the mean of `samples`: the mean is
+/// recomputed over `resamples` samples of the same size drawn with
+/// replacement, and the middle `confidence` share of those means is kept.
+/// The resampling is seeded, so the same samples always give the same
+/// interval. `None` if there are no samples.
+pub fn bootstrap_mean(
+    samples: &[f64],
+    confidence: f64,
+    resamples: usize,
+    seed: u64,
+) -> Option<ConfidenceInterval> {
+    if samples.is_empty() || resamples == 0 {
+        return None;
+    }
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut means = (0..resamples)
+        .map(|_| {
+            let total: f64 = (0..samples.len())
+                .map(|_| samples[rng.gen_range(0..samples.len())])
+                .sum();
+            total / samples.len() as f64
+        })
+        .collect::<Vec<_>>();
+    means.sort_by(f64::total_cmp);
+    let tail = (1.0 - confidence) / 2.0;
+    let at = |share: f64| means[((share * resamples as f64) as usize).min(resamples - 1)];
+    Some(ConfidenceInterval {
+        low: at(tail),
+        high: at(1.0 - tail),
+    })
+}
@@ -1,115 +1,1457 @@
+use crate::adaptive::AdaptiveStrategy;
+use crate::advice::{Advisor, Comparison, Disambiguator};
+use crate::board::Board;
+use crate::display::{renderer_for, Renderer};
+use crate::engine::GuessError;
+use crate::error::{read_stdin_line, WordleError};
+use crate::family::dangerous_family;
+use crate::frequency::Frequencies;
+use crate::letter_stats::LetterStats;
+use crate::metrics::Metrics;
+use crate::optimal::OptimalStrategy;
+use crate::policy::WordPolicy;
+use crate::score_matrix::{solved_pattern, ScoreMatrix};
+use crate::tiles::TileStyle;
+use crate::trace::{
+    diverse, write_bucket_histogram, write_candidate_table, write_eliminated, CandidateStats,
+    TraceSink, DIVERSE_MAX_SHARED,
+};
 use crate::word::*;
-use std::{collections::HashSet, convert::TryInto};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    convert::TryInto,
+    fmt,
+    io::Write,
+    str::FromStr,
+    sync::Arc,
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+/// Why a strategy couldn't come up with a guess, or couldn't carry on after
+/// what the engine made of its last one. A game whose strategy gives up like
+/// this ends as lost, with [`GameTranscript::gave_up`] saying why; only
+/// [`StrategyError::Input`] stops the game with an error instead.
+///
+/// [`GameTranscript::gave_up`]: crate::engine::GameTranscript::gave_up
+#[derive(Debug, Clone)]
+pub enum StrategyError {
+    /// Words could still be the answer, but every one the strategy may play
+    /// has been played already or is ruled out by its guess rules.
+    OutOfCandidates,
+    /// Choosing the guess took longer than the strategy was allowed.
+    BudgetExceeded(Duration),
+    /// No word fits every score so far, so the strategy can't go on with
+    /// this game; usually a score was entered wrong. Start a new strategy
+    /// for the next game.
+    NeedsReset,
+    /// A multi-board engine scored the guess on this many boards, and the
+    /// strategy only plays one.
+    SingleBoard(usize),
+    /// The engine refused the guess, and the strategy can't come up with a
+    /// different one.
+    CantRetry,
+    /// The player played something else than the strategy suggested, and it
+    /// can't follow along.
+    CantFollow,
+    /// The player, or the program the guesses come from, stopped answering
+    /// or said something it shouldn't have.
+    Input(Arc<WordleError>),
+}
+
+impl fmt::Display for StrategyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OutOfCandidates => f.write_str("every word the solver may still guess was tried"),
+            Self::BudgetExceeded(limit) => {
+                write!(f, "the solver took longer than {:?} to guess", limit)
+            }
+            Self::NeedsReset => {
+                f.write_str("no word fits every score so far; was one entered wrong?")
+            }
+            Self::SingleBoard(boards) => {
+                write!(f, "the solver can't play {} boards at once", boards)
+            }
+            Self::CantRetry => f.write_str("the guess was refused and the solver has no other"),
+            Self::CantFollow => f.write_str("the solver can't follow a guess it didn't suggest"),
+            Self::Input(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for StrategyError {}
+
+impl PartialEq for StrategyError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::OutOfCandidates, Self::OutOfCandidates)
+            | (Self::NeedsReset, Self::NeedsReset)
+            | (Self::CantRetry, Self::CantRetry)
+            | (Self::CantFollow, Self::CantFollow) => true,
+            (Self::BudgetExceeded(a), Self::BudgetExceeded(b)) => a == b,
+            (Self::SingleBoard(a), Self::SingleBoard(b)) => a == b,
+            // Input errors can't be compared, only told apart.
+            (Self::Input(a), Self::Input(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for StrategyError {}
+
+impl From<WordleError> for StrategyError {
+    fn from(error: WordleError) -> Self {
+        match error {
+            WordleError::Strategy(error) => error,
+            error => Self::Input(Arc::new(error)),
+        }
+    }
+}
 
 pub trait Strategy<const WORD_LENGTH: usize> {
-    fn make_guess(&mut self) -> Word<WORD_LENGTH>;
+    /// The next guess, or why the strategy can't come up with one.
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError>;
 
     fn receive_score(&mut self, score: &Score<WORD_LENGTH>);
+
+    /// Called with one score per board by a multi-board engine, `None` for
+    /// boards that were already solved. Strategies that only play one board
+    /// keep the default, which gives up with [`StrategyError::SingleBoard`].
+    fn receive_scores(
+        &mut self,
+        scores: &[Option<Score<WORD_LENGTH>>],
+    ) -> Result<(), StrategyError> {
+        Err(StrategyError::SingleBoard(scores.len()))
+    }
+
+    /// Called when the engine refuses `guess` without scoring it; the next
+    /// `make_guess` should come up with something else. Strategies that can't
+    /// retry keep the default, which gives up with
+    /// [`StrategyError::CantRetry`].
+    fn guess_rejected(
+        &mut self,
+        _guess: &Word<WORD_LENGTH>,
+        _error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        Err(StrategyError::CantRetry)
+    }
+
+    /// Called when the engine refuses `guess` but still counts it as a turn.
+    /// Strategies that don't care about the turn keep the default, which
+    /// treats it like any other refusal.
+    fn guess_wasted(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        self.guess_rejected(guess, error)
+    }
+
+    /// Called when the player plays `played` instead of the guess
+    /// `make_guess` just returned; the next score is for `played`. Strategies
+    /// that can't follow along keep the default, which gives up with
+    /// [`StrategyError::CantFollow`].
+    fn guess_replaced(&mut self, _played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        Err(StrategyError::CantFollow)
+    }
+
+    /// Tells the strategy what's known before its first guess: letters the
+    /// engine gave away, guesses already played in a game being resumed, or
+    /// anything else known about the secret. Strategies that can't make use
+    /// of clues keep the default, which ignores them.
+    fn prime(&mut self, _clues: &[Clue<WORD_LENGTH>]) {}
+
+    /// The work that went into the most recent guess, for strategies that
+    /// keep count; the default doesn't.
+    fn last_metrics(&self) -> Option<Metrics> {
+        None
+    }
+}
+
+impl<S, const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for &mut S
+where
+    S: Strategy<WORD_LENGTH> + ?Sized,
+{
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        (**self).make_guess()
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        (**self).receive_score(score)
+    }
+
+    fn receive_scores(
+        &mut self,
+        scores: &[Option<Score<WORD_LENGTH>>],
+    ) -> Result<(), StrategyError> {
+        (**self).receive_scores(scores)
+    }
+
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        (**self).guess_rejected(guess, error)
+    }
+
+    fn guess_wasted(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        (**self).guess_wasted(guess, error)
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        (**self).guess_replaced(played)
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        (**self).prime(clues)
+    }
+
+    fn last_metrics(&self) -> Option<Metrics> {
+        (**self).last_metrics()
+    }
+}
+
+impl<S, const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for Box<S>
+where
+    S: Strategy<WORD_LENGTH> + ?Sized,
+{
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        (**self).make_guess()
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        (**self).receive_score(score)
+    }
+
+    fn receive_scores(
+        &mut self,
+        scores: &[Option<Score<WORD_LENGTH>>],
+    ) -> Result<(), StrategyError> {
+        (**self).receive_scores(scores)
+    }
+
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        (**self).guess_rejected(guess, error)
+    }
+
+    fn guess_wasted(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        (**self).guess_wasted(guess, error)
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        (**self).guess_replaced(played)
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        (**self).prime(clues)
+    }
+
+    fn last_metrics(&self) -> Option<Metrics> {
+        (**self).last_metrics()
+    }
+}
+
+/// Personal or league rules about what may be guessed, applied by strategies
+/// as hard constraints on their own choices.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GuessRules<const WORD_LENGTH: usize> {
+    /// Words never to play, even as the answer.
+    pub never_guess: HashSet<Word<WORD_LENGTH>>,
+    /// The first guess of every game.
+    pub always_open: Option<Word<WORD_LENGTH>>,
+}
+
+impl<const WORD_LENGTH: usize> GuessRules<WORD_LENGTH> {
+    pub fn allows(&self, word: &Word<WORD_LENGTH>) -> bool {
+        !self.never_guess.contains(word)
+    }
+}
+
+/// What a strategy can be relied on for, so frontends can turn down
+/// combinations it doesn't support instead of playing a broken game.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StrategyInfo {
+    /// Can play against a hard-mode engine when wrapped in
+    /// [`HardMode`](crate::hard_mode::HardMode), since it follows along when
+    /// a guess is swapped.
+    pub supports_hard_mode: bool,
+    /// Always makes the same guess in the same position, so one game per
+    /// secret says all there is to know.
+    pub deterministic: bool,
+    /// Too slow to be worth playing without a prebuilt score matrix.
+    pub needs_score_matrix: bool,
+    /// Every guess comes from a heuristic, never a search for the best one.
+    pub approximate: bool,
 }
 
+/// The solvers that can play a game on their own, by the names the command
+/// line knows them by.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Solver {
+    #[default]
+    Simple,
+    Adaptive,
+    Entropy,
+    Optimal,
+}
+
+impl Solver {
+    /// What this kind of solver supports.
+    pub fn info(self) -> StrategyInfo {
+        match self {
+            Self::Simple => SimpleStrategy::<0>::INFO,
+            Self::Adaptive => AdaptiveStrategy::<0>::INFO,
+            Self::Entropy => EntropyStrategy::<0>::INFO,
+            Self::Optimal => OptimalStrategy::<0>::INFO,
+        }
+    }
+
+    /// A fresh solver of this kind, guessing from `word_list` within `rules`.
+    pub fn build<const WORD_LENGTH: usize>(
+        self,
+        word_list: WordList<WORD_LENGTH>,
+        rules: GuessRules<WORD_LENGTH>,
+    ) -> Box<dyn Strategy<WORD_LENGTH>> {
+        self.build_with_answers(word_list.clone(), word_list, rules)
+    }
+
+    /// A fresh solver of this kind, guessing from `guess_list` within
+    /// `rules` and only considering words in `answer_list` as the secret,
+    /// like the NYT's allowed guesses and answers.
+    pub fn build_with_answers<const WORD_LENGTH: usize>(
+        self,
+        guess_list: WordList<WORD_LENGTH>,
+        answer_list: WordList<WORD_LENGTH>,
+        rules: GuessRules<WORD_LENGTH>,
+    ) -> Box<dyn Strategy<WORD_LENGTH>> {
+        match self {
+            Self::Simple => {
+                let mut strategy = SimpleStrategy::with_guess_list(guess_list, answer_list);
+                strategy.set_guess_rules(rules);
+                Box::new(strategy)
+            }
+            Self::Adaptive => {
+                let mut strategy = AdaptiveStrategy::with_guess_list(guess_list, answer_list);
+                strategy.set_guess_rules(rules);
+                Box::new(strategy)
+            }
+            Self::Entropy => {
+                let mut strategy = EntropyStrategy::with_guess_list(guess_list, answer_list);
+                strategy.set_guess_rules(rules);
+                Box::new(strategy)
+            }
+            Self::Optimal => {
+                let mut strategy = OptimalStrategy::with_guess_list(guess_list, answer_list);
+                strategy.set_guess_rules(rules);
+                Box::new(strategy)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Solver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Simple => "simple",
+            Self::Adaptive => "adaptive",
+            Self::Entropy => "entropy",
+            Self::Optimal => "optimal",
+        })
+    }
+}
+
+impl FromStr for Solver {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "simple" => Ok(Self::Simple),
+            "adaptive" => Ok(Self::Adaptive),
+            "entropy" => Ok(Self::Entropy),
+            "optimal" => Ok(Self::Optimal),
+            _ => Err(format!(
+                "unknown strategy {:?} (expected simple, adaptive, entropy or optimal)",
+                s
+            )),
+        }
+    }
+}
+
+/// What [`SimpleStrategy`] opens with unless told otherwise, when it's in
+/// the word list.
+pub const DEFAULT_OPENER: &str = "arose";
+
 pub struct SimpleStrategy<const WORD_LENGTH: usize> {
     word_list: WordList<WORD_LENGTH>,
     viable_words: WordList<WORD_LENGTH>,
     last_guess: Option<Word<WORD_LENGTH>>,
     right_place: HashSet<char>,
+    guessed: HashSet<Word<WORD_LENGTH>>,
     num_guesses: usize,
+    trace: Option<TraceSink>,
+    /// Whether the trace skips candidates too like better ones.
+    diverse_trace: bool,
+    think_budget: Option<Duration>,
+    rules: GuessRules<WORD_LENGTH>,
+    score_matrix: Option<Arc<ScoreMatrix<WORD_LENGTH>>>,
+    /// The first guess, or `None` to work it out like any other.
+    opener: Option<Word<WORD_LENGTH>>,
+    /// Counted up by the scorer, which only borrows the strategy.
+    metrics: Cell<Metrics>,
+    /// Whether to take the second guess from the bundled tables when they
+    /// cover the game.
+    bundled_replies: bool,
 }
 
 impl<const WORD_LENGTH: usize> SimpleStrategy<WORD_LENGTH> {
-    fn set_viable_words(&mut self, viable_words: WordList<WORD_LENGTH>) {
-        self.viable_words = viable_words;
-        // Don't seed with the first guess word b/c we've already done that in
-        // the interactive session
-        self.num_guesses = 1;
-    }
-}
+    pub const INFO: StrategyInfo = StrategyInfo {
+        supports_hard_mode: true,
+        deterministic: true,
+        needs_score_matrix: false,
+        approximate: true,
+    };
 
-impl<const WORD_LENGTH: usize> SimpleStrategy<WORD_LENGTH> {
     pub fn new(word_list: WordList<WORD_LENGTH>) -> Self {
         Self {
+            opener: default_opener(&word_list),
             word_list: word_list.clone(),
             viable_words: word_list,
             last_guess: None,
             right_place: HashSet::new(),
+            guessed: HashSet::new(),
+            num_guesses: 0,
+            trace: None,
+            diverse_trace: false,
+            think_budget: None,
+            rules: GuessRules::default(),
+            score_matrix: None,
+            metrics: Cell::default(),
+            bundled_replies: true,
+        }
+    }
+
+    /// Like [`SimpleStrategy::new`], but only ever guesses words from
+    /// `guess_list` while still considering every word in `answer_list` as a
+    /// possible secret.
+    pub fn with_guess_list(
+        guess_list: WordList<WORD_LENGTH>,
+        answer_list: WordList<WORD_LENGTH>,
+    ) -> Self {
+        Self {
+            opener: default_opener(&guess_list),
+            word_list: guess_list,
+            viable_words: answer_list,
+            last_guess: None,
+            right_place: HashSet::new(),
+            guessed: HashSet::new(),
             num_guesses: 0,
+            trace: None,
+            diverse_trace: false,
+            think_budget: None,
+            rules: GuessRules::default(),
+            score_matrix: None,
+            metrics: Cell::default(),
+            bundled_replies: true,
         }
     }
 
-    fn score(&self, word: &Word<WORD_LENGTH>) -> usize {
-        self.viable_words
+    pub fn set_guess_rules(&mut self, rules: GuessRules<WORD_LENGTH>) {
+        self.rules = rules;
+    }
+
+    /// Opens every game with `opener` instead of [`DEFAULT_OPENER`]. It has
+    /// to be in the word list, or the engine wouldn't take it.
+    pub fn with_opener(
+        mut self,
+        opener: Word<WORD_LENGTH>,
+    ) -> Result<Self, GuessError<WORD_LENGTH>> {
+        if !self.word_list.0.contains(&opener) {
+            return Err(GuessError::NotInWordList(opener));
+        }
+        self.opener = Some(opener);
+        Ok(self)
+    }
+
+    /// Works the first guess out from the word list like any other. That's
+    /// slow for a big list; to only pay for it once over many games, pass
+    /// [`SimpleStrategy::best_opener`] to [`SimpleStrategy::with_opener`]
+    /// instead.
+    pub fn with_computed_opener(mut self) -> Self {
+        self.opener = None;
+        self
+    }
+
+    /// The best first guess by the strategy's own scoring, or
+    /// [`StrategyError::OutOfCandidates`] for an empty word list.
+    pub fn best_opener(&self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        let scorer = self.scorer();
+        self.candidates()
             .0
             .iter()
-            .map(|secret| {
-                let score = secret.evaluate_guess(word);
-                self.viable_words
+            .max_by_key(|word| scorer(word))
+            .copied()
+            .ok_or(StrategyError::OutOfCandidates)
+    }
+
+    /// Writes a table of the top candidates to `sink` every time a guess is
+    /// computed.
+    pub fn set_trace(&mut self, sink: TraceSink) {
+        self.trace = Some(sink);
+    }
+
+    /// Leaves out of the trace any candidate sharing more than
+    /// [`DIVERSE_MAX_SHARED`] letters with one ranked above it, so the table
+    /// shows different alternatives rather than anagrams of the best.
+    pub fn set_diverse_trace(&mut self) {
+        self.diverse_trace = true;
+    }
+
+    /// Caps the time spent choosing each guess. When time runs out the best
+    /// candidate found so far is played; candidates that could be the answer
+    /// are looked at first, so a short budget still gives a sensible guess.
+    pub fn set_think_budget(&mut self, budget: Duration) {
+        self.think_budget = Some(budget);
+    }
+
+    /// Looks scores up in `matrix` instead of computing them. Guesses and
+    /// viable words it doesn't cover are still computed, so it only has to
+    /// cover the words that matter for speed. Share one matrix between games
+    /// rather than building it for each.
+    pub fn set_score_matrix(&mut self, matrix: Arc<ScoreMatrix<WORD_LENGTH>>) {
+        self.score_matrix = Some(matrix);
+    }
+
+    /// The best of `candidates` scored by `deadline`, or `None` if there are
+    /// none.
+    fn best_within(
+        &self,
+        candidates: &WordList<WORD_LENGTH>,
+        deadline: Instant,
+    ) -> Option<Word<WORD_LENGTH>> {
+        let viable = self.viable_words.0.iter().collect::<HashSet<_>>();
+        let (first, rest): (Vec<_>, Vec<_>) =
+            candidates.0.iter().partition(|word| viable.contains(word));
+        let mut best = None;
+        let scorer = self.scorer();
+        for (considered, word) in first.into_iter().chain(rest).enumerate() {
+            // Checking the clock is cheap next to scoring, but not free.
+            if considered % 64 == 0 && best.is_some() && Instant::now() >= deadline {
+                let mut metrics = self.metrics.get();
+                metrics.candidates_skipped = candidates.0.len() - considered;
+                self.metrics.set(metrics);
+                break;
+            }
+            let score = scorer(word);
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((*word, score));
+            }
+        }
+        best.map(|(word, _)| word)
+    }
+
+    /// The fixed first guess, if this is the first turn and there is one.
+    fn opener(&self) -> Option<Word<WORD_LENGTH>> {
+        if self.num_guesses != 0 {
+            return None;
+        }
+        // Our own opener is only played when the rules allow it; otherwise the
+        // first guess is computed like any other.
+        self.rules
+            .always_open
+            .or(self.opener.filter(|opener| self.rules.allows(opener)))
+            // Unless the engine already refused it.
+            .filter(|opener| !self.guessed.contains(opener))
+    }
+
+    /// Always works the second guess out, even when the
+    /// [bundled tables](crate::replies) have it.
+    pub fn ignore_bundled_replies(&mut self) {
+        self.bundled_replies = false;
+    }
+
+    /// The second guess from the bundled tables, if this game is one they
+    /// cover and nothing would make it guess differently.
+    fn bundled_reply(&self) -> Option<Word<WORD_LENGTH>> {
+        let opener: Word<WORD_LENGTH> = DEFAULT_OPENER.try_into().ok()?;
+        if !self.bundled_replies
+            || self.rules != GuessRules::default()
+            || self.guessed.iter().any(|guessed| *guessed != opener)
+        {
+            return None;
+        }
+        crate::replies::bundled_reply(&self.word_list, &self.viable_words)
+    }
+
+    /// The words worth scoring for the next guess.
+    fn candidates(&self) -> WordList<WORD_LENGTH> {
+        let mut candidates = if self.viable_words.0.len() == 1 || self.num_guesses == 9 {
+            self.viable_words.clone()
+        } else {
+            self.word_list.clone()
+        };
+        // Repeating a guess never tells us anything new.
+        let playable =
+            |word: &Word<WORD_LENGTH>| !self.guessed.contains(word) && self.rules.allows(word);
+        candidates.0.retain(playable);
+        if candidates.0.is_empty() {
+            // The only word left is one we may not play; probe instead.
+            candidates = self.word_list.clone();
+            candidates.0.retain(playable);
+        }
+        candidates
+    }
+
+    /// Starts working out the next guess a slice at a time, for callers that
+    /// have to hand control back often, like a browser's main thread.
+    /// Candidates are looked at in the same order as with a think budget, so
+    /// the search can be cut short with [`SuggestSearch::best_so_far`].
+    /// Nothing is committed: the strategy's state is untouched.
+    pub fn start_suggest(&self) -> SuggestSearch<WORD_LENGTH> {
+        let viable = self.viable_words.0.iter().collect::<HashSet<_>>();
+        let (mut order, rest): (Vec<_>, Vec<_>) = self
+            .candidates()
+            .0
+            .into_iter()
+            .partition(|word| viable.contains(word));
+        order.extend(rest);
+        let opener = self.opener();
+        SuggestSearch {
+            viable_words: self.viable_words.clone(),
+            order,
+            next: 0,
+            best: opener.map(|opener| (opener, usize::MAX)),
+            done: opener.is_some(),
+        }
+    }
+
+    fn write_trace(&mut self, candidates: &WordList<WORD_LENGTH>) {
+        if self.trace.is_none() {
+            return;
+        }
+        let mut ranked = {
+            let scorer = self.scorer();
+            candidates
+                .0
+                .iter()
+                .map(|word| (*word, scorer(word)))
+                .collect::<Vec<_>>()
+        };
+        ranked.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        let max_shared = if self.diverse_trace {
+            DIVERSE_MAX_SHARED
+        } else {
+            WORD_LENGTH
+        };
+        let table = diverse(ranked, 10, max_shared)
+            .into_iter()
+            .map(|(word, score)| (CandidateStats::new(word, &self.viable_words), score))
+            .collect::<Vec<_>>();
+        let turn = self.num_guesses + 1;
+        let viable_count = self.viable_words.0.len();
+        if let Some(sink) = self.trace.as_mut() {
+            // Tracing is best-effort; a broken sink shouldn't stop the game.
+            let _ = write_candidate_table(sink, turn, viable_count, &table);
+        }
+    }
+
+    /// Scores guesses for the current viable words, through the score matrix
+    /// if there is one.
+    fn scorer(&self) -> impl Fn(&Word<WORD_LENGTH>) -> usize + '_ {
+        let indexed = self
+            .score_matrix
+            .as_ref()
+            .and_then(|matrix| Some((matrix, matrix.secret_indices(&self.viable_words)?)));
+        move |word| {
+            let mut metrics = self.metrics.get();
+            metrics.candidates_evaluated += 1;
+            let looked_up = indexed
+                .as_ref()
+                .and_then(|(matrix, viable)| Some((matrix, viable, matrix.guess_index(word)?)));
+            let score = match looked_up {
+                Some((matrix, viable, guess)) => {
+                    metrics.cache_hits += viable.len();
+                    let mut sizes = matrix.partition_sizes(guess, viable);
+                    sizes[solved_pattern::<WORD_LENGTH>() as usize] = 0;
+                    sizes.iter().sum::<usize>() - sizes.iter().max().unwrap()
+                }
+                None => {
+                    metrics.scores_computed += self.viable_words.0.len();
+                    worst_case_eliminations(&self.viable_words, word)
+                }
+            };
+            self.metrics.set(metrics);
+            score
+        }
+    }
+}
+
+/// [`DEFAULT_OPENER`], if it's in `word_list`.
+fn default_opener<const WORD_LENGTH: usize>(
+    word_list: &WordList<WORD_LENGTH>,
+) -> Option<Word<WORD_LENGTH>> {
+    let opener: Option<Word<WORD_LENGTH>> = DEFAULT_OPENER.try_into().ok();
+    opener.filter(|opener| word_list.0.contains(opener))
+}
+
+fn worst_case_eliminations<const WORD_LENGTH: usize>(
+    viable_words: &WordList<WORD_LENGTH>,
+    word: &Word<WORD_LENGTH>,
+) -> usize {
+    // For each possible secret, count the other viable words that the
+    // guess would rule out, and take the worst case. A secret rules out
+    // everything outside its own score bucket, so the worst case is the
+    // largest bucket (the guess itself always sits alone in the all-green
+    // bucket).
+    let mut buckets = HashMap::new();
+    let mut others = 0;
+    for viable_word in viable_words.0.iter().filter(|v| *v != word) {
+        others += 1;
+        *buckets.entry(viable_word.evaluate_guess(word)).or_insert(0) += 1;
+    }
+    others - buckets.values().copied().max().unwrap_or(0)
+}
+
+/// A [`SimpleStrategy`] guess being worked out incrementally; see
+/// [`SimpleStrategy::start_suggest`].
+pub struct SuggestSearch<const WORD_LENGTH: usize> {
+    viable_words: WordList<WORD_LENGTH>,
+    /// Candidates in the order they're scored, those that could be the
+    /// answer first.
+    order: Vec<Word<WORD_LENGTH>>,
+    next: usize,
+    best: Option<(Word<WORD_LENGTH>, usize)>,
+    done: bool,
+}
+
+impl<const WORD_LENGTH: usize> SuggestSearch<WORD_LENGTH> {
+    /// Scores candidates for about `budget`, always at least one, and returns
+    /// the guess once every candidate has been scored, or
+    /// [`StrategyError::OutOfCandidates`] if there are none.
+    pub fn poll_suggest(
+        &mut self,
+        budget: Duration,
+    ) -> Result<Option<Word<WORD_LENGTH>>, StrategyError> {
+        let deadline = Instant::now() + budget;
+        while !self.done {
+            let word = *self
+                .order
+                .get(self.next)
+                .ok_or(StrategyError::OutOfCandidates)?;
+            let score = worst_case_eliminations(&self.viable_words, &word);
+            if self.best.is_none_or(|(_, best_score)| score > best_score) {
+                self.best = Some((word, score));
+            }
+            self.next += 1;
+            self.done = self.next == self.order.len();
+            if Instant::now() >= deadline {
+                break;
+            }
+        }
+        if !self.done {
+            return Ok(None);
+        }
+        self.best
+            .map(|(word, _)| Some(word))
+            .ok_or(StrategyError::OutOfCandidates)
+    }
+
+    /// The best guess among the candidates scored so far.
+    pub fn best_so_far(&self) -> Option<Word<WORD_LENGTH>> {
+        self.best.map(|(word, _)| word)
+    }
+
+    /// How many candidates have been scored, out of how many.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.next, self.order.len())
+    }
+}
+
+/// How [`EntropyStrategy`] picks between guesses worth the same.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// The likeliest answer, so a tie can still win the game this turn.
+    #[default]
+    Likeliest,
+    /// Whichever comes first in the guess list, answer or not.
+    First,
+}
+
+/// Picks the guess with the most expected information, in bits, about the
+/// secret, treating every viable word as equally likely. Unlike
+/// [`SimpleStrategy`], which looks at the worst case, every score bucket
+/// counts in proportion to its size. Ties go to guesses that could be the
+/// answer, unless told otherwise with a [`TieBreak`].
+pub struct EntropyStrategy<const WORD_LENGTH: usize> {
+    word_list: WordList<WORD_LENGTH>,
+    viable_words: WordList<WORD_LENGTH>,
+    guessed: HashSet<Word<WORD_LENGTH>>,
+    last_guess: Option<Word<WORD_LENGTH>>,
+    rules: GuessRules<WORD_LENGTH>,
+    frequencies: Option<Arc<Frequencies<WORD_LENGTH>>>,
+    tie_break: TieBreak,
+    last_metrics: Option<Metrics>,
+}
+
+impl<const WORD_LENGTH: usize> EntropyStrategy<WORD_LENGTH> {
+    pub const INFO: StrategyInfo = StrategyInfo {
+        supports_hard_mode: true,
+        deterministic: true,
+        needs_score_matrix: false,
+        approximate: true,
+    };
+
+    pub fn new(word_list: WordList<WORD_LENGTH>) -> Self {
+        Self {
+            viable_words: word_list.clone(),
+            word_list,
+            guessed: HashSet::new(),
+            last_guess: None,
+            rules: GuessRules::default(),
+            frequencies: None,
+            tie_break: TieBreak::default(),
+            last_metrics: None,
+        }
+    }
+
+    /// Like [`EntropyStrategy::new`], but guesses from `guess_list` while only
+    /// considering words in `answer_list` as the secret.
+    pub fn with_guess_list(
+        guess_list: WordList<WORD_LENGTH>,
+        answer_list: WordList<WORD_LENGTH>,
+    ) -> Self {
+        Self {
+            viable_words: answer_list,
+            ..Self::new(guess_list)
+        }
+    }
+
+    pub fn set_guess_rules(&mut self, rules: GuessRules<WORD_LENGTH>) {
+        self.rules = rules;
+    }
+
+    /// Treats common words as likelier secrets than rare ones, going by
+    /// `frequencies`, rather than every viable word as equally likely. Ties
+    /// then go to the likeliest answer.
+    pub fn set_frequencies(&mut self, frequencies: Arc<Frequencies<WORD_LENGTH>>) {
+        self.frequencies = Some(frequencies);
+    }
+
+    pub fn set_tie_break(&mut self, tie_break: TieBreak) {
+        self.tie_break = tie_break;
+    }
+}
+
+impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for EntropyStrategy<WORD_LENGTH> {
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        if self.viable_words.0.is_empty() {
+            return Err(StrategyError::NeedsReset);
+        }
+        let started = Instant::now();
+        let mut metrics = Metrics::default();
+        let guess = match self.rules.always_open {
+            Some(opener) if self.guessed.is_empty() => opener,
+            _ => {
+                let viable = self.viable_words.0.iter().collect::<HashSet<_>>();
+                let candidates = self
+                    .word_list
                     .0
                     .iter()
-                    .filter(|viable_word| {
-                        *viable_word != word && score != viable_word.evaluate_guess(word)
+                    .filter(|word| !self.guessed.contains(word) && self.rules.allows(word))
+                    .collect::<Vec<_>>();
+                metrics.candidates_evaluated = candidates.len();
+                metrics.scores_computed = candidates.len() * self.viable_words.0.len();
+                // How likely the word is to be the answer, for breaking ties.
+                let chance = |word: &Word<WORD_LENGTH>| match &self.frequencies {
+                    _ if self.tie_break == TieBreak::First || !viable.contains(word) => 0.0,
+                    Some(frequencies) => frequencies.weight(word),
+                    None => 1.0,
+                };
+                candidates
+                    .into_iter()
+                    .map(|word| {
+                        let entropy = match &self.frequencies {
+                            Some(frequencies) => frequencies.entropy(&self.viable_words, word),
+                            None => self.viable_words.entropy(word),
+                        };
+                        (*word, entropy, chance(word))
                     })
-                    .count()
-            })
-            .min()
-            .unwrap()
+                    // `max_by` keeps the last of equals; go backwards so the
+                    // earliest word wins exact ties.
+                    .rev()
+                    .max_by(|(_, a, a_chance), (_, b, b_chance)| {
+                        a.total_cmp(b).then(a_chance.total_cmp(b_chance))
+                    })
+                    .ok_or(StrategyError::OutOfCandidates)?
+                    .0
+            }
+        };
+        metrics.think_time = started.elapsed();
+        self.last_metrics = Some(metrics);
+        self.guessed.insert(guess);
+        self.last_guess = Some(guess);
+        tracing::debug!(%guess, candidates_remaining = self.viable_words.0.len(), "guess chosen");
+        Ok(guess)
+    }
+
+    fn last_metrics(&self) -> Option<Metrics> {
+        self.last_metrics
+    }
 
-        // self.viable_bags
-        //     .iter()
-        //     .map(|viable_bag| {
-        //         let inter = bag.intersection(viable_bag);
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        let last_guess = self.last_guess.expect("should've made a guess by now");
+        self.viable_words.retain_viable_words(&last_guess, score);
+        tracing::debug!(
+            score = %format_score(score),
+            candidates_remaining = self.viable_words.0.len(),
+            "score received"
+        );
+    }
 
-        //         if discount_right_place {
-        //             inter.filter(|c| !self.right_place.contains(c)).count()
-        //         } else {
-        //             inter.count()
-        //         }
-        //     })
-        //     .sum()
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        _error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        // The engine never refuses the secret. The word stays guessed so it
+        // isn't suggested again.
+        self.viable_words.0.retain(|word| word != guess);
+        Ok(())
     }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        if let Some(suggested) = self.last_guess.replace(*played) {
+            self.guessed.remove(&suggested);
+        }
+        self.guessed.insert(*played);
+        Ok(())
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.viable_words.retain_fitting(clues);
+        for clue in clues {
+            if let Clue::Scored(guess, _) = clue {
+                self.guessed.insert(*guess);
+            }
+        }
+    }
+}
+
+/// Plays Antiwordle: only guesses words that are consistent with every clue
+/// so far, picking the one expected to leave the most legal guesses for the
+/// following turn.
+pub struct AntiWordleStrategy<const WORD_LENGTH: usize> {
+    viable_words: WordList<WORD_LENGTH>,
+    last_guess: Option<Word<WORD_LENGTH>>,
 }
 
-pub struct StdinGuesser<const WORD_LENGTH: usize>;
+impl<const WORD_LENGTH: usize> AntiWordleStrategy<WORD_LENGTH> {
+    pub fn new(word_list: WordList<WORD_LENGTH>) -> Self {
+        Self {
+            viable_words: word_list,
+            last_guess: None,
+        }
+    }
 
-impl<const WORD_LENGTH: usize> StdinGuesser<WORD_LENGTH> {
-    fn read_guess(&self) -> Option<Word<WORD_LENGTH>> {
-        let mut buffer = String::new();
-        std::io::stdin()
-            .read_line(&mut buffer)
-            .expect("Failed to read line");
+    /// Sum of the squared sizes of the buckets `word` would split the viable
+    /// words into, leaving out the bucket where `word` is the secret. This is
+    /// proportional to the expected number of legal guesses on the next turn.
+    fn survival_score(&self, word: &Word<WORD_LENGTH>) -> usize {
+        let mut buckets = HashMap::new();
+        for secret in self.viable_words.0.iter().filter(|v| *v != word) {
+            *buckets.entry(secret.evaluate_guess(word)).or_insert(0) += 1;
+        }
+        buckets.values().map(|size| size * size).sum()
+    }
+}
 
-        if !buffer.is_ascii() {
-            return None;
+impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for AntiWordleStrategy<WORD_LENGTH> {
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        // The secret is always a legal guess, so this only runs dry if a
+        // score was wrong.
+        let guess = *self
+            .viable_words
+            .0
+            .iter()
+            .max_by_key(|word| self.survival_score(word))
+            .ok_or(StrategyError::NeedsReset)?;
+        self.last_guess = Some(guess);
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        let last_guess = self.last_guess.expect("Should've made a guess by now");
+        self.viable_words.retain_viable_words(&last_guess, score);
+    }
+
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        _error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        // Only viable words are guessed, so dropping it is enough to not
+        // play it again.
+        self.viable_words.0.retain(|word| word != guess);
+        Ok(())
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.viable_words.retain_fitting(clues);
+    }
+}
+
+/// Approximates how a typical person plays: it sticks to words that fit the
+/// clues, keeps green letters where they are, favours words made of common
+/// letters, and now and then plays an arbitrary word instead of a good probe.
+pub struct HumanLikeStrategy<const WORD_LENGTH: usize> {
+    word_list: WordList<WORD_LENGTH>,
+    viable_words: WordList<WORD_LENGTH>,
+    letter_stats: Arc<LetterStats<WORD_LENGTH>>,
+    greens: [Option<char>; WORD_LENGTH],
+    guessed: HashSet<Word<WORD_LENGTH>>,
+    last_guess: Option<Word<WORD_LENGTH>>,
+    mistake_rate: f64,
+    rng: ChaCha8Rng,
+    rules: GuessRules<WORD_LENGTH>,
+}
+
+impl<const WORD_LENGTH: usize> HumanLikeStrategy<WORD_LENGTH> {
+    /// Its slips are seeded, but still random from game to game.
+    pub const INFO: StrategyInfo = StrategyInfo {
+        supports_hard_mode: false,
+        deterministic: false,
+        needs_score_matrix: false,
+        approximate: true,
+    };
+
+    /// `mistake_rate` is the chance, each turn, of playing a random word that
+    /// keeps the greens instead of the most natural viable word. It's clamped
+    /// to between 0 and 1, and NaN counts as 0.
+    pub fn new(word_list: WordList<WORD_LENGTH>, mistake_rate: f64, seed: u64) -> Self {
+        let letter_stats = Arc::new(LetterStats::new(&word_list));
+        Self::with_letter_stats(word_list, letter_stats, mistake_rate, seed)
+    }
+
+    /// Like [`HumanLikeStrategy::new`], taking the letter counts from
+    /// `letter_stats`, which has to be of `word_list`, instead of counting
+    /// again; share one between many games.
+    pub fn with_letter_stats(
+        word_list: WordList<WORD_LENGTH>,
+        letter_stats: Arc<LetterStats<WORD_LENGTH>>,
+        mistake_rate: f64,
+        seed: u64,
+    ) -> Self {
+        Self {
+            letter_stats,
+            word_list: word_list.clone(),
+            viable_words: word_list,
+            greens: [None; WORD_LENGTH],
+            guessed: HashSet::new(),
+            last_guess: None,
+            mistake_rate: if mistake_rate.is_nan() {
+                0.0
+            } else {
+                mistake_rate.clamp(0.0, 1.0)
+            },
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            rules: GuessRules::default(),
+        }
+    }
+
+    /// Only considers words in `answer_list` as the secret.
+    pub(crate) fn set_answer_list(&mut self, answer_list: WordList<WORD_LENGTH>) {
+        self.viable_words = answer_list;
+    }
+
+    pub fn set_guess_rules(&mut self, rules: GuessRules<WORD_LENGTH>) {
+        self.rules = rules;
+    }
+
+    /// How familiar a word feels: the total frequency of its distinct letters.
+    fn commonness(&self, word: &Word<WORD_LENGTH>) -> usize {
+        HashSet::<char>::from(word)
+            .iter()
+            .map(|letter| self.letter_stats.occurrences(*letter))
+            .sum()
+    }
+
+    fn keeps_greens(&self, word: &Word<WORD_LENGTH>) -> bool {
+        word.0
+            .iter()
+            .zip(self.greens.iter())
+            .all(|(letter, green)| green.is_none_or(|green| green == *letter))
+    }
+}
+
+impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for HumanLikeStrategy<WORD_LENGTH> {
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        let opener = self.rules.always_open.filter(|_| self.guessed.is_empty());
+        let guess = if opener.is_some() {
+            opener
+        } else if self.rng.gen_bool(self.mistake_rate) {
+            let candidates = self
+                .word_list
+                .0
+                .iter()
+                .filter(|word| {
+                    self.keeps_greens(word)
+                        && !self.guessed.contains(*word)
+                        && self.rules.allows(word)
+                })
+                .collect::<Vec<_>>();
+            candidates.choose(&mut self.rng).map(|word| **word)
+        } else {
+            None
+        };
+
+        let guess = match guess {
+            Some(guess) => guess,
+            None => {
+                let most_common = |words: &WordList<WORD_LENGTH>| {
+                    words
+                        .0
+                        .iter()
+                        .filter(|word| self.rules.allows(word) && !self.guessed.contains(*word))
+                        .max_by_key(|word| self.commonness(word))
+                        .copied()
+                };
+                // If every viable word is off limits, settle for any word.
+                most_common(&self.viable_words)
+                    .or_else(|| most_common(&self.word_list))
+                    .ok_or(StrategyError::OutOfCandidates)?
+            }
+        };
+        self.last_guess = Some(guess);
+        self.guessed.insert(guess);
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        let last_guess = self.last_guess.expect("Should've made a guess by now");
+        self.viable_words.retain_viable_words(&last_guess, score);
+        for ((green, letter), annotation) in self
+            .greens
+            .iter_mut()
+            .zip(last_guess.0.iter())
+            .zip(score.iter())
+        {
+            if *annotation == LetterScore::RightPlace {
+                *green = Some(*letter);
+            }
+        }
+    }
+
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        _error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        // The engine never refuses the secret. The word stays guessed so it
+        // isn't played again.
+        self.viable_words.0.retain(|word| word != guess);
+        Ok(())
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        if let Some(suggested) = self.last_guess.replace(*played) {
+            self.guessed.remove(&suggested);
+        }
+        self.guessed.insert(*played);
+        Ok(())
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.viable_words.retain_fitting(clues);
+        for clue in clues {
+            match clue {
+                Clue::Scored(guess, score) => {
+                    self.guessed.insert(*guess);
+                    for (i, annotation) in score.iter().enumerate() {
+                        if *annotation == LetterScore::RightPlace {
+                            self.greens[i] = Some(guess.0[i]);
+                        }
+                    }
+                }
+                Clue::Letter(free_letter) => {
+                    self.greens[free_letter.position] = Some(free_letter.letter);
+                }
+            }
+        }
+    }
+}
+
+/// Wraps another strategy and checks that it never plays the same word twice
+/// in a game, which is almost always a bug. Debug builds panic on a repeat;
+/// release builds log it and let the guess through.
+pub struct NoRepeatGuard<S, const WORD_LENGTH: usize> {
+    inner: S,
+    guessed: HashSet<Word<WORD_LENGTH>>,
+    last_guess: Option<Word<WORD_LENGTH>>,
+}
+
+impl<S, const WORD_LENGTH: usize> NoRepeatGuard<S, WORD_LENGTH>
+where
+    S: Strategy<WORD_LENGTH>,
+{
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            guessed: HashSet::new(),
+            last_guess: None,
         }
+    }
 
-        buffer
-            .to_ascii_lowercase()
-            .chars()
-            .filter(|c| matches!(c, 'a'..='z'))
-            .collect::<String>()
-            .as_str()
-            .try_into()
-            .ok()
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for NoRepeatGuard<S, WORD_LENGTH>
+where
+    S: Strategy<WORD_LENGTH>,
+{
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        let guess = self.inner.make_guess()?;
+        if !self.guessed.insert(guess) {
+            if cfg!(debug_assertions) {
+                panic!("strategy repeated guess {}", guess);
+            }
+            tracing::warn!(%guess, "strategy repeated a guess");
+        }
+        self.last_guess = Some(guess);
+        Ok(guess)
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        self.inner.receive_score(score)
+    }
+
+    fn receive_scores(
+        &mut self,
+        scores: &[Option<Score<WORD_LENGTH>>],
+    ) -> Result<(), StrategyError> {
+        self.inner.receive_scores(scores)
+    }
+
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        self.inner.guess_rejected(guess, error)
+    }
+
+    fn guess_wasted(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        self.inner.guess_wasted(guess, error)
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        // The replaced guess was never played, so suggesting it again later
+        // isn't a repeat.
+        if let Some(suggested) = self.last_guess.take() {
+            self.guessed.remove(&suggested);
+        }
+        self.guessed.insert(*played);
+        self.inner.guess_replaced(played)
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        for clue in clues {
+            if let Clue::Scored(guess, _) = clue {
+                self.guessed.insert(*guess);
+            }
+        }
+        self.inner.prime(clues)
+    }
+}
+
+/// Wraps another strategy and gives up with
+/// [`StrategyError::BudgetExceeded`] when it takes longer than its limit to
+/// choose a guess, for hosts that can't wait. Unlike a think budget, which
+/// makes the strategy settle for the best guess so far, a late guess is
+/// thrown away.
+pub struct TimeLimit<S> {
+    inner: S,
+    limit: Duration,
+}
+
+impl<S> TimeLimit<S> {
+    pub fn new(inner: S, limit: Duration) -> Self {
+        Self { inner, limit }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for TimeLimit<S>
+where
+    S: Strategy<WORD_LENGTH>,
+{
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        let started = Instant::now();
+        let guess = self.inner.make_guess()?;
+        if started.elapsed() > self.limit {
+            return Err(StrategyError::BudgetExceeded(self.limit));
+        }
+        Ok(guess)
+    }
+
+    fn last_metrics(&self) -> Option<Metrics> {
+        self.inner.last_metrics()
+    }
+
+    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        self.inner.receive_score(score)
+    }
+
+    fn receive_scores(
+        &mut self,
+        scores: &[Option<Score<WORD_LENGTH>>],
+    ) -> Result<(), StrategyError> {
+        self.inner.receive_scores(scores)
+    }
+
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        self.inner.guess_rejected(guess, error)
+    }
+
+    fn guess_wasted(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        self.inner.guess_wasted(guess, error)
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        self.inner.guess_replaced(played)
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.inner.prime(clues)
+    }
+}
+
+/// A guess read from a line of user input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParsedGuess<const WORD_LENGTH: usize> {
+    /// The line held exactly one word and nothing else.
+    Clean(Word<WORD_LENGTH>),
+    /// A word could be read, but only after dropping characters that aren't
+    /// letters (e.g. "can't" read as "cant"), so the user should confirm it.
+    Stripped(Word<WORD_LENGTH>),
+    Invalid,
+}
+
+fn is_share_tile(c: char) -> bool {
+    matches!(c, '🟩' | '🟨' | '⬛' | '⬜' | '🟧' | '🟦')
+}
+
+/// Reads a guess from a line of input. Runs of share-grid tiles are ignored,
+/// so a whole pasted line like `"CRANE 🟩⬛⬛🟨⬛"` reads as `crane`.
+pub fn parse_guess_line<const WORD_LENGTH: usize>(line: &str) -> ParsedGuess<WORD_LENGTH> {
+    let mut tokens = line
+        .split_whitespace()
+        .filter(|token| !token.chars().all(is_share_tile));
+    let token = match (tokens.next(), tokens.next()) {
+        (Some(token), None) => token.to_lowercase(),
+        _ => return ParsedGuess::Invalid,
+    };
+
+    let letters = token
+        .chars()
+        .filter(char::is_ascii_lowercase)
+        .collect::<String>();
+    match letters.as_str().try_into() {
+        Ok(word) if letters == token => ParsedGuess::Clean(word),
+        Ok(word) => ParsedGuess::Stripped(word),
+        Err(_) => ParsedGuess::Invalid,
+    }
+}
+
+/// Reads the player's guesses from stdin. Drawing the board is left to
+/// whoever owns the guesser.
+#[derive(Default)]
+pub struct StdinGuesser<const WORD_LENGTH: usize> {
+    last_guess: Option<Word<WORD_LENGTH>>,
+}
+
+impl<const WORD_LENGTH: usize> StdinGuesser<WORD_LENGTH> {
+    fn read_guess(&self) -> Result<Option<Word<WORD_LENGTH>>, WordleError> {
+        Ok(match parse_guess_line(&read_stdin_line()?) {
+            ParsedGuess::Clean(word) => Some(word),
+            ParsedGuess::Stripped(word) => {
+                println!("Read that as {}, is that right? [y/n]", word);
+                read_stdin_line()?
+                    .trim()
+                    .eq_ignore_ascii_case("y")
+                    .then_some(word)
+            }
+            ParsedGuess::Invalid => None,
+        })
     }
 }
 
 impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for StdinGuesser<WORD_LENGTH> {
-    fn make_guess(&mut self) -> Word<WORD_LENGTH> {
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
         println!("Enter guess:");
         loop {
-            if let Some(guess) = self.read_guess() {
-                return guess;
+            if let Some(guess) = self.read_guess()? {
+                self.last_guess = Some(guess);
+                return Ok(guess);
             }
             println!("Not valid guess:");
         }
     }
 
-    fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
-        println!("Score was {:?}", score);
+    fn receive_score(&mut self, _score: &Score<WORD_LENGTH>) {}
+
+    fn guess_rejected(
+        &mut self,
+        _guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        println!("Guess rejected: {}", error);
+        Ok(())
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        for clue in clues {
+            if let Clue::Letter(free_letter) = clue {
+                println!("Free letter: {}", free_letter);
+            }
+        }
     }
 }
 
 enum StdinOrAlgo<const WORD_LENGTH: usize> {
     Stdin(StdinGuesser<WORD_LENGTH>),
-    Algo(SimpleStrategy<WORD_LENGTH>),
+    Algo(Box<SimpleStrategy<WORD_LENGTH>>),
 }
 
 impl<const WORD_LENGTH: usize> StdinOrAlgo<WORD_LENGTH> {
@@ -118,11 +1460,30 @@ impl<const WORD_LENGTH: usize> StdinOrAlgo<WORD_LENGTH> {
     }
 }
 
+/// Most scores shown in a turn's histogram; the rest are summed up.
+const HISTOGRAM_ROWS: usize = 12;
+
 pub struct StdinThenSolver<const WORD_LENGTH: usize> {
     word_list: WordList<WORD_LENGTH>,
+    /// The words the solver considers as the secret once it takes over.
+    answers: WordList<WORD_LENGTH>,
     last_guess: Option<Word<WORD_LENGTH>>,
     viable_words: WordList<WORD_LENGTH>,
     strategy: std::cell::RefCell<StdinOrAlgo<WORD_LENGTH>>,
+    trace: Option<TraceSink>,
+    diverse_trace: bool,
+    think_budget: Option<Duration>,
+    advisors: Option<[Advisor; 2]>,
+    histogram: bool,
+    /// How many of the words each score ruled out to name, if they're
+    /// shown at all.
+    eliminated: Option<usize>,
+    /// The score matrix being worked out in the background, for the solver
+    /// once it takes over.
+    score_matrix: Option<JoinHandle<Arc<ScoreMatrix<WORD_LENGTH>>>>,
+    rules: GuessRules<WORD_LENGTH>,
+    board: Board<WORD_LENGTH>,
+    renderer: Box<dyn Renderer<WORD_LENGTH>>,
 }
 
 impl<const WORD_LENGTH: usize> StdinThenSolver<WORD_LENGTH> {
@@ -134,98 +1495,277 @@ impl<const WORD_LENGTH: usize> StdinThenSolver<WORD_LENGTH> {
 impl<const WORD_LENGTH: usize> StdinThenSolver<WORD_LENGTH> {
     pub fn new(word_list: WordList<WORD_LENGTH>) -> Self {
         Self {
-            strategy: std::cell::RefCell::new(StdinOrAlgo::Stdin(StdinGuesser)),
+            strategy: std::cell::RefCell::new(StdinOrAlgo::Stdin(StdinGuesser::default())),
             viable_words: word_list.clone(),
+            answers: word_list.clone(),
             word_list,
             last_guess: None,
+            trace: None,
+            diverse_trace: false,
+            think_budget: None,
+            advisors: None,
+            histogram: false,
+            eliminated: None,
+            score_matrix: None,
+            rules: GuessRules::default(),
+            board: Board::new(),
+            renderer: renderer_for(TileStyle::default()),
         }
     }
 
-    pub fn start_solver(&mut self) {
-        if let StdinOrAlgo::Stdin(_) = self.strategy.replace(StdinOrAlgo::Stdin(StdinGuesser)) {
-            let mut algo = SimpleStrategy::new(self.word_list.clone());
-            let mut viable_words = WordList(Vec::new());
-            std::mem::swap(&mut viable_words, &mut self.viable_words);
-            algo.set_viable_words(viable_words);
-            self.strategy.replace(StdinOrAlgo::Algo(algo));
-        } else {
-            panic!("already started solver")
+    /// After every turn, prints how the guess split the words that were
+    /// viable before it.
+    pub fn show_histogram(&mut self) {
+        self.histogram = true;
+    }
+
+    /// After every turn, lists the words the score ruled out, naming up to
+    /// `max_words` of them.
+    pub fn show_eliminated(&mut self, max_words: usize) {
+        self.eliminated = Some(max_words);
+    }
+
+    /// Starts scoring every word against every other on a background thread
+    /// while the player types their first guesses, so the solver can look
+    /// scores up once it takes over. The solver never waits for it: until
+    /// it's done, scores are worked out as usual. For the full word list
+    /// that's about 170 MB.
+    pub fn warm_up(&mut self) {
+        let word_list = self.word_list.clone();
+        self.score_matrix = Some(std::thread::spawn(move || {
+            Arc::new(ScoreMatrix::new(&word_list, &word_list))
+        }));
+    }
+
+    /// Whether the scoring started by [`StdinThenSolver::warm_up`] is done,
+    /// or there is none.
+    pub fn warmed_up(&self) -> bool {
+        self.score_matrix
+            .as_ref()
+            .is_none_or(|warming| warming.is_finished())
+    }
+
+    /// The score matrix from [`StdinThenSolver::warm_up`], if it's done and
+    /// hasn't been handed over yet.
+    fn take_score_matrix(&mut self) -> Option<Arc<ScoreMatrix<WORD_LENGTH>>> {
+        if !self.score_matrix.as_ref()?.is_finished() {
+            return None;
+        }
+        let warming = self.score_matrix.take()?;
+        Some(
+            warming
+                .join()
+                .unwrap_or_else(|panic| std::panic::resume_unwind(panic)),
+        )
+    }
+
+    /// Narrows the viable words down after `guess` scored `score`, showing
+    /// the histogram first and the words ruled out after if they were asked
+    /// for.
+    fn narrow(&mut self, guess: &Word<WORD_LENGTH>, score: &Score<WORD_LENGTH>) {
+        if self.histogram {
+            // Failing to print a histogram shouldn't end the game.
+            let _ = write_bucket_histogram(
+                &mut std::io::stdout(),
+                guess,
+                &self.viable_words,
+                score,
+                HISTOGRAM_ROWS,
+            );
+        }
+        let eliminated = self.viable_words.eliminate(guess, score);
+        if let Some(max_words) = self.eliminated {
+            let _ = write_eliminated(&mut std::io::stdout(), &eliminated, max_words);
+        }
+    }
+
+    /// How the board is drawn after each score.
+    pub fn set_tile_style(&mut self, tiles: TileStyle) {
+        self.renderer = renderer_for(tiles);
+    }
+
+    /// Draws the board some other way, e.g. as CSV.
+    pub fn set_renderer(&mut self, renderer: Box<dyn Renderer<WORD_LENGTH>>) {
+        self.renderer = renderer;
+    }
+
+    /// Has the solver, once it takes over, trace its candidates to `sink`.
+    pub fn set_trace(&mut self, sink: TraceSink) {
+        self.trace = Some(sink);
+    }
+
+    /// Has the solver's trace keep to candidates unlike each other; see
+    /// [`SimpleStrategy::set_diverse_trace`].
+    pub fn set_diverse_trace(&mut self) {
+        self.diverse_trace = true;
+    }
+
+    /// Rules the solver follows once it takes over.
+    pub fn set_guess_rules(&mut self, rules: GuessRules<WORD_LENGTH>) {
+        self.rules = rules;
+    }
+
+    /// Only considers words `policy` allows as the secret, e.g. no plurals,
+    /// as the NYT curates its answers. They can still be guessed.
+    pub fn set_answer_policy<P>(&mut self, policy: &P)
+    where
+        P: WordPolicy<WORD_LENGTH> + ?Sized,
+    {
+        self.answers = self.answers.filtered(policy);
+        self.viable_words = self.viable_words.filtered(policy);
+    }
+
+    /// Caps the solver's time per guess once it takes over.
+    pub fn set_think_budget(&mut self, budget: Duration) {
+        self.think_budget = Some(budget);
+    }
+
+    /// While the player is still guessing, shows what each of the two
+    /// advisors would play before every guess after the first.
+    pub fn set_advisors(&mut self, advisors: [Advisor; 2]) {
+        self.advisors = Some(advisors);
+    }
+
+    fn print_advice(&self) {
+        // Before any clues every word is viable, and ranking the whole list
+        // against itself takes far too long to wait for.
+        let advisors = match (self.advisors, self.last_guess) {
+            (Some(advisors), Some(_)) => advisors,
+            _ => return,
+        };
+        if let Some(comparison) = Comparison::new(advisors, &self.word_list, &self.viable_words) {
+            print!("Advice:\n{}", comparison);
         }
     }
 
-    fn should_switch_to_solver(&self) -> bool {
+    /// Points out a guess that splits the viable words when they've come
+    /// down to a family only one letter apart, or warns of a large family
+    /// among them while there's still time to probe for it.
+    fn print_family_advice(&self) {
+        // There's nothing to go on before the first guess.
+        if self.last_guess.is_none() {
+            return;
+        }
+        if let Some(disambiguator) = Disambiguator::find(&self.word_list, &self.viable_words) {
+            println!("Tip: {}", disambiguator);
+        } else if let Some(family) = dangerous_family(&self.viable_words) {
+            let letters = family
+                .letters()
+                .iter()
+                .map(char::to_string)
+                .collect::<Vec<_>>();
+            println!(
+                "Watch out: {} of the {} viable words fit {}. Rather than guessing them one at a time, probe for several of {} at once.",
+                family.members.len(),
+                self.viable_words.0.len(),
+                family.pattern(),
+                letters.join("/")
+            );
+        }
+    }
+
+    /// Hands the rest of the game to the solver. It can only take over once.
+    pub fn start_solver(&mut self) -> Result<(), WordleError> {
+        if !self.is_stdin() {
+            return Err(WordleError::SolverAlreadyStarted);
+        }
+        let mut algo =
+            SimpleStrategy::with_guess_list(self.word_list.clone(), self.answers.clone());
+        // The player's turns count as the solver's, so it narrows the words
+        // the same way and doesn't open or repeat a word already played.
+        let played = self
+            .board
+            .rows()
+            .iter()
+            .map(|row| Clue::Scored(row.guess, row.score))
+            .collect::<Vec<_>>();
+        algo.prime(&played);
+        if let Some(sink) = self.trace.take() {
+            algo.set_trace(sink);
+        }
+        if self.diverse_trace {
+            algo.set_diverse_trace();
+        }
+        if let Some(budget) = self.think_budget {
+            algo.set_think_budget(budget);
+        }
+        algo.set_guess_rules(self.rules.clone());
+        self.strategy.replace(StdinOrAlgo::Algo(Box::new(algo)));
+        Ok(())
+    }
+
+    fn should_switch_to_solver(&self) -> Result<bool, WordleError> {
         if self.is_stdin() {
             println!("There are {} viable words remaining\nDo you want to let the solver take over? [y/n]", self.viable_words.0.len());
             loop {
-                let mut buffer = String::new();
-                std::io::stdin()
-                    .read_line(&mut buffer)
-                    .expect("Failed to read line");
-
-                println!("buffer: {:?}", buffer);
-
-                match buffer.trim_end().to_ascii_lowercase().as_str() {
-                    "y" => {
-                        println!("y!");
-                        return true;
-                    }
-                    "n" => {
-                        println!("n!");
-                        return false;
-                    }
-                    _ => {
-                        println!("trying again");
-                    }
+                match read_stdin_line()?.trim_end().to_ascii_lowercase().as_str() {
+                    "y" => return Ok(true),
+                    "n" => return Ok(false),
+                    _ => println!("Please answer y or n:"),
                 }
             }
         } else {
-            false
+            Ok(false)
         }
     }
 }
 
 impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for SimpleStrategy<WORD_LENGTH> {
-    fn make_guess(&mut self) -> Word<WORD_LENGTH> {
-        let guess = if self.num_guesses == 0 {
-            unsafe { std::mem::transmute_copy(&['a', 'r', 'o', 's', 'e']) }
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        if self.viable_words.0.is_empty() {
+            return Err(StrategyError::NeedsReset);
+        }
+        let started = Instant::now();
+        self.metrics.set(Metrics::default());
+        let guess = if let Some(opener) = self.opener() {
+            opener
+        } else if let Some(reply) = self.bundled_reply() {
+            reply
         } else {
-            // let n = self.viable_words.len() / 2;
-            // let dont_discount = self.viable_words.len() == 1 || self.num_guesses == 9;
-            // let guess =
-            *((if self.viable_words.0.len() == 1 || self.num_guesses == 9 {
-                self.viable_words.clone()
+            let candidates = self.candidates();
+            self.write_trace(&candidates);
+            // Only count the work of choosing, not of the trace.
+            self.metrics.set(Metrics::default());
+            let best = if let Some(budget) = self.think_budget {
+                self.best_within(&candidates, Instant::now() + budget)
             } else {
-                self.word_list.clone()
-            })
-            // *self
-            //     .word_list
-            // .clone()
-            .0
-            .iter()
-            .max_by_key(|viable_word| self.score(*viable_word))
-            .unwrap())
-            // .max_by_key(|viable )
-            // .select_nth_unstable_by_key(n, |viable_word| self.score(viable_word))
-            // .1
-            // .iter()
-            // .map(|word| (word,))
-            // .min_by_key(|viable_word| self.score(*viable_word))
-            // .expect("viable words shouldn't be empty")
-            // };
+                let scorer = self.scorer();
+                candidates
+                    .0
+                    .iter()
+                    .max_by_key(|viable_word| scorer(viable_word))
+                    .copied()
+            };
+            best.ok_or(StrategyError::OutOfCandidates)?
         };
+        let mut metrics = self.metrics.get();
+        metrics.think_time = started.elapsed();
+        self.metrics.set(metrics);
+        if let Some(sink) = self.trace.as_mut() {
+            let _ = writeln!(sink, "Chose {}: {}", guess, metrics);
+        }
 
         self.last_guess = Some(guess);
-        println!("Score: {:?}, {:?}", guess, self.score(&guess));
+        self.guessed.insert(guess);
 
         self.num_guesses += 1;
 
-        guess
+        tracing::debug!(%guess, candidates_remaining = self.viable_words.0.len(), "guess chosen");
+        Ok(guess)
+    }
+
+    fn last_metrics(&self) -> Option<Metrics> {
+        self.last_guess.map(|_| self.metrics.get())
     }
 
     fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
-        println!("Score: {:?}", score);
         let last_guess = self.last_guess.expect("Should've made a guess by now");
         self.viable_words.retain_viable_words(&last_guess, score);
+        tracing::debug!(
+            score = %format_score(score),
+            candidates_remaining = self.viable_words.0.len(),
+            "score received"
+        );
 
         last_guess
             .0
@@ -236,39 +1776,149 @@ impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for SimpleStrategy<WORD_LEN
                     self.right_place.insert(*c);
                 }
             });
+    }
+
+    fn guess_rejected(
+        &mut self,
+        _guess: &Word<WORD_LENGTH>,
+        _error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        // The turn doesn't count, but the word stays guessed so it isn't
+        // suggested again.
+        self.num_guesses -= 1;
+        Ok(())
+    }
+
+    fn guess_wasted(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        _error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        // The turn is used up; only the word is ruled out.
+        self.guessed.insert(*guess);
+        Ok(())
+    }
+
+    fn guess_replaced(&mut self, played: &Word<WORD_LENGTH>) -> Result<(), StrategyError> {
+        if let Some(suggested) = self.last_guess.replace(*played) {
+            self.guessed.remove(&suggested);
+        }
+        self.guessed.insert(*played);
+        Ok(())
+    }
 
-        println!("Right places: {:?}", self.right_place);
-        println!("Num viable words left: {:?}", self.viable_words);
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.viable_words.retain_fitting(clues);
+        for clue in clues {
+            match clue {
+                Clue::Scored(guess, score) => {
+                    // Counts as a turn, so the fixed opener isn't played.
+                    self.num_guesses += 1;
+                    self.guessed.insert(*guess);
+                    for (letter, annotation) in guess.0.iter().zip(score.iter()) {
+                        if *annotation == LetterScore::RightPlace {
+                            self.right_place.insert(*letter);
+                        }
+                    }
+                }
+                Clue::Letter(free_letter) => {
+                    self.right_place.insert(free_letter.letter);
+                }
+            }
+        }
     }
 }
 
 impl<const WORD_LENGTH: usize> Strategy<WORD_LENGTH> for StdinThenSolver<WORD_LENGTH> {
-    fn make_guess(&mut self) -> Word<WORD_LENGTH> {
-        if self.should_switch_to_solver() {
-            self.start_solver();
+    fn make_guess(&mut self) -> Result<Word<WORD_LENGTH>, StrategyError> {
+        if self.should_switch_to_solver()? {
+            self.start_solver()?;
         }
+        let score_matrix = if self.is_stdin() {
+            None
+        } else {
+            self.take_score_matrix()
+        };
 
         match &mut *self.strategy.borrow_mut() {
             StdinOrAlgo::Stdin(stdin) => {
-                let guess = stdin.make_guess();
+                self.print_advice();
+                self.print_family_advice();
+                let guess = stdin.make_guess()?;
                 self.last_guess = Some(guess);
-                guess
+                Ok(guess)
             }
             StdinOrAlgo::Algo(ref mut strat) => {
+                if let Some(matrix) = score_matrix {
+                    strat.set_score_matrix(matrix);
+                }
                 println!("Computing...");
-                strat.make_guess()
+                let guess = strat.make_guess()?;
+                self.last_guess = Some(guess);
+                Ok(guess)
             }
         }
     }
 
     fn receive_score(&mut self, score: &Score<WORD_LENGTH>) {
+        let last_guess = self.last_guess.expect("should've made a guess by now");
+        self.narrow(&last_guess, score);
+        self.board
+            .push(last_guess, *score, Some(self.viable_words.0.len()));
         match &mut *self.strategy.borrow_mut() {
-            StdinOrAlgo::Stdin(stdin) => {
-                let last_guess = self.last_guess.expect("should've made a guess by now");
-                self.viable_words.retain_viable_words(&last_guess, &score);
-                stdin.receive_score(score);
-            }
+            StdinOrAlgo::Stdin(stdin) => stdin.receive_score(score),
             StdinOrAlgo::Algo(strat) => strat.receive_score(score),
         }
+        print!("{}", self.renderer.render(&self.board));
+    }
+
+    fn guess_rejected(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        match &mut *self.strategy.borrow_mut() {
+            StdinOrAlgo::Stdin(stdin) => stdin.guess_rejected(guess, error),
+            StdinOrAlgo::Algo(strat) => strat.guess_rejected(guess, error),
+        }
+    }
+
+    fn guess_wasted(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        error: &GuessError<WORD_LENGTH>,
+    ) -> Result<(), StrategyError> {
+        match &mut *self.strategy.borrow_mut() {
+            StdinOrAlgo::Stdin(stdin) => stdin.guess_wasted(guess, error),
+            StdinOrAlgo::Algo(strat) => strat.guess_wasted(guess, error),
+        }
+    }
+
+    fn prime(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.viable_words.retain_fitting(clues);
+        if clues.iter().any(|clue| matches!(clue, Clue::Scored(..))) {
+            // There are clues to advise on from the first guess.
+            self.last_guess = clues.iter().rev().find_map(|clue| match clue {
+                Clue::Scored(guess, _) => Some(*guess),
+                Clue::Letter(_) => None,
+            });
+        }
+        for clue in clues {
+            if let Clue::Scored(guess, score) = clue {
+                self.board.push(*guess, *score, None);
+            }
+        }
+        match &mut *self.strategy.borrow_mut() {
+            StdinOrAlgo::Stdin(stdin) => stdin.prime(clues),
+            StdinOrAlgo::Algo(strat) => strat.prime(clues),
+        }
+        print!("{}", self.renderer.render(&self.board));
+    }
+
+    fn last_metrics(&self) -> Option<Metrics> {
+        match &*self.strategy.borrow() {
+            StdinOrAlgo::Stdin(_) => None,
+            StdinOrAlgo::Algo(strat) => strat.last_metrics(),
+        }
     }
 }
@@ -0,0 +1,160 @@
+//! Ways of drawing tiles, so players who can't tell green from yellow can
+//! still read scores and share grids, and grids survive being pasted where
+//! emoji get stripped.
+
+use crate::dynamic::DynWord;
+use crate::word::*;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TileStyle {
+    /// The usual green, yellow and black squares.
+    #[default]
+    Color,
+    /// The NYT's high-contrast palette: orange for the right place, blue for
+    /// the right letter.
+    HighContrast,
+    /// No colour at all: `[A]` for the right place, `(B)` for the right
+    /// letter and `.C.` for a miss.
+    Symbols,
+    /// Plain ASCII for places that strip emoji: `=` for the right place, `+`
+    /// for the right letter and `-` for a miss.
+    Text,
+}
+
+impl TileStyle {
+    pub const ALL: [Self; 4] = [Self::Color, Self::HighContrast, Self::Symbols, Self::Text];
+
+    /// A tile without its letter, as used in share grids.
+    pub fn tile(self, tile: LetterScore) -> &'static str {
+        use LetterScore::*;
+        match (self, tile) {
+            (Self::Color, RightPlace) => "🟩",
+            (Self::Color, RightLetter) => "🟨",
+            (Self::HighContrast, RightPlace) => "🟧",
+            (Self::HighContrast, RightLetter) => "🟦",
+            (Self::Color | Self::HighContrast, Wrong) => "⬛",
+            (Self::Symbols, RightPlace) => "[#]",
+            (Self::Symbols, RightLetter) => "(#)",
+            (Self::Symbols, Wrong) => ".#.",
+            (Self::Text, RightPlace) => "=",
+            (Self::Text, RightLetter) => "+",
+            (Self::Text, Wrong) => "-",
+        }
+    }
+
+    /// A score without its letters, e.g. `🟩⬛🟨⬛⬛`, `=-+--` or
+    /// `[#] .#. (#) .#. .#.`.
+    pub fn tiles<const WORD_LENGTH: usize>(self, score: &Score<WORD_LENGTH>) -> String {
+        self.tiles_of(score)
+    }
+
+    /// A guess with its score, e.g. `🟩⬛🟨⬛⬛ crane` or
+    /// `[C] .R. (A) .N. .E.`.
+    pub fn row<const WORD_LENGTH: usize>(
+        self,
+        guess: &Word<WORD_LENGTH>,
+        score: &Score<WORD_LENGTH>,
+    ) -> String {
+        self.row_of(&guess.0, score)
+    }
+
+    /// Like [`row`](Self::row), for a word whose length is only known at run
+    /// time.
+    pub fn dyn_row(self, guess: &DynWord, score: &[LetterScore]) -> String {
+        self.row_of(&guess.0, score)
+    }
+
+    fn tiles_of(self, score: &[LetterScore]) -> String {
+        let separator = if self == Self::Symbols { " " } else { "" };
+        score
+            .iter()
+            .map(|tile| self.tile(*tile))
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    fn row_of(self, letters: &[char], score: &[LetterScore]) -> String {
+        match self {
+            Self::Color | Self::HighContrast | Self::Text => {
+                format!(
+                    "{} {}",
+                    self.tiles_of(score),
+                    letters.iter().collect::<String>()
+                )
+            }
+            Self::Symbols => letters
+                .iter()
+                .zip(score)
+                .map(|(letter, tile)| {
+                    let letter = letter.to_ascii_uppercase();
+                    match tile {
+                        LetterScore::RightPlace => format!("[{}]", letter),
+                        LetterScore::RightLetter => format!("({})", letter),
+                        LetterScore::Wrong => format!(".{}.", letter),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+
+    /// Reads a row of share-grid tiles in any style, e.g. from a grid pasted
+    /// by another player.
+    pub fn parse_tiles(line: &str) -> Option<Vec<LetterScore>> {
+        use LetterScore::*;
+        let symbols = line
+            .split_whitespace()
+            .map(|token| match token {
+                "[#]" => Some(RightPlace),
+                "(#)" => Some(RightLetter),
+                ".#." => Some(Wrong),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>();
+        let single_chars = || {
+            line.chars()
+                // Some platforms paste ⬛ with an emoji variation selector.
+                .filter(|c| *c != '\u{fe0f}')
+                .map(|c| match c {
+                    '🟩' | '🟧' | '=' => Some(RightPlace),
+                    '🟨' | '🟦' | '+' => Some(RightLetter),
+                    '⬛' | '⬜' | '-' => Some(Wrong),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+        };
+        symbols
+            .or_else(single_chars)
+            .filter(|tiles: &Vec<_>| !tiles.is_empty())
+    }
+}
+
+impl fmt::Display for TileStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Color => "color",
+            Self::HighContrast => "high-contrast",
+            Self::Symbols => "symbols",
+            Self::Text => "text",
+        })
+    }
+}
+
+impl FromStr for TileStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|style| style.to_string() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown tile style {:?} (expected color, high-contrast, symbols or text)",
+                    s
+                )
+            })
+    }
+}
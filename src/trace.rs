@@ -0,0 +1,196 @@
+//! Verbose "thinking aloud" output showing how a strategy ranked its
+//! candidates each turn.
+
+use crate::word::*;
+use std::io::{self, Write};
+
+/// Where trace output goes: stdout, a log file, or anything else.
+pub type TraceSink = Box<dyn Write + Send>;
+
+/// How a candidate guess would split the words that are still viable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CandidateStats<const WORD_LENGTH: usize> {
+    pub word: Word<WORD_LENGTH>,
+    /// Expected information from the guess, in bits.
+    pub entropy: f64,
+    /// Viable words left in the worst case.
+    pub worst_case: usize,
+    /// Whether the guess could itself be the secret.
+    pub viable: bool,
+}
+
+impl<const WORD_LENGTH: usize> CandidateStats<WORD_LENGTH> {
+    pub fn new(word: Word<WORD_LENGTH>, viable_words: &WordList<WORD_LENGTH>) -> Self {
+        let sizes = viable_words.partition_sizes(&word);
+        let total = viable_words.0.len() as f64;
+        Self {
+            word,
+            entropy: sizes
+                .values()
+                .map(|size| {
+                    let p = *size as f64 / total;
+                    -p * p.log2()
+                })
+                .sum(),
+            worst_case: sizes.values().copied().max().unwrap_or(0),
+            viable: sizes.contains_key(&[LetterScore::RightPlace; WORD_LENGTH]),
+        }
+    }
+}
+
+/// Writes a table of a turn's top candidates, in the order the strategy
+/// ranked them, alongside the strategy's own score for each.
+pub fn write_candidate_table<W, const WORD_LENGTH: usize>(
+    out: &mut W,
+    turn: usize,
+    viable_count: usize,
+    candidates: &[(CandidateStats<WORD_LENGTH>, usize)],
+) -> io::Result<()>
+where
+    W: Write + ?Sized,
+{
+    writeln!(
+        out,
+        "turn {}: {} viable words, top {} candidates",
+        turn,
+        viable_count,
+        candidates.len()
+    )?;
+    writeln!(
+        out,
+        "  {:<width$}  {:>7}  {:>7}  {:>10}  viable",
+        "guess",
+        "score",
+        "entropy",
+        "worst case",
+        width = WORD_LENGTH.max(5)
+    )?;
+    for (stats, score) in candidates {
+        writeln!(
+            out,
+            "  {:<width$}  {:>7}  {:>7.3}  {:>10}  {}",
+            stats.word.to_string(),
+            score,
+            stats.entropy,
+            stats.worst_case,
+            if stats.viable { "yes" } else { "no" },
+            width = WORD_LENGTH.max(5)
+        )?;
+    }
+    out.flush()
+}
+
+/// Most letters two candidates may have in common in a diverse table.
+pub const DIVERSE_MAX_SHARED: usize = 3;
+
+/// The first `count` of `ranked` that share at most `max_shared` letters
+/// with every one picked before them, so a table of alternatives isn't
+/// filled with near-anagrams of the top pick.
+pub fn diverse<T, const WORD_LENGTH: usize>(
+    ranked: impl IntoIterator<Item = (Word<WORD_LENGTH>, T)>,
+    count: usize,
+    max_shared: usize,
+) -> Vec<(Word<WORD_LENGTH>, T)> {
+    let mut picked: Vec<(Word<WORD_LENGTH>, T)> = Vec::new();
+    for (word, score) in ranked {
+        if picked.len() == count {
+            break;
+        }
+        if picked
+            .iter()
+            .all(|(other, _)| word.shared_letters(other) <= max_shared)
+        {
+            picked.push((word, score));
+        }
+    }
+    picked
+}
+
+/// Writes which words a score ruled out, at most `max_words` of them by
+/// name.
+pub fn write_eliminated<W, const WORD_LENGTH: usize>(
+    out: &mut W,
+    eliminated: &[Word<WORD_LENGTH>],
+    max_words: usize,
+) -> io::Result<()>
+where
+    W: Write + ?Sized,
+{
+    let named = eliminated
+        .iter()
+        .take(max_words)
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    write!(
+        out,
+        "Ruled out {} word{}",
+        eliminated.len(),
+        if eliminated.len() == 1 { "" } else { "s" }
+    )?;
+    if !named.is_empty() {
+        write!(out, ": {}", named.join(", "))?;
+    }
+    if eliminated.len() > named.len() && !named.is_empty() {
+        write!(out, " and {} more", eliminated.len() - named.len())?;
+    }
+    writeln!(out)?;
+    out.flush()
+}
+
+/// Writes a histogram of how `guess` split `viable_words`: how many words
+/// fall under each score, largest first, with the score it actually got
+/// marked. Shows at most `max_rows` scores.
+pub fn write_bucket_histogram<W, const WORD_LENGTH: usize>(
+    out: &mut W,
+    guess: &Word<WORD_LENGTH>,
+    viable_words: &WordList<WORD_LENGTH>,
+    score: &Score<WORD_LENGTH>,
+    max_rows: usize,
+) -> io::Result<()>
+where
+    W: Write + ?Sized,
+{
+    const BAR_WIDTH: usize = 40;
+
+    let mut buckets = viable_words
+        .partition_sizes(guess)
+        .into_iter()
+        .collect::<Vec<_>>();
+    buckets.sort_by(|(a_score, a_size), (b_score, b_size)| {
+        b_size.cmp(a_size).then(a_score.cmp(b_score))
+    });
+    writeln!(
+        out,
+        "{} split {} viable words into {} scores:",
+        guess,
+        viable_words.0.len(),
+        buckets.len()
+    )?;
+    let largest = buckets.first().map_or(1, |(_, size)| *size);
+    let shown = buckets
+        .iter()
+        .enumerate()
+        .filter(|(rank, (bucket, _))| *rank < max_rows || bucket == score);
+    let mut hidden = buckets.len();
+    for (_, (bucket, size)) in shown {
+        let line = format!(
+            "  {}  {:>5}  {:<width$}{}",
+            format_score(bucket),
+            size,
+            "#".repeat((size * BAR_WIDTH).div_ceil(largest)),
+            if bucket == score {
+                "  <- this turn"
+            } else {
+                ""
+            },
+            width = BAR_WIDTH
+        );
+        writeln!(out, "{}", line.trim_end())?;
+        hidden -= 1;
+    }
+    if hidden > 0 {
+        writeln!(out, "  ... and {} more", hidden)?;
+    }
+    out.flush()
+}
@@ -1,21 +1,259 @@
 use std::collections::{hash_map::Entry::Occupied, HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum LetterScore {
     RightPlace,
     RightLetter,
     Wrong,
 }
 
+impl LetterScore {
+    /// Parses the single-letter shorthand for a tile: `g`reen, `y`ellow or
+    /// `b`lack, case-insensitively.
+    pub fn from_char(c: char) -> Option<Self> {
+        use LetterScore::*;
+        match c.to_ascii_lowercase() {
+            'g' => Some(RightPlace),
+            'y' => Some(RightLetter),
+            'b' => Some(Wrong),
+            _ => None,
+        }
+    }
+
+    /// The single-letter shorthand for this tile, the inverse of
+    /// [`LetterScore::from_char`].
+    pub fn to_char(self) -> char {
+        match self {
+            LetterScore::RightPlace => 'g',
+            LetterScore::RightLetter => 'y',
+            LetterScore::Wrong => 'b',
+        }
+    }
+}
+
 pub type Score<const N: usize> = [LetterScore; N];
 
+/// Writes a score as tile shorthand, e.g. `"gybbg"`.
+pub fn format_score<const N: usize>(score: &Score<N>) -> String {
+    score
+        .iter()
+        .map(|annotation| annotation.to_char())
+        .collect()
+}
+
+/// Parses a score written as tile shorthand, e.g. `"gybbg"`.
+pub fn parse_score<const N: usize>(s: &str) -> Option<Score<N>> {
+    s.chars()
+        .map(LetterScore::from_char)
+        .collect::<Option<Vec<_>>>()?
+        .try_into()
+        .ok()
+}
+
+/// A letter of the secret given away before the first guess, as some Wordle
+/// variants do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FreeLetter {
+    /// Zero-based position of the letter.
+    pub position: usize,
+    pub letter: char,
+}
+
+impl FreeLetter {
+    /// Whether `word` has the letter in the right place.
+    pub fn fits<const WORD_LENGTH: usize>(&self, word: &Word<WORD_LENGTH>) -> bool {
+        word.0.get(self.position) == Some(&self.letter)
+    }
+}
+
+impl std::fmt::Display for FreeLetter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} in position {}", self.letter, self.position + 1)
+    }
+}
+
+/// Something known about the secret before a strategy makes its first guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Clue<const WORD_LENGTH: usize> {
+    /// A guess already played, e.g. before resuming a game, and its score.
+    Scored(Word<WORD_LENGTH>, Score<WORD_LENGTH>),
+    /// A letter known to be in place, whether given away by the engine or
+    /// known some other way.
+    Letter(FreeLetter),
+}
+
+impl<const WORD_LENGTH: usize> Clue<WORD_LENGTH> {
+    /// Whether `word` could be the secret given this clue.
+    pub fn fits(&self, word: &Word<WORD_LENGTH>) -> bool {
+        match self {
+            Self::Scored(guess, score) => word.is_consistent_with(guess, score),
+            Self::Letter(free_letter) => free_letter.fits(word),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct WordList<const N: usize>(pub Vec<Word<N>>);
 
+/// How [`WordList::merge`] settles words that appear in more than one list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergePolicy {
+    /// Treat `Crane` and `crane` as the same word, keeping the lower-case
+    /// spelling.
+    pub fold_case: bool,
+    pub precedence: Precedence,
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        Self {
+            fold_case: true,
+            precedence: Precedence::Earlier,
+        }
+    }
+}
+
+/// Which list decides where a repeated word goes. Order matters beyond
+/// tidiness: it's part of [`WordList::content_hash`], and strategies break
+/// ties by it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precedence {
+    /// A word stays where the first list to have it put it.
+    Earlier,
+    /// A word moves to where the last list to have it puts it, so a personal
+    /// list layered on top can reorder the official one.
+    Later,
+}
+
 impl<const WORD_LENGTH: usize> WordList<WORD_LENGTH> {
+    /// Identifies the exact contents of the list, in order. It's FNV-1a over
+    /// the letters rather than the standard library's hasher, so it's the same
+    /// on every platform and build and can be saved alongside anything
+    /// computed from the list.
+    pub fn content_hash(&self) -> u64 {
+        self.0
+            .iter()
+            .flat_map(|word| word.0.iter())
+            .fold(0xcbf29ce484222325u64, |hash, c| {
+                (hash ^ *c as u64).wrapping_mul(0x100000001b3)
+            })
+    }
+
+    /// Combines `lists`, in order, into one list without duplicates.
+    pub fn merge(lists: &[Self], policy: MergePolicy) -> Self {
+        let normalize = |word: &Word<WORD_LENGTH>| {
+            if policy.fold_case {
+                Word(word.0.map(|letter| {
+                    let mut lower = letter.to_lowercase();
+                    match (lower.next(), lower.next()) {
+                        (Some(lower), None) => lower,
+                        // Letters like `İ` lower to more than one char, which
+                        // wouldn't fit; leave them be.
+                        _ => letter,
+                    }
+                }))
+            } else {
+                *word
+            }
+        };
+        let words = lists
+            .iter()
+            .flat_map(|list| list.0.iter().map(normalize))
+            .collect::<Vec<_>>();
+        let mut kept = HashSet::new();
+        match policy.precedence {
+            Precedence::Earlier => words
+                .into_iter()
+                .filter(|word| kept.insert(*word))
+                .collect(),
+            Precedence::Later => {
+                let mut merged = words
+                    .into_iter()
+                    .rev()
+                    .filter(|word| kept.insert(*word))
+                    .collect::<Vec<_>>();
+                merged.reverse();
+                Self(merged)
+            }
+        }
+    }
+
     pub fn retain_viable_words(&mut self, guess: &Word<WORD_LENGTH>, score: &Score<WORD_LENGTH>) {
-        self.0.retain(|word| word.evaluate_guess(guess) == *score);
+        self.0.retain(|word| word.is_consistent_with(guess, score));
+    }
+
+    /// Like [`WordList::retain_viable_words`], but returns the words ruled
+    /// out, in list order.
+    pub fn eliminate(
+        &mut self,
+        guess: &Word<WORD_LENGTH>,
+        score: &Score<WORD_LENGTH>,
+    ) -> Vec<Word<WORD_LENGTH>> {
+        let (kept, eliminated) = self
+            .0
+            .iter()
+            .partition(|word| word.is_consistent_with(guess, score));
+        self.0 = kept;
+        eliminated
+    }
+
+    /// Keeps only the words that fit every clue.
+    pub fn retain_fitting(&mut self, clues: &[Clue<WORD_LENGTH>]) {
+        self.0
+            .retain(|word| clues.iter().all(|clue| clue.fits(word)));
+    }
+
+    /// Number of words that would receive each score for `guess`, were they
+    /// the secret.
+    pub fn partition_sizes(&self, guess: &Word<WORD_LENGTH>) -> HashMap<Score<WORD_LENGTH>, usize> {
+        let mut sizes = HashMap::new();
+        for word in &self.0 {
+            *sizes.entry(word.evaluate_guess(guess)).or_insert(0) += 1;
+        }
+        sizes
+    }
+
+    /// Expected information, in bits, revealed by the score for `guess` when
+    /// the secret is drawn uniformly from this list.
+    pub fn entropy(&self, guess: &Word<WORD_LENGTH>) -> f64 {
+        let total = self.0.len() as f64;
+        self.partition_sizes(guess)
+            .values()
+            .map(|size| {
+                let p = *size as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// Splits the words by the score `guess` would receive were each of them
+    /// the secret.
+    pub fn partition(&self, guess: &Word<WORD_LENGTH>) -> HashMap<Score<WORD_LENGTH>, Self> {
+        let mut partition = HashMap::<_, Self>::new();
+        for word in &self.0 {
+            partition
+                .entry(word.evaluate_guess(guess))
+                .or_insert_with(|| Self(Vec::new()))
+                .0
+                .push(*word);
+        }
+        partition
+    }
+
+    /// The score an adversary would answer `guess` with: the one that keeps
+    /// the most words alive, preferring the least revealing score on ties.
+    /// Returns the score together with the words it leaves, or `None` if the
+    /// list is empty.
+    pub fn adversarial_score(
+        &self,
+        guess: &Word<WORD_LENGTH>,
+    ) -> Option<(Score<WORD_LENGTH>, Self)> {
+        self.partition(guess)
+            .into_iter()
+            .max_by_key(|(score, words)| (words.0.len(), *score))
     }
 }
 
@@ -36,44 +274,187 @@ impl<const WORD_LENGTH: usize> std::iter::FromIterator<Word<WORD_LENGTH>>
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Word<const N: usize>(pub [char; N]);
 
 impl<const WORD_LENGTH: usize> Word<WORD_LENGTH> {
+    /// Whether this word could still be the secret after `guess` was scored
+    /// `score`.
+    pub fn is_consistent_with(
+        &self,
+        guess: &Word<WORD_LENGTH>,
+        score: &Score<WORD_LENGTH>,
+    ) -> bool {
+        self.evaluate_guess(guess) == *score
+    }
+
     pub fn evaluate_guess(&self, guess: &Word<WORD_LENGTH>) -> Score<WORD_LENGTH> {
-        use LetterScore::*;
-        let mut score = [Wrong; WORD_LENGTH];
-        let mut unused_letters = HashMap::new();
+        let mut score = [LetterScore::Wrong; WORD_LENGTH];
+        score_letters(&self.0, &guess.0, &mut score);
+        score
+    }
 
-        let remaining_letters = self
-            .0
+    /// How many letters the two words have in common, wherever they are,
+    /// counting a repeated letter as often as both words have it.
+    pub fn shared_letters(&self, other: &Word<WORD_LENGTH>) -> usize {
+        let mut unmatched = other.0.to_vec();
+        self.0
             .iter()
-            .zip(guess.0.iter())
-            .enumerate()
-            .filter_map(|(i, (letter, guess_letter))| {
-                if letter == guess_letter {
-                    score[i] = RightPlace;
-                    None
-                } else {
-                    let counter = unused_letters.entry(letter).or_insert(0);
-                    *counter += 1;
-                    Some((i, guess_letter))
-                }
+            .filter(|letter| {
+                let found = unmatched.iter().position(|other| other == *letter);
+                found.map(|index| unmatched.swap_remove(index)).is_some()
             })
-            .collect::<Vec<_>>();
+            .count()
+    }
+}
 
-        remaining_letters.iter().for_each(|(i, guess_letter)| {
-            if let Occupied(mut entry) = unused_letters.entry(guess_letter) {
-                score[*i] = RightLetter;
-                if *entry.get() == 1 {
-                    entry.remove_entry();
-                } else {
-                    *entry.get_mut() -= 1;
+/// Whether `word` could be the secret given every guess and score in
+/// `history`.
+pub fn is_consistent<const WORD_LENGTH: usize>(
+    word: &Word<WORD_LENGTH>,
+    history: &[(Word<WORD_LENGTH>, Score<WORD_LENGTH>)],
+) -> bool {
+    first_conflict(word, history).is_none()
+}
+
+/// The index of the first clue in `history` that rules `word` out, if any.
+pub fn first_conflict<const WORD_LENGTH: usize>(
+    word: &Word<WORD_LENGTH>,
+    history: &[(Word<WORD_LENGTH>, Score<WORD_LENGTH>)],
+) -> Option<usize> {
+    history
+        .iter()
+        .position(|(guess, score)| !word.is_consistent_with(guess, score))
+}
+
+/// Why `guess` scoring `score` rules `word` out, one reason per tile that
+/// `word` would have scored differently, e.g. `"has too many es for the grey
+/// in position 5"`. Empty if it doesn't.
+pub fn conflict_reasons<const WORD_LENGTH: usize>(
+    word: &Word<WORD_LENGTH>,
+    guess: &Word<WORD_LENGTH>,
+    score: &Score<WORD_LENGTH>,
+) -> Vec<String> {
+    use LetterScore::*;
+    let expected = word.evaluate_guess(guess);
+    (0..WORD_LENGTH)
+        .filter(|&i| expected[i] != score[i])
+        .map(|i| {
+            let (letter, position) = (guess.0[i], i + 1);
+            match score[i] {
+                RightPlace => format!(
+                    "doesn't have {} in position {}, which was green",
+                    letter, position
+                ),
+                RightLetter if word.0[i] == letter => {
+                    format!("has {} in position {}, which was yellow", letter, position)
                 }
+                RightLetter => format!(
+                    "doesn't have enough {}s for the yellow in position {}",
+                    letter, position
+                ),
+                Wrong => format!(
+                    "has too many {}s for the grey in position {}",
+                    letter, position
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Scores `guess` against `secret` into `score`; all three must be the same
+/// length.
+pub(crate) fn score_letters(secret: &[char], guess: &[char], score: &mut [LetterScore]) {
+    use LetterScore::*;
+    let mut unused_letters = HashMap::new();
+
+    let remaining_letters = secret
+        .iter()
+        .zip(guess.iter())
+        .enumerate()
+        .filter_map(|(i, (letter, guess_letter))| {
+            if letter == guess_letter {
+                score[i] = RightPlace;
+                None
+            } else {
+                let counter = unused_letters.entry(letter).or_insert(0);
+                *counter += 1;
+                Some((i, guess_letter))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    remaining_letters.iter().for_each(|(i, guess_letter)| {
+        if let Occupied(mut entry) = unused_letters.entry(guess_letter) {
+            score[*i] = RightLetter;
+            if *entry.get() == 1 {
+                entry.remove_entry();
+            } else {
+                *entry.get_mut() -= 1;
             }
+        }
+    });
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreError {
+    LengthMismatch { secret: usize, guess: usize },
+    Empty,
+    NotALetter(char),
+}
+
+impl std::fmt::Display for ScoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::LengthMismatch { secret, guess } => write!(
+                f,
+                "the secret has {} letters but the guess has {}",
+                secret, guess
+            ),
+            Self::Empty => write!(f, "words can't be empty"),
+            Self::NotALetter(c) => write!(f, "{:?} isn't a letter", c),
+        }
+    }
+}
+
+impl std::error::Error for ScoreError {}
+
+/// Like [`score`], but as typed tiles.
+pub fn score_tiles(secret: &str, guess: &str) -> Result<Vec<LetterScore>, ScoreError> {
+    let letters = |word: &str| {
+        word.chars()
+            .map(|c| {
+                if c.is_alphabetic() {
+                    Ok(c.to_lowercase().next().unwrap_or(c))
+                } else {
+                    Err(ScoreError::NotALetter(c))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let (secret, guess) = (letters(secret)?, letters(guess)?);
+    if secret.len() != guess.len() {
+        return Err(ScoreError::LengthMismatch {
+            secret: secret.len(),
+            guess: guess.len(),
         });
-        score
     }
+    if secret.is_empty() {
+        return Err(ScoreError::Empty);
+    }
+    let mut score = vec![LetterScore::Wrong; secret.len()];
+    score_letters(&secret, &guess, &mut score);
+    Ok(score)
+}
+
+/// Scores `guess` against `secret` exactly as the game does, as tile
+/// shorthand: `score("crane", "caper") == Ok("gybyy".into())`. Words of any
+/// length work, and letter case doesn't matter.
+pub fn score(secret: &str, guess: &str) -> Result<String, ScoreError> {
+    Ok(score_tiles(secret, guess)?
+        .into_iter()
+        .map(LetterScore::to_char)
+        .collect())
 }
 
 impl<const WORD_LENGTH: usize> From<&Word<WORD_LENGTH>> for HashSet<char> {
@@ -82,13 +463,69 @@ impl<const WORD_LENGTH: usize> From<&Word<WORD_LENGTH>> for HashSet<char> {
     }
 }
 
+/// Why a string isn't a word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordError {
+    Length {
+        expected: usize,
+        found: usize,
+    },
+    /// A character, as the reader sees it, that has no single-`char` form,
+    /// like a letter with two accents or an emoji.
+    NotALetter(String),
+}
+
+impl std::fmt::Display for WordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Length { expected, found } => {
+                write!(f, "expected {} letters, got {}", expected, found)
+            }
+            Self::NotALetter(text) => write!(f, "{:?} isn't a single letter", text),
+        }
+    }
+}
+
+impl std::error::Error for WordError {}
+
 impl<const WORD_LENGTH: usize> TryFrom<&str> for Word<WORD_LENGTH> {
-    type Error = <[char; WORD_LENGTH] as TryFrom<&'static [char]>>::Error;
+    type Error = WordError;
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        Ok(Word(s.chars().collect::<Vec<_>>().as_slice().try_into()?))
+        let letters = letters(s)?;
+        let found = letters.len();
+        letters.try_into().map(Word).map_err(|_| WordError::Length {
+            expected: WORD_LENGTH,
+            found,
+        })
     }
 }
 
+/// The letters of `s`, one per `char`.
+#[cfg(not(feature = "unicode"))]
+pub(crate) fn letters(s: &str) -> Result<Vec<char>, WordError> {
+    Ok(s.chars().collect())
+}
+
+/// The letters of `s`, one per grapheme after NFC normalization, so an `ñ`
+/// typed as an `n` and a combining tilde is the same one letter as a
+/// precomposed `ñ`.
+#[cfg(feature = "unicode")]
+pub(crate) fn letters(s: &str) -> Result<Vec<char>, WordError> {
+    use unicode_normalization::UnicodeNormalization;
+    use unicode_segmentation::UnicodeSegmentation;
+    let normalized = s.nfc().collect::<String>();
+    normalized
+        .graphemes(true)
+        .map(|grapheme| {
+            let mut chars = grapheme.chars();
+            match (chars.next(), chars.next()) {
+                (Some(letter), None) => Ok(letter),
+                _ => Err(WordError::NotALetter(grapheme.to_owned())),
+            }
+        })
+        .collect()
+}
+
 impl<const WORD_LENGTH: usize> From<Word<WORD_LENGTH>> for String {
     fn from(word: Word<WORD_LENGTH>) -> Self {
         word.0.iter().collect()
@@ -97,6 +534,72 @@ impl<const WORD_LENGTH: usize> From<Word<WORD_LENGTH>> for String {
 
 impl<const WORD_LENGTH: usize> std::fmt::Display for Word<WORD_LENGTH> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", <Self as Into<String>>::into(self.clone()))
+        write!(f, "{}", <Self as Into<String>>::into(*self))
+    }
+}
+
+/// Words are written as plain strings, e.g. `"crane"`.
+#[cfg(feature = "serde")]
+impl<const WORD_LENGTH: usize> serde::Serialize for Word<WORD_LENGTH> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const WORD_LENGTH: usize> serde::Deserialize<'de> for Word<WORD_LENGTH> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Word::try_from(text.as_str()).map_err(|_| {
+            serde::de::Error::custom(format!(
+                "expected a word of {} letters, got {:?}",
+                WORD_LENGTH, text
+            ))
+        })
+    }
+}
+
+/// Tiles are written in shorthand, e.g. `"g"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for LetterScore {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_char(self.to_char())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for LetterScore {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let c = char::deserialize(deserializer)?;
+        LetterScore::from_char(c)
+            .ok_or_else(|| serde::de::Error::custom(format!("expected g, y or b, got {:?}", c)))
+    }
+}
+
+/// Writes a [`Score`] in tile shorthand, e.g. `"gybbg"`, for fields marked
+/// `#[serde(with = "serde_score")]`. A score is just an array, so it can't
+/// implement the serde traits itself.
+#[cfg(feature = "serde")]
+pub mod serde_score {
+    use super::*;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        score: &Score<N>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_score(score))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<Score<N>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        parse_score(&text).ok_or_else(|| {
+            serde::de::Error::custom(format!(
+                "expected a score of {} tiles from g, y and b, got {:?}",
+                N, text
+            ))
+        })
     }
 }
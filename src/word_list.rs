@@ -1,4 +1,4 @@
-﻿pub const WORD_LIST: &'static [&'static str] = &[
+﻿pub const WORD_LIST: &[&str] = &[
     "aahed", "aband", "abele", "abide", "aboil", "absey", "accas", "acidy", "acold", "acorn",
     "acrid", "adbot", "adits", "admin", "aduki", "aeons", "afrit", "agger", "agita", "agood",
     "ahigh", "aidoi", "aioli", "aizle", "akkas", "alaps", "aldol", "alert", "algas", "alkie",